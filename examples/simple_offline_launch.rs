@@ -1,5 +1,3 @@
-use std::process::Command;
-
 use mc_launcher_core::prelude::*;
 
 fn main() -> mc_launcher_core::Result<()> {
@@ -9,18 +7,18 @@ fn main() -> mc_launcher_core::Result<()> {
     let install = launcher.install(InstallRequest::vanilla("1.20.1"))?;
     let version_json = launcher.load_version(&install.version_id)?;
 
-    let command = launcher.build_launch_command_from_version(
+    let mut game = launcher.launch(
         &version_json,
         LaunchOptions {
             account: Account::offline("Steve"),
+            java_executable: install.java_executable,
             ..Default::default()
         },
     )?;
-
-    let mut child = Command::new(&command.executable)
-        .args(&command.args)
-        .current_dir(&command.working_dir)
-        .spawn()?;
-    child.wait()?;
+    for line in game.lines() {
+        println!("[{:?}] {}", line.stream, line.line);
+    }
+    let status = game.wait()?;
+    println!("exited with {status} after {:?}", game.play_duration());
     Ok(())
 }
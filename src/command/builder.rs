@@ -5,7 +5,7 @@
 //! not spawn the process; callers can inspect or adjust the returned
 //! [`LaunchCommand`] before passing it to [`std::process::Command`].
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::{
     account::Account,
@@ -16,6 +16,7 @@ use crate::{
         rules::FeatureSet,
         version::VersionJson,
     },
+    install::assets::{log_config_path, CLIENT_LOGGING},
     platform::{Os, Platform},
     LauncherError, Result,
 };
@@ -26,6 +27,8 @@ use crate::{
 /// launcher exposes. By default the game directory is isolated under
 /// `<minecraft_dir>/versions/<version_id>`, which keeps saves, options, logs,
 /// and mods separate per installed profile.
+/// [`crate::instance::Instance::launch_options`] points it at an instance's
+/// game directory instead.
 #[derive(Debug, Clone)]
 pub struct LaunchOptions {
     /// Account values substituted into Minecraft's auth placeholders.
@@ -38,6 +41,9 @@ pub struct LaunchOptions {
     ///
     /// If omitted, a version-isolated directory is used.
     pub game_directory: Option<PathBuf>,
+    /// Extra JVM arguments, such as `-Xmx4G`, placed after the version's JVM
+    /// arguments and before the main class.
+    pub jvm_args: Vec<String>,
     /// Directory containing extracted native libraries.
     ///
     /// If omitted, this points at `<minecraft_dir>/versions/<version_id>/natives`.
@@ -58,6 +64,27 @@ pub struct LaunchOptions {
     pub disable_chat: bool,
     /// Controls whether known compatibility patches are applied before building.
     pub compatibility: CompatibilityPolicy,
+    /// Controls which log4j configuration, if any, is passed to the JVM.
+    pub log_config: LogConfigPolicy,
+}
+
+/// Log4j configuration passed to the game.
+///
+/// Mojang ships a client logging configuration with each version, including
+/// the patched configurations for CVE-2021-44228 on older versions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum LogConfigPolicy {
+    /// Use the `logging.client` configuration from version metadata, stored
+    /// under `assets/log_configs/`. Versions without one get no argument.
+    #[default]
+    Auto,
+    /// Do not pass a logging configuration.
+    Disabled,
+    /// Use this configuration file instead of the version's.
+    ///
+    /// The version's `logging.client.argument` template is used when present,
+    /// otherwise `-Dlog4j.configurationFile=<path>`.
+    Custom(PathBuf),
 }
 
 impl Default for LaunchOptions {
@@ -66,6 +93,7 @@ impl Default for LaunchOptions {
             account: Account::offline("Steve"),
             java_executable: None,
             game_directory: None,
+            jvm_args: Vec::new(),
             natives_directory: None,
             launcher_name: "mc-launcher-core".to_string(),
             launcher_version: env!("CARGO_PKG_VERSION").to_string(),
@@ -75,6 +103,7 @@ impl Default for LaunchOptions {
             disable_multiplayer: false,
             disable_chat: false,
             compatibility: CompatibilityPolicy::Auto,
+            log_config: LogConfigPolicy::Auto,
         }
     }
 }
@@ -209,6 +238,12 @@ pub fn build_launch_command_for_platform(
             platform,
         ));
     }
+    args.extend(options.jvm_args);
+    args.extend(log_config_argument(
+        version,
+        &minecraft_dir,
+        &options.log_config,
+    )?);
     args.push(main_class);
 
     if version.minecraft_arguments.is_some() {
@@ -260,6 +295,23 @@ pub fn build_launch_command_for_platform(
     })
 }
 
+fn log_config_argument(
+    version: &VersionJson,
+    minecraft_dir: &Path,
+    policy: &LogConfigPolicy,
+) -> Result<Option<String>> {
+    let logging = version.logging.get(CLIENT_LOGGING);
+    let path = match (policy, logging) {
+        (LogConfigPolicy::Disabled, _) | (LogConfigPolicy::Auto, None) => return Ok(None),
+        (LogConfigPolicy::Auto, Some(logging)) => log_config_path(minecraft_dir, &logging.file.id)?,
+        (LogConfigPolicy::Custom(path), _) => path.clone(),
+    };
+    let template = logging.map_or("-Dlog4j.configurationFile=${path}", |logging| {
+        logging.argument.as_str()
+    });
+    Ok(Some(template.replace("${path}", &path.to_string_lossy())))
+}
+
 fn default_legacy_jvm_arguments(
     natives_dir: &std::path::Path,
     classpath: &str,
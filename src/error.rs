@@ -31,6 +31,13 @@ pub enum LauncherError {
         #[from]
         source: serde_json::Error,
     },
+    /// TOML parse failure.
+    #[error("toml error: {source}")]
+    Toml {
+        /// Original toml error.
+        #[from]
+        source: toml::de::Error,
+    },
     /// ZIP archive failure.
     #[error("zip error: {source}")]
     Zip {
@@ -62,6 +69,16 @@ pub enum LauncherError {
         /// Actual checksum.
         actual: String,
     },
+    /// A downloaded file did not have its expected size.
+    #[error("size mismatch for {path}: expected {expected} bytes, got {actual}")]
+    SizeMismatch {
+        /// File that failed validation.
+        path: PathBuf,
+        /// Expected size in bytes.
+        expected: u64,
+        /// Actual size in bytes.
+        actual: u64,
+    },
     /// A joined path escaped the intended base directory.
     #[error("unsafe path {path} escapes base {base}")]
     UnsafePath {
@@ -70,6 +87,46 @@ pub enum LauncherError {
         /// Path that escaped the base directory.
         path: PathBuf,
     },
+    /// A version cannot be removed because other installed profiles inherit
+    /// from it.
+    #[error("version {id} is inherited by installed profiles: {}", .dependents.join(", "))]
+    VersionInUse {
+        /// Version that was to be removed.
+        id: String,
+        /// Installed profiles that inherit from it, directly or indirectly.
+        dependents: Vec<String>,
+    },
+    /// An instance name cannot be used as a directory name.
+    #[error("invalid instance name: {name:?}")]
+    InvalidInstanceName {
+        /// Rejected name.
+        name: String,
+    },
+    /// No instance with this name exists.
+    #[error("instance not found: {name}")]
+    InstanceNotFound {
+        /// Requested instance name.
+        name: String,
+    },
+    /// An instance with this name already exists.
+    #[error("instance already exists: {name}")]
+    InstanceExists {
+        /// Conflicting instance name.
+        name: String,
+    },
+    /// No mod, resource pack, or shader pack with this name is installed.
+    #[error("content not found: {}", .path.display())]
+    ContentNotFound {
+        /// Path the entry was expected at, without the `.disabled` suffix.
+        path: PathBuf,
+    },
+    /// A mod, resource pack, or shader pack with this name is already
+    /// installed.
+    #[error("content already exists: {}", .path.display())]
+    ContentExists {
+        /// Path of the existing entry.
+        path: PathBuf,
+    },
     /// Maven coordinate parsing failed.
     #[error("invalid maven coordinate: {coordinate}")]
     InvalidMavenCoordinate {
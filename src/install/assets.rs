@@ -1,4 +1,4 @@
-//! Asset index and asset object installation helpers.
+//! Asset index, asset object, and logging configuration installation helpers.
 
 use std::{
     collections::HashMap,
@@ -10,12 +10,19 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     core::version::VersionJson,
-    net::download::{execute_plan, Checksum, DownloadPlan, DownloadTask},
+    io::paths::safe_join,
+    net::{
+        download::{
+            execute_plan_with_options, Checksum, DownloadOptions, DownloadPlan, DownloadTask,
+        },
+        mirror::Endpoints,
+    },
     progress::ProgressReporter,
     Result,
 };
 
-const RESOURCES_BASE: &str = "https://resources.download.minecraft.net";
+/// Logging type used by the game client.
+pub const CLIENT_LOGGING: &str = "client";
 
 /// Minecraft asset index JSON.
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq)]
@@ -68,16 +75,57 @@ pub fn plan_asset_index_download(
         url: asset_index.url.clone(),
         destination: asset_index_path(minecraft_dir, &asset_index.id),
         checksum: Some(Checksum::Sha1(asset_index.sha1.clone())),
+        size: u64::try_from(asset_index.size).ok(),
         label: format!("assets index {}", asset_index.id),
     }])
 }
 
+/// Returns the local path for a logging configuration file.
+///
+/// Files are stored as `assets/log_configs/<file_id>`, where `file_id` is the
+/// `logging.client.file.id` value from version metadata.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if `file_id` escapes the log config
+/// directory.
+pub fn log_config_path(minecraft_dir: impl AsRef<Path>, file_id: &str) -> Result<PathBuf> {
+    safe_join(
+        minecraft_dir.as_ref().join("assets").join("log_configs"),
+        file_id,
+    )
+}
+
+/// Plans the client logging configuration download for a version.
+///
+/// Versions without `logging.client` metadata return an empty task list.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the configured file id is unsafe.
+pub fn plan_log_config_download(
+    version: &VersionJson,
+    minecraft_dir: &Path,
+) -> Result<Vec<DownloadTask>> {
+    let Some(logging) = version.logging.get(CLIENT_LOGGING) else {
+        return Ok(Vec::new());
+    };
+    Ok(vec![DownloadTask {
+        url: logging.file.url.clone(),
+        destination: log_config_path(minecraft_dir, &logging.file.id)?,
+        checksum: Some(Checksum::Sha1(logging.file.sha1.clone())),
+        size: u64::try_from(logging.file.size).ok(),
+        label: format!("log config {}", logging.file.id),
+    }])
+}
+
 /// Plans all asset object downloads from an already-read asset index.
 pub fn plan_asset_object_downloads_from_index(
     index: &AssetIndexJson,
     minecraft_dir: impl AsRef<Path>,
 ) -> DownloadPlan {
     let minecraft_dir = minecraft_dir.as_ref();
+    let resources = Endpoints::official().resources;
     let mut objects = index.objects.iter().collect::<Vec<_>>();
     objects.sort_by_key(|(name, _)| name.as_str());
 
@@ -87,9 +135,10 @@ pub fn plan_asset_object_downloads_from_index(
             .map(|(name, object)| {
                 let prefix = object.hash.get(..2).unwrap_or(&object.hash);
                 DownloadTask {
-                    url: format!("{RESOURCES_BASE}/{prefix}/{}", object.hash),
+                    url: format!("{resources}/{prefix}/{}", object.hash),
                     destination: asset_object_path(minecraft_dir, &object.hash),
                     checksum: Some(Checksum::Sha1(object.hash.clone())),
+                    size: u64::try_from(object.size).ok(),
                     label: format!("asset {name}"),
                 }
             })
@@ -107,12 +156,32 @@ pub fn install_assets(
     version: &VersionJson,
     minecraft_dir: impl AsRef<Path>,
     reporter: &mut dyn ProgressReporter,
+) -> Result<()> {
+    install_assets_with_options(
+        version,
+        minecraft_dir,
+        &DownloadOptions::default(),
+        reporter,
+    )
+}
+
+/// Downloads the asset index and asset objects with explicit download options.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if download, checksum, filesystem, or JSON
+/// decoding fails.
+pub fn install_assets_with_options(
+    version: &VersionJson,
+    minecraft_dir: impl AsRef<Path>,
+    options: &DownloadOptions,
+    reporter: &mut dyn ProgressReporter,
 ) -> Result<()> {
     let minecraft_dir = minecraft_dir.as_ref();
     let index_plan = DownloadPlan {
         tasks: plan_asset_index_download(version, minecraft_dir)?,
     };
-    execute_plan(&index_plan, reporter)?;
+    execute_plan_with_options(&index_plan, options, reporter)?;
 
     let Some(asset_index) = &version.asset_index else {
         return Ok(());
@@ -120,5 +189,5 @@ pub fn install_assets(
     let index_path = asset_index_path(minecraft_dir, &asset_index.id);
     let index: AssetIndexJson = serde_json::from_slice(&fs::read(index_path)?)?;
     let object_plan = plan_asset_object_downloads_from_index(&index, minecraft_dir);
-    execute_plan(&object_plan, reporter)
+    execute_plan_with_options(&object_plan, options, reporter)
 }
@@ -10,15 +10,16 @@ use serde::Deserialize;
 use crate::{
     compatibility::{apply_compatibility, CompatibilityPolicy},
     core::version::VersionJson,
-    net::{download::execute_plan, http},
+    net::{
+        download::{execute_plan_with_options, DownloadOptions},
+        http,
+        mirror::{Endpoints, MirrorConfig},
+    },
     platform::Platform,
     progress::ProgressReporter,
     LauncherError, Result,
 };
 
-const VERSION_MANIFEST_URL: &str =
-    "https://launchermeta.mojang.com/mc/game/version_manifest_v2.json";
-
 #[derive(Debug, Deserialize)]
 struct VersionManifest {
     versions: Vec<VersionManifestEntry>,
@@ -37,7 +38,24 @@ struct VersionManifestEntry {
 /// Returns [`crate::LauncherError`] if the manifest cannot be fetched, the
 /// version id is unknown, or the version JSON cannot be decoded.
 pub fn fetch_vanilla_version(version_id: &str) -> Result<VersionJson> {
-    let manifest: VersionManifest = http::get_json(VERSION_MANIFEST_URL)?;
+    fetch_vanilla_version_with_mirrors(version_id, &MirrorConfig::official())
+}
+
+/// Fetches a vanilla Minecraft version JSON through the given mirrors.
+///
+/// Both the version manifest and the version JSON it points at are requested
+/// from each mirror candidate in order.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the manifest cannot be fetched from any
+/// candidate, the version id is unknown, or the version JSON cannot be decoded.
+pub fn fetch_vanilla_version_with_mirrors(
+    version_id: &str,
+    mirrors: &MirrorConfig,
+) -> Result<VersionJson> {
+    let manifest: VersionManifest =
+        http::get_json_with_mirrors(&Endpoints::official().version_manifest, mirrors)?;
     let entry = manifest
         .versions
         .iter()
@@ -46,7 +64,7 @@ pub fn fetch_vanilla_version(version_id: &str) -> Result<VersionJson> {
             id: version_id.to_string(),
         })?;
 
-    http::get_json(&entry.url)
+    http::get_json_with_mirrors(&entry.url, mirrors)
 }
 
 /// Returns the canonical local path for a version JSON file.
@@ -58,6 +76,36 @@ pub fn version_json_path(minecraft_dir: impl AsRef<Path>, version_id: &str) -> P
         .join(format!("{version_id}.json"))
 }
 
+/// Lists the ids of version profiles under `<minecraft_dir>/versions`.
+///
+/// A directory counts as an installed version when it contains a JSON file
+/// named after the directory. Ids are returned sorted.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the versions directory exists but cannot
+/// be read.
+pub fn installed_version_ids(minecraft_dir: impl AsRef<Path>) -> Result<Vec<String>> {
+    let minecraft_dir = minecraft_dir.as_ref();
+    let entries = match fs::read_dir(minecraft_dir.join("versions")) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let mut ids = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let Ok(id) = entry.file_name().into_string() else {
+            continue;
+        };
+        if version_json_path(minecraft_dir, &id).is_file() {
+            ids.push(id);
+        }
+    }
+    ids.sort();
+    Ok(ids)
+}
+
 /// Writes a version JSON to the standard local profile path.
 ///
 /// # Errors
@@ -140,6 +188,31 @@ pub fn install_version_files_for_platform(
     platform: Platform,
     compatibility: CompatibilityPolicy,
     reporter: &mut dyn ProgressReporter,
+) -> Result<()> {
+    install_version_files_with_options(
+        version,
+        minecraft_dir,
+        platform,
+        compatibility,
+        &DownloadOptions::default(),
+        reporter,
+    )
+}
+
+/// Installs version files with explicit platform, compatibility, and download
+/// options.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if planning, downloads, checksums, asset
+/// decoding, or native extraction fails.
+pub fn install_version_files_with_options(
+    version: &VersionJson,
+    minecraft_dir: impl AsRef<Path>,
+    platform: Platform,
+    compatibility: CompatibilityPolicy,
+    options: &DownloadOptions,
+    reporter: &mut dyn ProgressReporter,
 ) -> Result<()> {
     let minecraft_dir = minecraft_dir.as_ref();
     let compatibility = apply_compatibility(version, platform, compatibility);
@@ -158,8 +231,8 @@ pub fn install_version_files_for_platform(
         platform,
         CompatibilityPolicy::Disabled,
     )?;
-    execute_plan(&plan, reporter)?;
-    crate::install::assets::install_assets(version, minecraft_dir, reporter)?;
+    execute_plan_with_options(&plan, options, reporter)?;
+    crate::install::assets::install_assets_with_options(version, minecraft_dir, options, reporter)?;
     crate::install::natives::extract_natives_for_platform(
         &version.libraries,
         minecraft_dir,
@@ -0,0 +1,484 @@
+//! Rust-native Forge and NeoForge installation.
+//!
+//! Modern Forge (1.13+) and NeoForge installer jars carry an
+//! `install_profile.json` that lists libraries, data entries, and processors,
+//! plus the `version.json` of the installed profile. This module reads both
+//! files from the jar, downloads libraries through a [`DownloadPlan`], and runs
+//! each client processor as its own Java invocation instead of running the
+//! installer's GUI/CLI entry point.
+//!
+//! Legacy installers, which use the `install`/`versionInfo` layout, are still
+//! executed with [`run_loader_installer`].
+
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+
+use crate::{
+    core::{maven::MavenCoordinate, version::Library},
+    install::{
+        client::version_json_path,
+        libraries::plan_library_downloads_for_platform,
+        loader::{run_loader_installer, write_loader_profile, InstallerInvocation},
+    },
+    io::{hash::sha1_file, paths::safe_join},
+    net::download::{execute_plan_with_options, DownloadOptions, DownloadPlan},
+    platform::Platform,
+    progress::{InstallStage, ProgressEvent, ProgressReporter, SkipReason},
+    LauncherError, Result,
+};
+
+/// Side whose processors and data entries are used.
+const SIDE: &str = "client";
+
+/// `install_profile.json` of a modern Forge or NeoForge installer.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct InstallProfile {
+    /// Installer profile format version.
+    #[serde(default)]
+    pub spec: u32,
+    /// Id of the installed version profile.
+    pub version: String,
+    /// Path of the version JSON inside the installer jar.
+    #[serde(default = "default_version_json")]
+    pub json: String,
+    /// Minecraft version the loader installs on top of.
+    pub minecraft: String,
+    /// Named values available to processor arguments, by side.
+    #[serde(default)]
+    pub data: HashMap<String, SidedValue>,
+    /// Processors that generate patched jars and mappings.
+    #[serde(default)]
+    pub processors: Vec<Processor>,
+    /// Libraries needed to run the processors.
+    #[serde(default)]
+    pub libraries: Vec<Library>,
+}
+
+fn default_version_json() -> String {
+    "/version.json".to_string()
+}
+
+/// A data entry with separate client and server values.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct SidedValue {
+    /// Client value.
+    #[serde(default)]
+    pub client: String,
+    /// Server value.
+    #[serde(default)]
+    pub server: String,
+}
+
+/// One installer processor.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Processor {
+    /// Sides the processor runs for; empty means every side.
+    #[serde(default)]
+    pub sides: Vec<String>,
+    /// Maven coordinate of the processor jar.
+    pub jar: String,
+    /// Maven coordinates added to the classpath after `jar`.
+    #[serde(default)]
+    pub classpath: Vec<String>,
+    /// Arguments with `{DATA}` and `[coordinate]` placeholders.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Expected SHA-1 of output files, keyed by output path placeholder.
+    #[serde(default)]
+    pub outputs: HashMap<String, String>,
+}
+
+impl Processor {
+    /// Returns whether the processor runs for the client side.
+    pub fn runs_on_client(&self) -> bool {
+        self.sides.is_empty() || self.sides.iter().any(|side| side == SIDE)
+    }
+}
+
+/// Resolved Java invocation for one processor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessorCommand {
+    /// Classpath entries, starting with the processor jar.
+    pub classpath: Vec<PathBuf>,
+    /// `Main-Class` from the processor jar manifest.
+    pub main_class: String,
+    /// Arguments after placeholder substitution.
+    pub args: Vec<String>,
+    /// Output files and their expected SHA-1 digests.
+    pub outputs: Vec<(PathBuf, String)>,
+}
+
+/// Reads `install_profile.json` from an installer jar.
+///
+/// Returns `Ok(None)` for legacy installers that use the
+/// `install`/`versionInfo` layout.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the jar cannot be read or the profile
+/// cannot be decoded.
+pub fn read_install_profile(installer_path: impl AsRef<Path>) -> Result<Option<InstallProfile>> {
+    let mut archive = ZipArchive::new(File::open(installer_path)?)?;
+    let profile: serde_json::Value =
+        serde_json::from_slice(&read_entry(&mut archive, "install_profile.json")?)?;
+    if profile.get("versionInfo").is_some() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_value(profile)?))
+}
+
+/// Resolves the client values of an install profile's data entries.
+///
+/// Values follow the installer conventions: `[coordinate]` becomes the local
+/// library path, `'text'` becomes `text`, and `/path` names a file inside the
+/// installer jar that is extracted below `data_dir`. The built-in `SIDE`,
+/// `MINECRAFT_JAR`, `MINECRAFT_VERSION`, `ROOT`, `INSTALLER`, and
+/// `LIBRARY_DIR` entries are added.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if a coordinate is invalid, an installer
+/// path is unsafe or missing, or extraction fails.
+pub fn resolve_data(
+    profile: &InstallProfile,
+    installer_path: &Path,
+    minecraft_dir: &Path,
+    data_dir: &Path,
+) -> Result<HashMap<String, String>> {
+    let libraries_dir = minecraft_dir.join("libraries");
+    let mut archive = ZipArchive::new(File::open(installer_path)?)?;
+    let mut data = HashMap::new();
+    for (key, value) in &profile.data {
+        let value = &value.client;
+        let resolved = if let Some(coordinate) = artifact_reference(value) {
+            display(&artifact_path(&libraries_dir, coordinate)?)
+        } else if let Some(literal) = value
+            .strip_prefix('\'')
+            .and_then(|value| value.strip_suffix('\''))
+        {
+            literal.to_string()
+        } else if let Some(entry) = value.strip_prefix('/') {
+            let output = safe_join(data_dir, entry)?;
+            if let Some(parent) = output.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&output, read_entry(&mut archive, entry)?)?;
+            display(&output)
+        } else {
+            value.clone()
+        };
+        data.insert(key.clone(), resolved);
+    }
+
+    let minecraft_jar = minecraft_dir
+        .join("versions")
+        .join(&profile.minecraft)
+        .join(format!("{}.jar", profile.minecraft));
+    data.insert("SIDE".to_string(), SIDE.to_string());
+    data.insert("MINECRAFT_JAR".to_string(), display(&minecraft_jar));
+    data.insert("MINECRAFT_VERSION".to_string(), profile.minecraft.clone());
+    data.insert("ROOT".to_string(), display(minecraft_dir));
+    data.insert("INSTALLER".to_string(), display(installer_path));
+    data.insert("LIBRARY_DIR".to_string(), display(&libraries_dir));
+    Ok(data)
+}
+
+/// Replaces placeholders in one processor argument.
+///
+/// An argument of the form `[coordinate]` becomes the local library path.
+/// Otherwise every `{KEY}` is replaced with its data value; `\` escapes the next
+/// character.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if a coordinate is invalid, a key is not in
+/// `data`, or a `{` is not closed.
+pub fn substitute_arg(
+    arg: &str,
+    data: &HashMap<String, String>,
+    libraries_dir: &Path,
+) -> Result<String> {
+    if let Some(coordinate) = artifact_reference(arg) {
+        return Ok(display(&artifact_path(libraries_dir, coordinate)?));
+    }
+
+    let mut output = String::with_capacity(arg.len());
+    let mut chars = arg.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => output.extend(chars.next()),
+            '{' => {
+                let mut key = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => key.push(c),
+                        None => {
+                            return Err(LauncherError::Other {
+                                message: format!("unclosed placeholder in argument {arg:?}"),
+                            })
+                        }
+                    }
+                }
+                let value = data.get(&key).ok_or_else(|| LauncherError::MissingField {
+                    context: format!("installer data for argument {arg:?}"),
+                    field: key,
+                })?;
+                output.push_str(value);
+            }
+            c => output.push(c),
+        }
+    }
+    Ok(output)
+}
+
+/// Resolves the classpath, main class, arguments, and outputs of a processor.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if a coordinate or placeholder cannot be
+/// resolved, or the processor jar has no `Main-Class`.
+pub fn processor_command(
+    processor: &Processor,
+    data: &HashMap<String, String>,
+    libraries_dir: &Path,
+) -> Result<ProcessorCommand> {
+    let jar = artifact_path(libraries_dir, &processor.jar)?;
+    let main_class = main_class(&jar)?;
+    let mut classpath = vec![jar];
+    for coordinate in &processor.classpath {
+        classpath.push(artifact_path(libraries_dir, coordinate)?);
+    }
+    let args = processor
+        .args
+        .iter()
+        .map(|arg| substitute_arg(arg, data, libraries_dir))
+        .collect::<Result<Vec<_>>>()?;
+    let mut outputs = Vec::new();
+    for (path, sha1) in &processor.outputs {
+        let path = PathBuf::from(substitute_arg(path, data, libraries_dir)?);
+        let sha1 = substitute_arg(sha1, data, libraries_dir)?;
+        outputs.push((path, sha1.trim_matches('\'').to_string()));
+    }
+    outputs.sort();
+    Ok(ProcessorCommand {
+        classpath,
+        main_class,
+        args,
+        outputs,
+    })
+}
+
+/// Installs a Forge or NeoForge profile from its installer jar.
+///
+/// The version JSON is written to `versions/<id>/<id>.json`, libraries shipped
+/// inside the jar under `maven/` are copied to `libraries/`, remaining
+/// libraries are downloaded with `options`, and each client processor is run
+/// with `invocation.java_executable`. Processors whose outputs already match
+/// their declared hashes are skipped. The vanilla client jar must already be
+/// installed.
+///
+/// Returns the installed version id.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] for archive, download, checksum, or
+/// filesystem failures, [`crate::LauncherError::InstallerFailed`] when a
+/// processor exits unsuccessfully, and
+/// [`crate::LauncherError::ChecksumMismatch`] when a processor output does not
+/// match its declared hash.
+pub fn install_from_installer(
+    invocation: &InstallerInvocation,
+    options: &DownloadOptions,
+    reporter: &mut dyn ProgressReporter,
+) -> Result<String> {
+    let installer_path = &invocation.installer_path;
+    let minecraft_dir = &invocation.minecraft_dir;
+    reporter.report(ProgressEvent::StageStarted {
+        stage: InstallStage::LoaderInstall,
+    });
+
+    let Some(profile) = read_install_profile(installer_path)? else {
+        return install_legacy(invocation);
+    };
+
+    let mut archive = ZipArchive::new(File::open(installer_path)?)?;
+    let version_entry = profile.json.trim_start_matches('/');
+    let version: crate::core::version::VersionJson =
+        serde_json::from_slice(&read_entry(&mut archive, version_entry)?)?;
+    let version_id = version
+        .id
+        .clone()
+        .unwrap_or_else(|| profile.version.clone());
+
+    let libraries_dir = minecraft_dir.join("libraries");
+    extract_maven_entries(&mut archive, &libraries_dir)?;
+    drop(archive);
+
+    let mut plan = DownloadPlan::default();
+    for libraries in [&profile.libraries, &version.libraries] {
+        plan.tasks.extend(
+            plan_library_downloads_for_platform(libraries, minecraft_dir, Platform::current())?
+                .into_iter()
+                // Empty URLs mark artifacts shipped inside the installer.
+                .filter(|task| !task.url.is_empty()),
+        );
+    }
+    execute_plan_with_options(&plan, options, reporter)?;
+
+    let data_dir = tempfile::tempdir()?;
+    let data = resolve_data(&profile, installer_path, minecraft_dir, data_dir.path())?;
+    for processor in profile.processors.iter().filter(|p| p.runs_on_client()) {
+        run_processor(invocation, processor, &data, &libraries_dir, reporter)?;
+    }
+
+    write_loader_profile(minecraft_dir, &version)?;
+    Ok(version_id)
+}
+
+fn run_processor(
+    invocation: &InstallerInvocation,
+    processor: &Processor,
+    data: &HashMap<String, String>,
+    libraries_dir: &Path,
+    reporter: &mut dyn ProgressReporter,
+) -> Result<()> {
+    let label = format!("processor {}", processor.jar);
+    let command = processor_command(processor, data, libraries_dir)?;
+    if !command.outputs.is_empty() && outputs_match(&command.outputs)? {
+        reporter.report(ProgressEvent::TaskSkipped {
+            label,
+            reason: SkipReason::ChecksumMatched,
+        });
+        return Ok(());
+    }
+
+    reporter.report(ProgressEvent::TaskStarted {
+        label: label.clone(),
+        path: command.classpath[0].clone(),
+    });
+    let classpath = env::join_paths(&command.classpath).map_err(|err| LauncherError::Other {
+        message: format!("invalid processor classpath: {err}"),
+    })?;
+    let status = Command::new(&invocation.java_executable)
+        .arg("-cp")
+        .arg(classpath)
+        .arg(&command.main_class)
+        .args(&command.args)
+        .current_dir(&invocation.minecraft_dir)
+        .status()?;
+    if !status.success() {
+        return Err(LauncherError::InstallerFailed {
+            loader: invocation.loader,
+            status: status.code(),
+        });
+    }
+
+    for (path, expected) in &command.outputs {
+        let actual = sha1_file(path)?;
+        if !actual.eq_ignore_ascii_case(expected) {
+            fs::remove_file(path)?;
+            return Err(LauncherError::ChecksumMismatch {
+                path: path.clone(),
+                expected: expected.clone(),
+                actual,
+            });
+        }
+    }
+    reporter.report(ProgressEvent::TaskFinished { label });
+    Ok(())
+}
+
+fn outputs_match(outputs: &[(PathBuf, String)]) -> Result<bool> {
+    for (path, expected) in outputs {
+        if !path.is_file() || !sha1_file(path)?.eq_ignore_ascii_case(expected) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn install_legacy(invocation: &InstallerInvocation) -> Result<String> {
+    let mut archive = ZipArchive::new(File::open(&invocation.installer_path)?)?;
+    let profile: serde_json::Value =
+        serde_json::from_slice(&read_entry(&mut archive, "install_profile.json")?)?;
+    let version_id = profile
+        .pointer("/versionInfo/id")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| LauncherError::MissingField {
+            context: "legacy install_profile.json".to_string(),
+            field: "versionInfo.id".to_string(),
+        })?
+        .to_string();
+    run_loader_installer(invocation)?;
+    if !version_json_path(&invocation.minecraft_dir, &version_id).is_file() {
+        return Err(LauncherError::InvalidVersionId { id: version_id });
+    }
+    Ok(version_id)
+}
+
+fn extract_maven_entries(archive: &mut ZipArchive<File>, libraries_dir: &Path) -> Result<()> {
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        let Some(relative) = entry.name().strip_prefix("maven/").map(str::to_string) else {
+            continue;
+        };
+        if entry.is_dir() || relative.is_empty() {
+            continue;
+        }
+        let output = safe_join(libraries_dir, &relative)?;
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        io::copy(&mut entry, &mut File::create(output)?)?;
+    }
+    Ok(())
+}
+
+fn read_entry(archive: &mut ZipArchive<File>, name: &str) -> Result<Vec<u8>> {
+    let mut entry = archive.by_name(name)?;
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn main_class(jar: &Path) -> Result<String> {
+    let mut archive = ZipArchive::new(File::open(jar)?)?;
+    let manifest = String::from_utf8_lossy(&read_entry(&mut archive, "META-INF/MANIFEST.MF")?)
+        // Manifest lines longer than 72 bytes continue on lines starting with
+        // a single space.
+        .replace("\r\n", "\n")
+        .replace("\n ", "");
+    manifest
+        .lines()
+        .find_map(|line| line.strip_prefix("Main-Class:"))
+        .map(|class| class.trim().to_string())
+        .ok_or_else(|| LauncherError::MissingField {
+            context: format!("manifest of {}", jar.display()),
+            field: "Main-Class".to_string(),
+        })
+}
+
+fn artifact_reference(value: &str) -> Option<&str> {
+    value.strip_prefix('[')?.strip_suffix(']')
+}
+
+fn artifact_path(libraries_dir: &Path, coordinate: &str) -> Result<PathBuf> {
+    safe_join(
+        libraries_dir,
+        MavenCoordinate::parse(coordinate)?.artifact_path(),
+    )
+}
+
+fn display(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
@@ -0,0 +1,367 @@
+//! Garbage collection of files no installed version references.
+//!
+//! Libraries and asset objects are shared by every version in a Minecraft
+//! directory, so uninstalling a version never removes them. A collection pass
+//! loads every installed version with
+//! [`crate::install::client::load_version_json`], records the library paths
+//! and asset hashes they reference, including the replacement libraries
+//! [`crate::compatibility::apply_compatibility`] installs on the current
+//! platform, and treats everything else below
+//! `libraries` and `assets` as reclaimable. Loader installers whose version is
+//! no longer installed and natives directories left behind by removed versions
+//! are reclaimed as well.
+//!
+//! [`plan_garbage_collection`] only reports; [`collect_garbage`] deletes.
+
+use std::{
+    collections::HashSet,
+    ffi::OsStr,
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use serde_json::Value;
+use zip::ZipArchive;
+
+use crate::{
+    compatibility::{apply_compatibility, CompatibilityPolicy},
+    core::{maven::MavenCoordinate, version::Library},
+    install::{
+        assets::{asset_index_path, AssetIndexJson},
+        client::{installed_version_ids, load_version_json},
+        forge::InstallProfile,
+    },
+    platform::Platform,
+    Result,
+};
+
+/// Kind of file or directory found by a collection pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GcItemKind {
+    /// File below `libraries` that no version or kept installer references.
+    Library,
+    /// Asset object no referenced asset index lists.
+    AssetObject,
+    /// Asset index no installed version uses.
+    AssetIndex,
+    /// Loader installer below `versions/.installers` whose version is gone.
+    Installer,
+    /// Natives directory of a version that is no longer installed.
+    Natives,
+}
+
+/// One reclaimable file or directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GcItem {
+    /// What the item is.
+    pub kind: GcItemKind,
+    /// Absolute path of the file or directory.
+    pub path: PathBuf,
+    /// Size in bytes, including every file inside a directory.
+    pub size: u64,
+}
+
+/// Result of a collection pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GcReport {
+    /// Installed version ids whose files were kept.
+    pub versions: Vec<String>,
+    /// Unreferenced files and directories, reclaimed or reclaimable.
+    pub items: Vec<GcItem>,
+}
+
+impl GcReport {
+    /// Returns the total size of all items in bytes.
+    pub fn reclaimable_bytes(&self) -> u64 {
+        self.items.iter().map(|item| item.size).sum()
+    }
+
+    /// Returns the items of one kind.
+    pub fn items_of(&self, kind: GcItemKind) -> impl Iterator<Item = &GcItem> {
+        self.items.iter().filter(move |item| item.kind == kind)
+    }
+}
+
+/// Lists unreferenced files without deleting anything.
+///
+/// Every installed version must load, because a version that cannot be read
+/// would have its files reported as unreferenced. Asset objects are kept only
+/// when an asset index that lists them exists locally.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if an installed version or asset index
+/// cannot be read or decoded, a library coordinate is invalid, or a directory
+/// cannot be listed.
+pub fn plan_garbage_collection(minecraft_dir: impl AsRef<Path>) -> Result<GcReport> {
+    plan_garbage_collection_for_platform(minecraft_dir, Platform::current())
+}
+
+/// Lists unreferenced files as [`plan_garbage_collection`] does, keeping the
+/// replacement libraries the compatibility layer installs on `platform`.
+///
+/// The unpatched libraries stay referenced as well, so changing the
+/// compatibility policy later does not leave a version without its files.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if an installed version or asset index
+/// cannot be read or decoded, a library coordinate is invalid, or a directory
+/// cannot be listed.
+pub fn plan_garbage_collection_for_platform(
+    minecraft_dir: impl AsRef<Path>,
+    platform: Platform,
+) -> Result<GcReport> {
+    let minecraft_dir = minecraft_dir.as_ref();
+    let versions = installed_version_ids(minecraft_dir)?;
+    let mut references = References::default();
+    for id in &versions {
+        let version = load_version_json(minecraft_dir, id)?;
+        references.add_libraries(&version.libraries)?;
+        let compatibility = apply_compatibility(&version, platform, CompatibilityPolicy::Auto);
+        references.add_libraries(&compatibility.version.libraries)?;
+        if let Some(asset_index) = &version.asset_index {
+            references.asset_indexes.insert(asset_index.id.clone());
+        }
+    }
+
+    let mut items = Vec::new();
+    let installed: HashSet<&str> = versions.iter().map(String::as_str).collect();
+    for path in list_entries(&minecraft_dir.join("versions").join(".installers"))? {
+        match installer_profile(&path) {
+            Some(profile) if installed.contains(profile.version.as_str()) => {
+                if let Some(profile) = profile.install_profile {
+                    references.add_install_profile(&profile)?;
+                }
+            }
+            _ => items.push(item(GcItemKind::Installer, path)?),
+        }
+    }
+
+    let libraries_dir = minecraft_dir.join("libraries");
+    for path in list_files(&libraries_dir)? {
+        let relative = path.strip_prefix(&libraries_dir).unwrap_or(&path);
+        if !references.libraries.contains(relative) {
+            items.push(item(GcItemKind::Library, path)?);
+        }
+    }
+
+    let mut asset_hashes = HashSet::new();
+    for id in &references.asset_indexes {
+        let index_path = asset_index_path(minecraft_dir, id);
+        match fs::read(&index_path) {
+            Ok(bytes) => {
+                let index: AssetIndexJson = serde_json::from_slice(&bytes)?;
+                asset_hashes.extend(index.objects.into_values().map(|object| object.hash));
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    for path in list_entries(&minecraft_dir.join("assets").join("indexes"))? {
+        let referenced = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|id| references.asset_indexes.contains(id));
+        if !referenced || path.extension() != Some(OsStr::new("json")) {
+            items.push(item(GcItemKind::AssetIndex, path)?);
+        }
+    }
+    for path in list_files(&minecraft_dir.join("assets").join("objects"))? {
+        let referenced = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|hash| asset_hashes.contains(hash));
+        if !referenced {
+            items.push(item(GcItemKind::AssetObject, path)?);
+        }
+    }
+
+    for version_dir in list_entries(&minecraft_dir.join("versions"))? {
+        let Some(id) = version_dir.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if id == ".installers" || !version_dir.is_dir() {
+            continue;
+        }
+        let stale_prefix = format!("{id}-natives-");
+        for path in list_entries(&version_dir)? {
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let orphaned = name == "natives" && !installed.contains(id);
+            if path.is_dir() && (orphaned || name.starts_with(&stale_prefix)) {
+                items.push(item(GcItemKind::Natives, path)?);
+            }
+        }
+    }
+
+    Ok(GcReport { versions, items })
+}
+
+/// Deletes every unreferenced file found by [`plan_garbage_collection`].
+///
+/// Directories below `libraries` and `assets/objects` that become empty are
+/// removed too. The returned report lists what was deleted.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if planning fails or a file cannot be
+/// deleted. Items deleted before the failure stay deleted.
+pub fn collect_garbage(minecraft_dir: impl AsRef<Path>) -> Result<GcReport> {
+    let minecraft_dir = minecraft_dir.as_ref();
+    let report = plan_garbage_collection(minecraft_dir)?;
+    for item in &report.items {
+        if item.kind == GcItemKind::Natives {
+            fs::remove_dir_all(&item.path)?;
+        } else {
+            fs::remove_file(&item.path)?;
+        }
+    }
+    remove_empty_dirs(&minecraft_dir.join("libraries"))?;
+    remove_empty_dirs(&minecraft_dir.join("assets").join("objects"))?;
+    Ok(report)
+}
+
+/// Library paths relative to `libraries`, and asset index ids, in use.
+#[derive(Debug, Default)]
+struct References {
+    libraries: HashSet<PathBuf>,
+    asset_indexes: HashSet<String>,
+}
+
+impl References {
+    /// Records every artifact a library list can resolve to, on any platform.
+    fn add_libraries(&mut self, libraries: &[Library]) -> Result<()> {
+        for library in libraries {
+            match &library.downloads {
+                Some(downloads) => {
+                    let artifacts = downloads
+                        .artifact
+                        .iter()
+                        .chain(downloads.classifiers.values());
+                    self.libraries
+                        .extend(artifacts.map(|artifact| PathBuf::from(&artifact.path)));
+                }
+                None => {
+                    self.add_coordinate(&library.name)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the processor libraries and generated files of a kept installer.
+    fn add_install_profile(&mut self, profile: &InstallProfile) -> Result<()> {
+        self.add_libraries(&profile.libraries)?;
+        for value in profile.data.values() {
+            if let Some(coordinate) = value
+                .client
+                .strip_prefix('[')
+                .and_then(|value| value.strip_suffix(']'))
+            {
+                self.add_coordinate(coordinate)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn add_coordinate(&mut self, coordinate: &str) -> Result<()> {
+        self.libraries
+            .insert(MavenCoordinate::parse(coordinate)?.artifact_path());
+        Ok(())
+    }
+}
+
+/// Version id an installer jar installs, plus its profile for modern installers.
+struct InstallerProfile {
+    version: String,
+    install_profile: Option<InstallProfile>,
+}
+
+/// Reads the target version of an installer jar.
+///
+/// Returns `None` for anything that is not a readable installer, such as a
+/// partial download, so it is reclaimed.
+fn installer_profile(path: &Path) -> Option<InstallerProfile> {
+    let mut archive = ZipArchive::new(File::open(path).ok()?).ok()?;
+    let mut bytes = Vec::new();
+    archive
+        .by_name("install_profile.json")
+        .ok()?
+        .read_to_end(&mut bytes)
+        .ok()?;
+    let profile: Value = serde_json::from_slice(&bytes).ok()?;
+    if profile.get("versionInfo").is_some() {
+        // Legacy installers name their target in `install.target`; the
+        // libraries they install are listed by the version profile itself.
+        let version = profile.pointer("/install/target")?.as_str()?.to_string();
+        return Some(InstallerProfile {
+            version,
+            install_profile: None,
+        });
+    }
+    let profile: InstallProfile = serde_json::from_value(profile).ok()?;
+    Some(InstallerProfile {
+        version: profile.version.clone(),
+        install_profile: Some(profile),
+    })
+}
+
+fn item(kind: GcItemKind, path: PathBuf) -> Result<GcItem> {
+    let size = disk_size(&path)?;
+    Ok(GcItem { kind, path, size })
+}
+
+fn disk_size(path: &Path) -> Result<u64> {
+    let metadata = fs::symlink_metadata(path)?;
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+    let mut size = 0;
+    for entry in list_entries(path)? {
+        size += disk_size(&entry)?;
+    }
+    Ok(size)
+}
+
+/// Lists the direct children of `dir`, or nothing when it does not exist.
+fn list_entries(dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let mut paths = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    paths.sort();
+    Ok(paths)
+}
+
+/// Lists every file below `dir`, recursively.
+fn list_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in list_entries(dir)? {
+        if fs::symlink_metadata(&path)?.is_dir() {
+            files.extend(list_files(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Removes empty directories below `dir`, keeping `dir` itself.
+fn remove_empty_dirs(dir: &Path) -> Result<()> {
+    for path in list_entries(dir)? {
+        if fs::symlink_metadata(&path)?.is_dir() {
+            remove_empty_dirs(&path)?;
+            if list_entries(&path)?.is_empty() {
+                fs::remove_dir(&path)?;
+            }
+        }
+    }
+    Ok(())
+}
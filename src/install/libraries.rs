@@ -8,7 +8,10 @@ use crate::{
         rules::{evaluate_rules, FeatureSet},
         version::{Library, LibraryArtifact},
     },
-    net::download::{Checksum, DownloadTask},
+    net::{
+        download::{Checksum, DownloadTask},
+        mirror::Endpoints,
+    },
     platform::Platform,
     Result,
 };
@@ -58,7 +61,7 @@ pub fn plan_library_downloads_for_platform(
             let base_url = library
                 .url
                 .clone()
-                .unwrap_or_else(|| "https://maven.fabricmc.net/".to_string());
+                .unwrap_or_else(|| Endpoints::official().fabric_maven);
             let url = format!(
                 "{}/{}",
                 base_url.trim_end_matches('/'),
@@ -68,6 +71,7 @@ pub fn plan_library_downloads_for_platform(
                 url,
                 destination: minecraft_dir.join("libraries").join(&path),
                 checksum: None,
+                size: None,
                 label: format!("library {}", library.name),
             });
         }
@@ -84,6 +88,7 @@ fn download_task(
         url: artifact.url.clone(),
         destination: minecraft_dir.join("libraries").join(&artifact.path),
         checksum: Some(Checksum::Sha1(artifact.sha1.clone())),
+        size: u64::try_from(artifact.size).ok(),
         label: library.name.clone(),
     }
 }
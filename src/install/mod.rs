@@ -7,11 +7,16 @@
 
 pub mod assets;
 pub mod client;
+pub mod forge;
+pub mod gc;
 pub mod libraries;
 pub mod loader;
 pub mod natives;
 pub mod request;
+pub mod runtime;
+pub mod uninstall;
 pub mod vanilla;
+pub mod verify;
 
 use std::path::Path;
 
@@ -77,6 +77,51 @@ pub fn extract_natives_for_platform(
     Ok(destination)
 }
 
+/// Lists the files native extraction writes, with their uncompressed sizes.
+///
+/// Native jars that are not downloaded yet are skipped.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if a native jar cannot be read, a Maven
+/// coordinate is invalid, or an archive entry would escape the destination.
+pub fn expected_native_files_for_platform(
+    libraries: &[Library],
+    minecraft_dir: impl AsRef<Path>,
+    version_id: &str,
+    platform: Platform,
+) -> Result<Vec<(PathBuf, u64)>> {
+    let minecraft_dir = minecraft_dir.as_ref();
+    let destination = natives_directory(minecraft_dir, version_id);
+
+    let mut files = Vec::new();
+    let mut seen = HashSet::new();
+    for library in libraries {
+        if !evaluate_rules(&library.rules, platform, &FeatureSet::default()) {
+            continue;
+        }
+        for artifact in native_artifacts_for_platform(library, platform)? {
+            let jar_path = minecraft_dir.join("libraries").join(&artifact.path);
+            if !seen.insert(artifact.path.as_str()) || !jar_path.is_file() {
+                continue;
+            }
+            let mut archive = ZipArchive::new(File::open(&jar_path)?)?;
+            let excluded = excluded_entries(library);
+            for index in 0..archive.len() {
+                let entry = archive.by_index(index)?;
+                if entry.is_dir() || should_skip_entry(entry.name(), &excluded) {
+                    continue;
+                }
+                let Some(enclosed) = entry.enclosed_name() else {
+                    continue;
+                };
+                files.push((safe_join(&destination, enclosed)?, entry.size()));
+            }
+        }
+    }
+    Ok(files)
+}
+
 fn native_artifacts_for_platform(
     library: &Library,
     platform: Platform,
@@ -105,12 +150,7 @@ fn native_artifacts_for_platform(
 fn extract_native_jar(jar_path: &Path, destination: &Path, library: &Library) -> Result<()> {
     let file = File::open(jar_path)?;
     let mut archive = ZipArchive::new(file)?;
-    let excluded = library
-        .extract
-        .as_ref()
-        .and_then(|extract| extract.get("exclude"))
-        .cloned()
-        .unwrap_or_default();
+    let excluded = excluded_entries(library);
 
     for index in 0..archive.len() {
         let mut entry = archive.by_index(index)?;
@@ -139,6 +179,15 @@ fn extract_native_jar(jar_path: &Path, destination: &Path, library: &Library) ->
     Ok(())
 }
 
+fn excluded_entries(library: &Library) -> Vec<String> {
+    library
+        .extract
+        .as_ref()
+        .and_then(|extract| extract.get("exclude"))
+        .cloned()
+        .unwrap_or_default()
+}
+
 fn should_skip_entry(name: &str, excluded: &[String]) -> bool {
     name.starts_with("META-INF/") || excluded.iter().any(|prefix| name.starts_with(prefix))
 }
@@ -1,5 +1,7 @@
 //! Install request and result types used by [`crate::launcher::Launcher`].
 
+use std::path::PathBuf;
+
 use crate::loader::common::LoaderSpec;
 
 /// Describes the profile that should be installed.
@@ -31,15 +33,17 @@ impl InstallRequest {
 }
 
 /// Controls how install code should handle Java runtime needs.
-///
-/// The current high-level facade does not bundle Java. `Auto` is retained as
-/// the default policy for future runtime management and compatibility with the
-/// public request shape.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JavaInstallPolicy {
-    /// Allow the launcher core to manage Java if a future implementation can do so.
+    /// Install the Mojang runtime named by the version's `javaVersion.component`
+    /// under `runtime/` and use it for loader installers.
+    ///
+    /// Versions without `javaVersion`, and platforms Mojang publishes no
+    /// runtime for, are installed without Java.
     Auto,
     /// Never install or manage Java automatically.
+    ///
+    /// Loader installers run with `java` from `PATH`.
     Never,
 }
 
@@ -48,4 +52,7 @@ pub enum JavaInstallPolicy {
 pub struct InstallResult {
     /// Version/profile id that should be loaded and launched.
     pub version_id: String,
+    /// Java executable of the runtime installed for this profile, when
+    /// [`JavaInstallPolicy::Auto`] installed one.
+    pub java_executable: Option<PathBuf>,
 }
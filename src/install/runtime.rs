@@ -0,0 +1,351 @@
+//! Mojang Java runtime installation.
+//!
+//! Runtimes are installed with the same layout as the official launcher:
+//! `runtime/<component>/<platform>/<component>` holds the Java home,
+//! `runtime/<component>/<platform>/.version` records the installed release, and
+//! `runtime/<component>/<platform>/<component>.sha1` lists installed file
+//! hashes for [`crate::runtime::verify_runtime`].
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::version::DownloadInfo,
+    io::paths::safe_join,
+    net::{
+        download::{
+            execute_plan_with_options, Checksum, DownloadOptions, DownloadPlan, DownloadTask,
+        },
+        http::get_json_with_mirrors,
+        mirror::{Endpoints, MirrorConfig},
+    },
+    platform::{Arch, Os, Platform},
+    progress::{InstallStage, ProgressEvent, ProgressReporter},
+    LauncherError, Result,
+};
+
+/// Mojang runtime index keyed by platform key, then by component name.
+pub type RuntimeIndex = HashMap<String, BTreeMap<String, Vec<RuntimeRelease>>>;
+
+/// One runtime release listed in the [`RuntimeIndex`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct RuntimeRelease {
+    /// File manifest download for this release.
+    pub manifest: DownloadInfo,
+    /// Release name and date.
+    pub version: RuntimeReleaseVersion,
+}
+
+/// Version information for a [`RuntimeRelease`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RuntimeReleaseVersion {
+    /// Java version name, such as `17.0.8`.
+    pub name: String,
+    /// RFC 3339 release timestamp.
+    pub released: String,
+}
+
+/// File manifest of one runtime release.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct RuntimeManifest {
+    /// Entries keyed by path relative to the Java home.
+    pub files: BTreeMap<String, RuntimeFile>,
+}
+
+/// One entry of a [`RuntimeManifest`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum RuntimeFile {
+    /// Regular file.
+    File {
+        /// Download metadata for the file.
+        downloads: RuntimeFileDownloads,
+        /// Whether the file needs executable permissions.
+        #[serde(default)]
+        executable: bool,
+    },
+    /// Directory.
+    Directory,
+    /// Symbolic link.
+    Link {
+        /// Link target relative to the directory that contains the link.
+        target: String,
+    },
+}
+
+/// Download variants of a runtime file.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct RuntimeFileDownloads {
+    /// Uncompressed file.
+    pub raw: DownloadInfo,
+    /// LZMA-compressed file, when offered.
+    pub lzma: Option<DownloadInfo>,
+}
+
+/// Returns the key Mojang uses for a platform in the [`RuntimeIndex`].
+///
+/// Returns `None` for platforms Mojang does not publish runtimes for.
+pub fn runtime_platform_key(platform: Platform) -> Option<&'static str> {
+    match (platform.os, platform.arch) {
+        (Os::Windows, Arch::X86) => Some("windows-x86"),
+        (Os::Windows, Arch::X86_64) => Some("windows-x64"),
+        (Os::Windows, Arch::Aarch64) => Some("windows-arm64"),
+        (Os::Linux, Arch::X86) => Some("linux-i386"),
+        (Os::Linux, Arch::X86_64) => Some("linux"),
+        (Os::MacOs, Arch::X86_64) => Some("mac-os"),
+        (Os::MacOs, Arch::Aarch64) => Some("mac-os-arm64"),
+        _ => None,
+    }
+}
+
+/// Returns the directory that holds a runtime component for one platform.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if Mojang publishes no runtimes for
+/// `platform` or if `component` is not a plain directory name.
+pub fn runtime_platform_dir(
+    minecraft_dir: impl AsRef<Path>,
+    component: &str,
+    platform: Platform,
+) -> Result<PathBuf> {
+    let key = runtime_platform_key(platform).ok_or_else(|| unsupported(platform))?;
+    Ok(safe_join(minecraft_dir.as_ref().join("runtime"), component)?.join(key))
+}
+
+/// Returns the Java home of a runtime component.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if Mojang publishes no runtimes for
+/// `platform` or if `component` is not a plain directory name.
+pub fn runtime_home(
+    minecraft_dir: impl AsRef<Path>,
+    component: &str,
+    platform: Platform,
+) -> Result<PathBuf> {
+    safe_join(
+        runtime_platform_dir(minecraft_dir, component, platform)?,
+        component,
+    )
+}
+
+/// Returns the `java` executable inside a runtime home, if present.
+///
+/// Both the flat layout and the macOS `jre.bundle` layout are checked.
+pub fn find_java_executable(home: impl AsRef<Path>) -> Option<PathBuf> {
+    let home = home.as_ref();
+    [
+        home.join("bin").join("java"),
+        home.join("bin").join("java.exe"),
+        home.join("jre.bundle/Contents/Home/bin/java"),
+    ]
+    .into_iter()
+    .find(|path| path.is_file())
+}
+
+/// Fetches the Mojang runtime index through the configured mirrors.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if every candidate URL fails or the index
+/// cannot be decoded.
+pub fn fetch_runtime_index_with_mirrors(mirrors: &MirrorConfig) -> Result<RuntimeIndex> {
+    get_json_with_mirrors(&Endpoints::official().java_runtime_manifest, mirrors)
+}
+
+/// Returns the newest release of `component` for `platform`.
+pub fn select_runtime_release<'a>(
+    index: &'a RuntimeIndex,
+    component: &str,
+    platform: Platform,
+) -> Option<&'a RuntimeRelease> {
+    index
+        .get(runtime_platform_key(platform)?)?
+        .get(component)?
+        .first()
+}
+
+/// Plans downloads for every regular file in a runtime manifest.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if a manifest path escapes `home`.
+pub fn plan_runtime_downloads(
+    manifest: &RuntimeManifest,
+    home: impl AsRef<Path>,
+) -> Result<DownloadPlan> {
+    let home = home.as_ref();
+    let mut plan = DownloadPlan::default();
+    for (path, file) in &manifest.files {
+        if let RuntimeFile::File { downloads, .. } = file {
+            plan.tasks.push(DownloadTask {
+                url: downloads.raw.url.clone(),
+                destination: safe_join(home, path)?,
+                checksum: Some(Checksum::Sha1(downloads.raw.sha1.clone())),
+                size: u64::try_from(downloads.raw.size).ok(),
+                label: format!("runtime {path}"),
+            });
+        }
+    }
+    Ok(plan)
+}
+
+/// Installs the files of a runtime manifest into `home`.
+///
+/// Directories are created first, regular files are downloaded through
+/// [`execute_plan_with_options`], and executable files receive `0o755`
+/// permissions on Unix. Symbolic links are created on Unix only; their targets
+/// must resolve inside `home`.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] for download, checksum, filesystem, or
+/// unsafe path failures.
+pub fn install_runtime_manifest(
+    manifest: &RuntimeManifest,
+    home: impl AsRef<Path>,
+    options: &DownloadOptions,
+    reporter: &mut dyn ProgressReporter,
+) -> Result<()> {
+    let home = home.as_ref();
+    fs::create_dir_all(home)?;
+    for (path, file) in &manifest.files {
+        if matches!(file, RuntimeFile::Directory) {
+            fs::create_dir_all(safe_join(home, path)?)?;
+        }
+    }
+
+    execute_plan_with_options(&plan_runtime_downloads(manifest, home)?, options, reporter)?;
+
+    for (path, file) in &manifest.files {
+        match file {
+            RuntimeFile::File {
+                executable: true, ..
+            } => set_executable(&safe_join(home, path)?)?,
+            RuntimeFile::Link { target } => create_link(home, path, target)?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Installs a Mojang runtime component and returns its `java` executable.
+///
+/// Returns `Ok(None)` when Mojang does not publish `component` for `platform`,
+/// so callers can fall back to a Java installation of their own.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] for metadata, download, checksum,
+/// filesystem, or unsafe path failures, or if the installed runtime contains no
+/// `java` executable.
+pub fn install_runtime_with_options(
+    component: &str,
+    minecraft_dir: impl AsRef<Path>,
+    platform: Platform,
+    options: &DownloadOptions,
+    reporter: &mut dyn ProgressReporter,
+) -> Result<Option<PathBuf>> {
+    let minecraft_dir = minecraft_dir.as_ref();
+    if runtime_platform_key(platform).is_none() {
+        return Ok(None);
+    }
+    let index = fetch_runtime_index_with_mirrors(&options.mirrors)?;
+    let Some(release) = select_runtime_release(&index, component, platform) else {
+        return Ok(None);
+    };
+
+    reporter.report(ProgressEvent::StageStarted {
+        stage: InstallStage::InstallRuntime,
+    });
+    let manifest: RuntimeManifest = get_json_with_mirrors(&release.manifest.url, &options.mirrors)?;
+    let home = runtime_home(minecraft_dir, component, platform)?;
+    install_runtime_manifest(&manifest, &home, options, reporter)?;
+
+    let platform_dir = runtime_platform_dir(minecraft_dir, component, platform)?;
+    fs::write(platform_dir.join(".version"), &release.version.name)?;
+    crate::runtime::write_sha1_index(
+        &manifest,
+        &home,
+        platform_dir.join(format!("{component}.sha1")),
+    )?;
+
+    find_java_executable(&home)
+        .map(Some)
+        .ok_or_else(|| LauncherError::MissingField {
+            context: format!("java runtime {component}"),
+            field: "bin/java".to_string(),
+        })
+}
+
+fn unsupported(platform: Platform) -> LauncherError {
+    LauncherError::UnsupportedPlatform {
+        os: platform.minecraft_os_name().to_string(),
+        arch: format!("{:?}", platform.arch).to_lowercase(),
+    }
+}
+
+/// Resolves a link target relative to the link's directory and checks that it
+/// stays inside `home`.
+fn link_target_inside(home: &Path, link: &str, target: &str) -> Result<PathBuf> {
+    let escapes = || LauncherError::UnsafePath {
+        base: home.to_path_buf(),
+        path: Path::new(link)
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(target),
+    };
+    let mut resolved = Path::new(link)
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    for component in Path::new(target).components() {
+        match component {
+            Component::Normal(part) => resolved.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !resolved.pop() {
+                    return Err(escapes());
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return Err(escapes()),
+        }
+    }
+    safe_join(home, resolved)
+}
+
+fn create_link(home: &Path, link: &str, target: &str) -> Result<()> {
+    link_target_inside(home, link, target)?;
+    let path = safe_join(home, link)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    #[cfg(unix)]
+    {
+        if fs::symlink_metadata(&path).is_ok() {
+            fs::remove_file(&path)?;
+        }
+        std::os::unix::fs::symlink(target, &path)?;
+    }
+    Ok(())
+}
+
+fn set_executable(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mut permissions = fs::metadata(path)?.permissions();
+        permissions.set_mode(permissions.mode() | 0o755);
+        fs::set_permissions(path, permissions)?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
@@ -0,0 +1,151 @@
+//! Removal of installed version profiles.
+//!
+//! Uninstalling deletes `versions/<id>`, which holds the profile JSON, client
+//! jar, and extracted natives. Loader profiles such as
+//! `fabric-loader-0.15.0-1.20.1` name their base version in `inheritsFrom`, so
+//! removing a base version breaks them; [`uninstall_version`] refuses unless
+//! [`DependentsPolicy::Cascade`] is selected. Shared libraries and assets are
+//! left in place unless a garbage collection pass is requested.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Component, Path},
+};
+
+use crate::{
+    install::{
+        client::{installed_version_ids, read_version_json},
+        gc::{collect_garbage, GcReport},
+    },
+    io::paths::safe_join,
+    LauncherError, Result,
+};
+
+/// What to do when other installed profiles inherit from the removed version.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DependentsPolicy {
+    /// Fail with [`LauncherError::VersionInUse`] and remove nothing.
+    #[default]
+    Refuse,
+    /// Remove the dependent profiles as well.
+    Cascade,
+}
+
+/// Options for [`uninstall_version`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UninstallOptions {
+    /// Handling of profiles that inherit from the removed version.
+    pub dependents: DependentsPolicy,
+    /// Run [`collect_garbage`] after the profiles are removed.
+    pub collect_garbage: bool,
+}
+
+/// Outcome of an uninstall.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UninstallResult {
+    /// Removed version ids, dependents before the versions they inherit from.
+    pub removed: Vec<String>,
+    /// Files reclaimed by the garbage collection pass, when one was requested.
+    pub garbage: Option<GcReport>,
+}
+
+/// Lists installed profiles that inherit from `version_id`, directly or
+/// through other profiles.
+///
+/// Profiles are ordered so that each one comes before any profile it inherits
+/// from, which is a safe removal order.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the versions directory or an installed
+/// profile cannot be read.
+pub fn dependent_versions(
+    minecraft_dir: impl AsRef<Path>,
+    version_id: &str,
+) -> Result<Vec<String>> {
+    let minecraft_dir = minecraft_dir.as_ref();
+    let mut children: HashMap<String, Vec<String>> = HashMap::new();
+    for id in installed_version_ids(minecraft_dir)? {
+        if id == version_id {
+            continue;
+        }
+        if let Some(parent) = read_version_json(minecraft_dir, &id)?.inherits_from {
+            children.entry(parent).or_default().push(id);
+        }
+    }
+
+    let mut dependents = Vec::new();
+    let mut visited = HashSet::from([version_id.to_string()]);
+    push_dependents(version_id, &children, &mut visited, &mut dependents);
+    Ok(dependents)
+}
+
+/// Removes an installed version profile.
+///
+/// # Errors
+///
+/// Returns [`LauncherError::InvalidVersionId`] if the version is not
+/// installed, [`LauncherError::VersionInUse`] if other profiles inherit from it
+/// and `options.dependents` is [`DependentsPolicy::Refuse`], or another
+/// [`crate::LauncherError`] if a directory cannot be read or removed.
+pub fn uninstall_version(
+    minecraft_dir: impl AsRef<Path>,
+    version_id: &str,
+    options: &UninstallOptions,
+) -> Result<UninstallResult> {
+    let minecraft_dir = minecraft_dir.as_ref();
+    let versions_dir = minecraft_dir.join("versions");
+    let version_dir = safe_join(&versions_dir, version_id)?;
+    let single_component = matches!(
+        Path::new(version_id)
+            .components()
+            .collect::<Vec<_>>()
+            .as_slice(),
+        [Component::Normal(_)]
+    );
+    if !single_component || !version_dir.is_dir() {
+        return Err(LauncherError::InvalidVersionId {
+            id: version_id.to_string(),
+        });
+    }
+
+    let mut removed = dependent_versions(minecraft_dir, version_id)?;
+    if !removed.is_empty() && options.dependents == DependentsPolicy::Refuse {
+        return Err(LauncherError::VersionInUse {
+            id: version_id.to_string(),
+            dependents: removed,
+        });
+    }
+    removed.push(version_id.to_string());
+    for id in &removed {
+        fs::remove_dir_all(safe_join(&versions_dir, id)?)?;
+    }
+
+    let garbage = if options.collect_garbage {
+        Some(collect_garbage(minecraft_dir)?)
+    } else {
+        None
+    };
+    Ok(UninstallResult { removed, garbage })
+}
+
+/// Appends the descendants of `id`, deepest first.
+fn push_dependents(
+    id: &str,
+    children: &HashMap<String, Vec<String>>,
+    visited: &mut HashSet<String>,
+    out: &mut Vec<String>,
+) {
+    let Some(direct) = children.get(id) else {
+        return;
+    };
+    for child in direct {
+        // Guard against inheritance cycles in hand-edited profiles.
+        if !visited.insert(child.clone()) {
+            continue;
+        }
+        push_dependents(child, children, visited, out);
+        out.push(child.clone());
+    }
+}
@@ -12,8 +12,9 @@ use crate::{
 
 /// Builds a download plan for the current platform.
 ///
-/// The plan includes the client jar, libraries, and asset index. Asset objects
-/// are planned after the index has been downloaded and read.
+/// The plan includes the client jar, libraries, asset index, and client logging
+/// configuration. Asset objects are planned after the index has been downloaded
+/// and read.
 ///
 /// # Errors
 ///
@@ -64,6 +65,7 @@ pub fn plan_vanilla_downloads_for_platform(
                 .join(jar_id)
                 .join(format!("{jar_id}.jar")),
             checksum: Some(Checksum::Sha1(client.sha1.clone())),
+            size: u64::try_from(client.size).ok(),
             label: format!("client {jar_id}"),
         });
     }
@@ -78,5 +80,9 @@ pub fn plan_vanilla_downloads_for_platform(
         version,
         minecraft_dir,
     )?);
+    plan.tasks.extend(super::assets::plan_log_config_download(
+        version,
+        minecraft_dir,
+    )?);
     Ok(plan)
 }
@@ -0,0 +1,259 @@
+//! Verification and repair of installed version files.
+//!
+//! Verification rebuilds the download plan an install would execute for a
+//! merged version (client jar, libraries, asset index, logging configuration,
+//! and, when the asset index is intact, every asset object) and checks each
+//! destination against its expected size and checksum without downloading
+//! anything. Extracted natives are compared with the entry sizes of their
+//! native jars.
+
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    compatibility::{apply_compatibility, CompatibilityPolicy},
+    core::version::VersionJson,
+    install::{
+        assets::{
+            asset_index_path, install_assets_with_options, plan_asset_object_downloads_from_index,
+            AssetIndexJson,
+        },
+        natives::{
+            expected_native_files_for_platform, extract_natives_for_platform, natives_directory,
+        },
+        vanilla::plan_vanilla_downloads_for_platform,
+    },
+    net::download::{execute_plan_with_options, DownloadOptions, DownloadPlan, DownloadTask},
+    platform::Platform,
+    progress::{InstallStage, ProgressEvent, ProgressReporter},
+    LauncherError, Result,
+};
+
+/// Result of checking an installed version's files.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstallVerification {
+    /// Number of files checked.
+    pub checked: usize,
+    /// Expected files that do not exist.
+    pub missing: Vec<PathBuf>,
+    /// Files whose size or checksum differs from the metadata.
+    pub corrupt: Vec<PathBuf>,
+    /// Existing files the metadata provides no checksum for.
+    pub unverifiable: Vec<PathBuf>,
+}
+
+impl InstallVerification {
+    /// Returns true when no file is missing or corrupt.
+    ///
+    /// Unverifiable files do not make a verification fail.
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.corrupt.is_empty()
+    }
+
+    fn needs_repair(&self, path: &Path) -> bool {
+        self.missing.iter().chain(&self.corrupt).any(|p| p == path)
+    }
+}
+
+/// Builds the plan of every file an install of `version` writes.
+///
+/// Asset objects are included only when the local asset index exists and
+/// matches its checksum, because the index is what lists them.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if required metadata is missing, a library
+/// coordinate is invalid, or the asset index cannot be read.
+pub fn plan_installed_files(
+    version: &VersionJson,
+    minecraft_dir: impl AsRef<Path>,
+    platform: Platform,
+) -> Result<DownloadPlan> {
+    let compatibility = apply_compatibility(version, platform, CompatibilityPolicy::Auto);
+    plan_patched_files(&compatibility.version, minecraft_dir.as_ref(), platform)
+}
+
+/// Checks the installed files of a merged version.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the expected files cannot be planned or
+/// an existing file cannot be read.
+pub fn verify_version_files(
+    version: &VersionJson,
+    minecraft_dir: impl AsRef<Path>,
+    platform: Platform,
+) -> Result<InstallVerification> {
+    let minecraft_dir = minecraft_dir.as_ref();
+    let compatibility = apply_compatibility(version, platform, CompatibilityPolicy::Auto);
+    let version = &compatibility.version;
+    let plan = plan_patched_files(version, minecraft_dir, platform)?;
+
+    let mut report = InstallVerification::default();
+    let mut seen = HashSet::new();
+    for task in &plan.tasks {
+        if !seen.insert(task.destination.as_path()) {
+            continue;
+        }
+        report.checked += 1;
+        match task_state(task)? {
+            FileState::Valid => {}
+            FileState::Missing => report.missing.push(task.destination.clone()),
+            FileState::Corrupt => report.corrupt.push(task.destination.clone()),
+            FileState::Unverifiable => report.unverifiable.push(task.destination.clone()),
+        }
+    }
+
+    for (path, size) in expected_native_files_for_platform(
+        &version.libraries,
+        minecraft_dir,
+        version_id(version)?,
+        platform,
+    )? {
+        report.checked += 1;
+        match fs::metadata(&path) {
+            Ok(metadata) if metadata.len() == size => {}
+            Ok(_) => report.corrupt.push(path),
+            Err(_) => report.missing.push(path),
+        }
+    }
+    Ok(report)
+}
+
+/// Verifies a merged version and re-downloads missing or corrupt files.
+///
+/// Only files reported missing or corrupt are downloaded. Asset objects are
+/// re-planned when the asset index itself was repaired, and natives are
+/// extracted again when an extracted file or a native jar was repaired.
+/// Unverifiable files are left in place. The returned report describes the
+/// state found before the repair.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if verification or a download fails, or if
+/// a broken file has no download URL, such as a library generated by a Forge
+/// installer processor.
+pub fn repair_version_files(
+    version: &VersionJson,
+    minecraft_dir: impl AsRef<Path>,
+    platform: Platform,
+    options: &DownloadOptions,
+    reporter: &mut dyn ProgressReporter,
+) -> Result<InstallVerification> {
+    let minecraft_dir = minecraft_dir.as_ref();
+    reporter.report(ProgressEvent::StageStarted {
+        stage: InstallStage::Verify,
+    });
+    let report = verify_version_files(version, minecraft_dir, platform)?;
+    if report.is_ok() {
+        return Ok(report);
+    }
+
+    let compatibility = apply_compatibility(version, platform, CompatibilityPolicy::Auto);
+    let version = &compatibility.version;
+    let version_id = version_id(version)?;
+    let plan = plan_patched_files(version, minecraft_dir, platform)?;
+    let mut repair = DownloadPlan::default();
+    for task in plan.tasks {
+        if !report.needs_repair(&task.destination) {
+            continue;
+        }
+        if task.url.is_empty() {
+            return Err(LauncherError::Other {
+                message: format!(
+                    "{} has no download URL; reinstall {version_id} to restore it",
+                    task.destination.display()
+                ),
+            });
+        }
+        repair.tasks.push(task);
+    }
+    execute_plan_with_options(&repair, options, reporter)?;
+
+    if let Some(asset_index) = &version.asset_index {
+        if report.needs_repair(&asset_index_path(minecraft_dir, &asset_index.id)) {
+            install_assets_with_options(version, minecraft_dir, options, reporter)?;
+        }
+    }
+    let natives_dir = natives_directory(minecraft_dir, version_id);
+    let libraries_dir = minecraft_dir.join("libraries");
+    if report
+        .missing
+        .iter()
+        .chain(&report.corrupt)
+        .any(|path| path.starts_with(&natives_dir) || path.starts_with(&libraries_dir))
+    {
+        reporter.report(ProgressEvent::StageStarted {
+            stage: InstallStage::ExtractNatives,
+        });
+        extract_natives_for_platform(&version.libraries, minecraft_dir, version_id, platform)?;
+    }
+    Ok(report)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileState {
+    Valid,
+    Missing,
+    Corrupt,
+    Unverifiable,
+}
+
+fn task_state(task: &DownloadTask) -> Result<FileState> {
+    let Ok(metadata) = fs::metadata(&task.destination) else {
+        return Ok(FileState::Missing);
+    };
+    if !metadata.is_file() {
+        return Ok(FileState::Missing);
+    }
+    if task.size.is_some_and(|size| size != metadata.len()) {
+        return Ok(FileState::Corrupt);
+    }
+    match &task.checksum {
+        Some(checksum) if checksum.matches_file(&task.destination)? => Ok(FileState::Valid),
+        Some(_) => Ok(FileState::Corrupt),
+        None => Ok(FileState::Unverifiable),
+    }
+}
+
+/// Plans files for a version that already had compatibility patches applied.
+fn plan_patched_files(
+    version: &VersionJson,
+    minecraft_dir: &Path,
+    platform: Platform,
+) -> Result<DownloadPlan> {
+    let mut plan = plan_vanilla_downloads_for_platform(
+        version,
+        minecraft_dir,
+        platform,
+        CompatibilityPolicy::Disabled,
+    )?;
+    if let Some(asset_index) = &version.asset_index {
+        let index_path = asset_index_path(minecraft_dir, &asset_index.id);
+        let index_task = plan
+            .tasks
+            .iter()
+            .find(|task| task.destination == index_path);
+        if let Some(task) = index_task {
+            if task_state(task)? == FileState::Valid {
+                let index: AssetIndexJson = serde_json::from_slice(&fs::read(&index_path)?)?;
+                plan.tasks
+                    .extend(plan_asset_object_downloads_from_index(&index, minecraft_dir).tasks);
+            }
+        }
+    }
+    Ok(plan)
+}
+
+fn version_id(version: &VersionJson) -> Result<&str> {
+    version
+        .id
+        .as_deref()
+        .ok_or_else(|| LauncherError::MissingField {
+            context: "version json".to_string(),
+            field: "id".to_string(),
+        })
+}
@@ -0,0 +1,495 @@
+//! Mods, resource packs, and shader packs in a game directory.
+//!
+//! [`ContentManager`] lists, installs, enables, disables, and deletes the
+//! entries of `mods/`, `resourcepacks/`, and `shaderpacks/`. Entries are
+//! disabled the way other launchers do it, by appending
+//! [`DISABLED_SUFFIX`] to the file or directory name, and names starting with
+//! `.` are left alone. The resource pack order is kept in the
+//! `resourcePacks:` line of `options.txt`.
+
+use std::{
+    fs::{self, File},
+    io::{ErrorKind, Read, Seek},
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+use zip::{result::ZipError, ZipArchive};
+
+use crate::{
+    mods::metadata::{read_mod_metadata, ModMetadata, DISABLED_SUFFIX},
+    net::download::{execute_plan_with_options, DownloadOptions, DownloadPlan, DownloadTask},
+    progress::ProgressReporter,
+    LauncherError, Result,
+};
+
+use super::copy_dir;
+
+/// Game settings file holding the resource pack order.
+pub const OPTIONS_FILE: &str = "options.txt";
+
+/// Prefix of resource packs from `resourcepacks/` in the resource pack order;
+/// built-in packs such as `vanilla` have none.
+pub const RESOURCE_PACK_PREFIX: &str = "file/";
+
+/// Key of the resource pack order in `options.txt`.
+const RESOURCE_PACKS_KEY: &str = "resourcePacks:";
+
+/// Kind of content a game directory holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContentKind {
+    /// Mod jars in `mods/`.
+    Mod,
+    /// Resource packs in `resourcepacks/`, as zips or directories.
+    ResourcePack,
+    /// Shader packs in `shaderpacks/`, as zips or directories.
+    ShaderPack,
+}
+
+impl ContentKind {
+    /// Returns the directory holding this kind of content, relative to the
+    /// game directory.
+    pub fn directory(self) -> &'static str {
+        match self {
+            Self::Mod => "mods",
+            Self::ResourcePack => "resourcepacks",
+            Self::ShaderPack => "shaderpacks",
+        }
+    }
+
+    /// Returns whether an entry with this name, without the
+    /// [`DISABLED_SUFFIX`], is content of this kind: a `.jar` or `.zip` mod, or
+    /// a `.zip` or directory pack.
+    fn accepts(self, name: &str, is_directory: bool) -> bool {
+        match self {
+            Self::Mod => !is_directory && (name.ends_with(".jar") || name.ends_with(".zip")),
+            Self::ResourcePack | Self::ShaderPack => is_directory || name.ends_with(".zip"),
+        }
+    }
+}
+
+/// Metadata read from a content entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentMetadata {
+    /// Mods declared by a mod jar; empty for plain libraries.
+    Mods(Vec<ModMetadata>),
+    /// The `pack.mcmeta` of a resource pack.
+    Pack(PackMetadata),
+    /// No metadata, as for shader packs and resource packs without
+    /// `pack.mcmeta`.
+    None,
+}
+
+/// Metadata of a resource pack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackMetadata {
+    /// Pack format the pack was made for.
+    pub pack_format: Option<u32>,
+    /// Description, flattened to plain text.
+    pub description: String,
+    /// `pack.png` bytes.
+    pub icon: Option<Vec<u8>>,
+}
+
+/// One mod, resource pack, or shader pack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentEntry {
+    /// Kind of content.
+    pub kind: ContentKind,
+    /// Name without the [`DISABLED_SUFFIX`], used to refer to the entry.
+    pub name: String,
+    /// Path of the file or directory.
+    pub path: PathBuf,
+    /// Whether the entry is loaded by the game.
+    pub enabled: bool,
+    /// Whether the entry is a directory rather than an archive.
+    pub is_directory: bool,
+    /// Metadata read from the entry.
+    pub metadata: ContentMetadata,
+    /// Why the metadata could not be read, if it could not.
+    pub error: Option<String>,
+}
+
+/// Manages the content of one game directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentManager {
+    game_directory: PathBuf,
+}
+
+impl ContentManager {
+    /// Creates a manager for a game directory.
+    pub fn new(game_directory: impl Into<PathBuf>) -> Self {
+        Self {
+            game_directory: game_directory.into(),
+        }
+    }
+
+    /// Returns the managed game directory.
+    pub fn game_directory(&self) -> &Path {
+        &self.game_directory
+    }
+
+    /// Returns the directory holding content of `kind`.
+    pub fn directory(&self, kind: ContentKind) -> PathBuf {
+        self.game_directory.join(kind.directory())
+    }
+
+    /// Lists the enabled and disabled entries of `kind`, sorted by name, with
+    /// their metadata.
+    ///
+    /// Entries whose metadata cannot be read are listed with
+    /// [`ContentEntry::error`] set. A missing directory yields an empty list.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the directory cannot be listed.
+    pub fn list(&self, kind: ContentKind) -> Result<Vec<ContentEntry>> {
+        let entries = match fs::read_dir(self.directory(kind)) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut content = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let is_directory = entry.file_type()?.is_dir();
+            let enabled = !file_name.ends_with(DISABLED_SUFFIX);
+            let name = file_name
+                .strip_suffix(DISABLED_SUFFIX)
+                .unwrap_or(&file_name);
+            if name.starts_with('.') || !kind.accepts(name, is_directory) {
+                continue;
+            }
+            let path = entry.path();
+            let (metadata, error) = match read_metadata(kind, &path, is_directory) {
+                Ok(metadata) => (metadata, None),
+                Err(err) => (ContentMetadata::None, Some(err.to_string())),
+            };
+            content.push(ContentEntry {
+                kind,
+                name: name.to_string(),
+                path,
+                enabled,
+                is_directory,
+                metadata,
+                error,
+            });
+        }
+        content.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(content)
+    }
+
+    /// Enables or disables an entry by adding or removing the
+    /// [`DISABLED_SUFFIX`], returning its new path. Entries already in the
+    /// requested state are left as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError::ContentNotFound`] if no entry has this name,
+    /// [`LauncherError::ContentExists`] if both an enabled and a disabled copy
+    /// exist, or another [`crate::LauncherError`] if the rename fails.
+    pub fn set_enabled(&self, kind: ContentKind, name: &str, enabled: bool) -> Result<PathBuf> {
+        let current = self.find(kind, name)?;
+        let target = self.entry_path(kind, name, enabled)?;
+        if current == target {
+            return Ok(target);
+        }
+        if target.exists() {
+            return Err(LauncherError::ContentExists { path: target });
+        }
+        fs::rename(&current, &target)?;
+        Ok(target)
+    }
+
+    /// Copies a local file or directory into the directory for `kind`, enabled,
+    /// under its own name, returning the installed path.
+    ///
+    /// The copy is written next to its destination and renamed into place, so
+    /// an interrupted install never leaves a partial entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError::ContentExists`] if an entry with this name is
+    /// installed, or another [`crate::LauncherError`] if `source` is not
+    /// content of `kind` or cannot be copied.
+    pub fn install_file(&self, kind: ContentKind, source: impl AsRef<Path>) -> Result<PathBuf> {
+        let source = source.as_ref();
+        let is_directory = source.is_dir();
+        let name = source
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let destination = self.vacant_path(kind, &name, is_directory)?;
+        let partial = destination.with_file_name(format!(".{name}.part"));
+        if partial.is_dir() {
+            fs::remove_dir_all(&partial)?;
+        }
+        let copied = if is_directory {
+            copy_dir(source, &partial)
+        } else {
+            fs::copy(source, &partial).map(|_| ()).map_err(Into::into)
+        };
+        if let Err(err) = copied.and_then(|()| Ok(fs::rename(&partial, &destination)?)) {
+            if is_directory {
+                let _ = fs::remove_dir_all(&partial);
+            } else {
+                let _ = fs::remove_file(&partial);
+            }
+            return Err(err);
+        }
+        Ok(destination)
+    }
+
+    /// Downloads a file into the directory for `kind`, enabled, under the file
+    /// name of the task's destination, returning the installed path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError::ContentExists`] if an entry with this name is
+    /// installed, or another [`crate::LauncherError`] if the file name is not
+    /// content of `kind` or the download fails.
+    pub fn install_download(
+        &self,
+        kind: ContentKind,
+        task: &DownloadTask,
+        options: &DownloadOptions,
+        reporter: &mut dyn ProgressReporter,
+    ) -> Result<PathBuf> {
+        let name = task
+            .destination
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let destination = self.vacant_path(kind, &name, false)?;
+        let plan = DownloadPlan {
+            tasks: vec![DownloadTask {
+                destination: destination.clone(),
+                ..task.clone()
+            }],
+        };
+        execute_plan_with_options(&plan, options, reporter)?;
+        Ok(destination)
+    }
+
+    /// Deletes an entry, enabled or disabled. Deleted resource packs are also
+    /// removed from the resource pack order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError::ContentNotFound`] if no entry has this name, or
+    /// another [`crate::LauncherError`] if it or `options.txt` cannot be
+    /// changed.
+    pub fn delete(&self, kind: ContentKind, name: &str) -> Result<()> {
+        let path = self.find(kind, name)?;
+        if path.is_dir() {
+            fs::remove_dir_all(&path)?;
+        } else {
+            fs::remove_file(&path)?;
+        }
+        if kind == ContentKind::ResourcePack {
+            let id = format!("{RESOURCE_PACK_PREFIX}{name}");
+            let order = self.resource_pack_order()?;
+            if order.contains(&id) {
+                let order = order
+                    .into_iter()
+                    .filter(|pack| *pack != id)
+                    .collect::<Vec<_>>();
+                self.set_resource_pack_order(&order)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the enabled resource packs from `options.txt` in the order the
+    /// game stores them, lowest priority first, such as
+    /// `["vanilla", "file/Faithful.zip"]`.
+    ///
+    /// A missing file or setting yields an empty list.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the file cannot be read or the
+    /// setting is not a JSON list of strings.
+    pub fn resource_pack_order(&self) -> Result<Vec<String>> {
+        let options = match fs::read_to_string(self.game_directory.join(OPTIONS_FILE)) {
+            Ok(options) => options,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        match options
+            .lines()
+            .find_map(|line| line.strip_prefix(RESOURCE_PACKS_KEY))
+        {
+            Some(value) => Ok(serde_json::from_str(value.trim())?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Writes the resource pack order to `options.txt`, lowest priority
+    /// first, keeping every other setting. Packs from `resourcepacks/` are
+    /// named with the [`RESOURCE_PACK_PREFIX`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the file cannot be read or written.
+    pub fn set_resource_pack_order(&self, packs: &[String]) -> Result<()> {
+        let path = self.game_directory.join(OPTIONS_FILE);
+        let options = match fs::read_to_string(&path) {
+            Ok(options) => options,
+            Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err.into()),
+        };
+        let setting = format!("{RESOURCE_PACKS_KEY}{}", serde_json::to_string(packs)?);
+        let mut replaced = false;
+        let mut lines = options
+            .lines()
+            .map(|line| {
+                if !replaced && line.starts_with(RESOURCE_PACKS_KEY) {
+                    replaced = true;
+                    setting.clone()
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+        if !replaced {
+            lines.push(setting);
+        }
+        fs::create_dir_all(&self.game_directory)?;
+        fs::write(path, lines.join("\n") + "\n")?;
+        Ok(())
+    }
+
+    /// Returns the installed path of an entry, enabled or disabled.
+    fn find(&self, kind: ContentKind, name: &str) -> Result<PathBuf> {
+        let enabled = self.entry_path(kind, name, true)?;
+        let disabled = self.entry_path(kind, name, false)?;
+        [enabled.clone(), disabled]
+            .into_iter()
+            .find(|path| path.exists())
+            .ok_or(LauncherError::ContentNotFound { path: enabled })
+    }
+
+    /// Returns where an entry named `name` is kept in the given state.
+    fn entry_path(&self, kind: ContentKind, name: &str, enabled: bool) -> Result<PathBuf> {
+        let directory = self.directory(kind);
+        if name.starts_with('.') || Path::new(name).file_name() != Some(name.as_ref()) {
+            return Err(LauncherError::UnsafePath {
+                base: directory,
+                path: PathBuf::from(name),
+            });
+        }
+        Ok(if enabled {
+            directory.join(name)
+        } else {
+            directory.join(format!("{name}{DISABLED_SUFFIX}"))
+        })
+    }
+
+    /// Checks that `name` is content of `kind` and not installed, and returns
+    /// the path to install it at, creating the content directory.
+    fn vacant_path(&self, kind: ContentKind, name: &str, is_directory: bool) -> Result<PathBuf> {
+        let destination = self.entry_path(kind, name, true)?;
+        if !kind.accepts(name, is_directory) {
+            return Err(LauncherError::Other {
+                message: format!("{name} is not a {} entry", kind.directory()),
+            });
+        }
+        for path in [&destination, &self.entry_path(kind, name, false)?] {
+            if path.exists() {
+                return Err(LauncherError::ContentExists { path: path.clone() });
+            }
+        }
+        fs::create_dir_all(self.directory(kind))?;
+        Ok(destination)
+    }
+}
+
+fn read_metadata(kind: ContentKind, path: &Path, is_directory: bool) -> Result<ContentMetadata> {
+    match kind {
+        ContentKind::Mod => Ok(ContentMetadata::Mods(read_mod_metadata(path)?)),
+        ContentKind::ResourcePack if is_directory => {
+            let icon = match fs::read(path.join("pack.png")) {
+                Ok(icon) => Some(icon),
+                Err(err) if err.kind() == ErrorKind::NotFound => None,
+                Err(err) => return Err(err.into()),
+            };
+            match fs::read(path.join("pack.mcmeta")) {
+                Ok(bytes) => Ok(ContentMetadata::Pack(pack_metadata(&bytes, icon)?)),
+                Err(err) if err.kind() == ErrorKind::NotFound => Ok(ContentMetadata::None),
+                Err(err) => Err(err.into()),
+            }
+        }
+        ContentKind::ResourcePack => {
+            let mut archive = ZipArchive::new(File::open(path)?)?;
+            let icon = read_entry(&mut archive, "pack.png")?;
+            match read_entry(&mut archive, "pack.mcmeta")? {
+                Some(bytes) => Ok(ContentMetadata::Pack(pack_metadata(&bytes, icon)?)),
+                None => Ok(ContentMetadata::None),
+            }
+        }
+        ContentKind::ShaderPack => Ok(ContentMetadata::None),
+    }
+}
+
+fn read_entry<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Result<Option<Vec<u8>>> {
+    match archive.by_name(name) {
+        Ok(mut entry) => {
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes)?;
+            Ok(Some(bytes))
+        }
+        Err(ZipError::FileNotFound) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[derive(Deserialize)]
+struct PackMcmeta {
+    pack: PackSection,
+}
+
+#[derive(Deserialize)]
+struct PackSection {
+    #[serde(default)]
+    pack_format: Option<u32>,
+    #[serde(default)]
+    description: serde_json::Value,
+}
+
+fn pack_metadata(bytes: &[u8], icon: Option<Vec<u8>>) -> Result<PackMetadata> {
+    let bytes = bytes.strip_prefix(b"\xef\xbb\xbf").unwrap_or(bytes);
+    let mcmeta: PackMcmeta = serde_json::from_slice(bytes)?;
+    let mut description = String::new();
+    component_text(&mcmeta.pack.description, &mut description);
+    Ok(PackMetadata {
+        pack_format: mcmeta.pack.pack_format,
+        description,
+        icon,
+    })
+}
+
+/// Appends the plain text of a JSON text component: a string, a list of
+/// components, or an object with `text` and `extra`.
+fn component_text(component: &serde_json::Value, text: &mut String) {
+    match component {
+        serde_json::Value::String(value) => text.push_str(value),
+        serde_json::Value::Number(value) => text.push_str(&value.to_string()),
+        serde_json::Value::Bool(value) => text.push_str(&value.to_string()),
+        serde_json::Value::Array(components) => {
+            for component in components {
+                component_text(component, text);
+            }
+        }
+        serde_json::Value::Object(object) => {
+            if let Some(value) = object.get("text") {
+                component_text(value, text);
+            }
+            if let Some(extra) = object.get("extra") {
+                component_text(extra, text);
+            }
+        }
+        serde_json::Value::Null => {}
+    }
+}
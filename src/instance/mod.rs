@@ -0,0 +1,479 @@
+//! Named game instances with isolated game directories.
+//!
+//! An instance lives in `<minecraft_dir>/instances/<name>` and stores its
+//! settings in `instance.json` next to a `.minecraft` game directory holding
+//! its own mods, configuration, saves, and options. Version profiles,
+//! libraries, assets, and runtimes stay in the shared Minecraft directory, so
+//! several instances of the same version share one install.
+//!
+//! ```text
+//! instances/
+//!   Survival/
+//!     instance.json
+//!     .minecraft/
+//!       mods/ config/ saves/ ...
+//! ```
+//!
+//! [`content`] manages the mods, resource packs, and shader packs inside a
+//! game directory.
+
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Component, Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::Account,
+    command::builder::LaunchOptions,
+    install::request::{InstallRequest, JavaInstallPolicy},
+    loader::{
+        common::{LoaderSpec, LoaderVersion},
+        fabric::fabric_installed_version_id,
+        forge::forge_installed_version_id,
+        neoforge::neoforge_installed_version_id,
+        quilt::quilt_installed_version_id,
+        LoaderKind,
+    },
+    LauncherError, Result,
+};
+
+pub mod content;
+
+use content::ContentManager;
+
+/// File name of the per-instance settings file.
+pub const INSTANCE_FILE: &str = "instance.json";
+
+/// Name of the game directory inside an instance directory.
+pub const GAME_DIRECTORY: &str = ".minecraft";
+
+/// Game directories created with every new instance.
+const GAME_SUBDIRECTORIES: [&str; 3] = ["mods", "config", "saves"];
+
+/// Settings persisted in `instance.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceConfig {
+    /// Minecraft version the instance is based on, such as `1.20.1`.
+    pub minecraft_version: String,
+    /// Installed profile id to launch, such as `fabric-loader-0.15.0-1.20.1`.
+    pub version_id: String,
+    /// Mod loader installed for the instance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loader: Option<InstanceLoader>,
+    /// Java executable to launch with; `java` from `PATH` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub java_executable: Option<PathBuf>,
+    /// Initial heap size in MiB, passed as `-Xms`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_memory_mb: Option<u32>,
+    /// Maximum heap size in MiB, passed as `-Xmx`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_memory_mb: Option<u32>,
+    /// Extra JVM arguments, passed after the memory flags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jvm_args: Vec<String>,
+    /// Window size.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<Resolution>,
+    /// Icon file inside the instance directory, or an icon key the launcher
+    /// understands.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+}
+
+impl InstanceConfig {
+    /// Creates settings for a vanilla instance of `minecraft_version`.
+    pub fn vanilla(minecraft_version: impl Into<String>) -> Self {
+        let minecraft_version = minecraft_version.into();
+        Self {
+            version_id: minecraft_version.clone(),
+            minecraft_version,
+            ..Default::default()
+        }
+    }
+
+    /// Creates settings for `minecraft_version` with an optional loader.
+    ///
+    /// The version id is the profile id the installer writes for that
+    /// combination, such as `fabric-loader-0.15.0-1.20.1` or
+    /// `1.20.1-forge-47.2.0`.
+    pub fn with_loader(
+        minecraft_version: impl Into<String>,
+        loader: Option<InstanceLoader>,
+    ) -> Self {
+        let minecraft_version = minecraft_version.into();
+        let Some(loader) = loader else {
+            return Self::vanilla(minecraft_version);
+        };
+        let version = &loader.version;
+        let version_id = match loader.kind {
+            LoaderKind::Fabric => fabric_installed_version_id(&minecraft_version, version),
+            LoaderKind::Quilt => quilt_installed_version_id(&minecraft_version, version),
+            LoaderKind::Forge => forge_installed_version_id(
+                &InstanceLoader::forge(&minecraft_version, version).version,
+            )
+            .expect("version has a separator"),
+            LoaderKind::NeoForge => neoforge_installed_version_id(&minecraft_version, version),
+        };
+        Self {
+            minecraft_version,
+            version_id,
+            loader: Some(loader),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the install request that produces this instance's profile.
+    ///
+    /// The loader version is pinned with [`LoaderVersion::Exact`].
+    pub fn install_request(&self) -> InstallRequest {
+        InstallRequest {
+            minecraft_version: self.minecraft_version.clone(),
+            loader: self.loader.as_ref().map(InstanceLoader::spec),
+            java: JavaInstallPolicy::Auto,
+        }
+    }
+}
+
+/// Mod loader recorded for an instance.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct InstanceLoader {
+    /// Loader family.
+    pub kind: LoaderKind,
+    /// Exact loader version as accepted by [`LoaderVersion::Exact`], such as
+    /// `0.15.0`, or `1.20.1-47.2.0` for Forge.
+    pub version: String,
+}
+
+impl InstanceLoader {
+    /// Creates a loader record, storing Forge versions in the
+    /// `<minecraft>-<forge>` form via [`InstanceLoader::forge`].
+    pub fn new(kind: LoaderKind, minecraft_version: &str, version: &str) -> Self {
+        match kind {
+            LoaderKind::Forge => Self::forge(minecraft_version, version),
+            _ => Self {
+                kind,
+                version: version.to_string(),
+            },
+        }
+    }
+
+    /// Creates a Forge record from a version given either as `47.2.0` or as
+    /// `1.20.1-47.2.0`, storing the `<minecraft>-<forge>` form Forge
+    /// installers are published under.
+    pub fn forge(minecraft_version: &str, version: &str) -> Self {
+        let prefix = format!("{minecraft_version}-");
+        let version = if version.starts_with(&prefix) {
+            version.to_string()
+        } else {
+            format!("{prefix}{version}")
+        };
+        Self {
+            kind: LoaderKind::Forge,
+            version,
+        }
+    }
+
+    /// Returns the version as modpack formats name it, which is the stored
+    /// version without the `<minecraft>-` prefix for Forge.
+    pub fn pack_version(&self, minecraft_version: &str) -> &str {
+        match self.kind {
+            LoaderKind::Forge => self
+                .version
+                .strip_prefix(&format!("{minecraft_version}-"))
+                .unwrap_or(&self.version),
+            _ => &self.version,
+        }
+    }
+
+    /// Returns the install spec selecting exactly this loader version.
+    pub fn spec(&self) -> LoaderSpec {
+        let version = LoaderVersion::Exact(self.version.clone());
+        match self.kind {
+            LoaderKind::Fabric => LoaderSpec::Fabric { version },
+            LoaderKind::Quilt => LoaderSpec::Quilt { version },
+            LoaderKind::Forge => LoaderSpec::Forge { version },
+            LoaderKind::NeoForge => LoaderSpec::NeoForge { version },
+        }
+    }
+}
+
+/// Game window size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Resolution {
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+}
+
+/// An instance on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instance {
+    /// Instance name, which is also its directory name.
+    pub name: String,
+    /// Instance directory containing `instance.json`.
+    pub path: PathBuf,
+    /// Persisted settings.
+    pub config: InstanceConfig,
+}
+
+impl Instance {
+    /// Returns the game directory passed to Minecraft.
+    pub fn game_directory(&self) -> PathBuf {
+        self.path.join(GAME_DIRECTORY)
+    }
+
+    /// Returns a manager for the mods, resource packs, and shader packs in the
+    /// game directory.
+    pub fn content(&self) -> ContentManager {
+        ContentManager::new(self.game_directory())
+    }
+
+    /// Builds launch options from the stored settings.
+    ///
+    /// Fields the instance does not store keep their
+    /// [`LaunchOptions::default`] values.
+    pub fn launch_options(&self, account: Account) -> LaunchOptions {
+        let config = &self.config;
+        let mut jvm_args = Vec::new();
+        if let Some(min) = config.min_memory_mb {
+            jvm_args.push(format!("-Xms{min}M"));
+        }
+        if let Some(max) = config.max_memory_mb {
+            jvm_args.push(format!("-Xmx{max}M"));
+        }
+        jvm_args.extend(config.jvm_args.iter().cloned());
+        LaunchOptions {
+            account,
+            java_executable: config.java_executable.clone(),
+            game_directory: Some(self.game_directory()),
+            jvm_args,
+            custom_resolution: config
+                .resolution
+                .map(|resolution| (resolution.width, resolution.height)),
+            ..Default::default()
+        }
+    }
+
+    /// Writes the current settings to `instance.json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the file cannot be written.
+    pub fn save(&self) -> Result<()> {
+        fs::write(
+            self.path.join(INSTANCE_FILE),
+            serde_json::to_vec_pretty(&self.config)?,
+        )?;
+        Ok(())
+    }
+}
+
+/// Returns the directory holding every instance.
+pub fn instances_directory(minecraft_dir: impl AsRef<Path>) -> PathBuf {
+    minecraft_dir.as_ref().join("instances")
+}
+
+/// Creates a new instance with an empty game directory.
+///
+/// # Errors
+///
+/// Returns [`LauncherError::InvalidInstanceName`] if `name` cannot be a
+/// directory name, [`LauncherError::InstanceExists`] if the name is taken, or
+/// another [`crate::LauncherError`] if the directories or settings cannot be
+/// written.
+pub fn create_instance(
+    minecraft_dir: impl AsRef<Path>,
+    name: &str,
+    config: InstanceConfig,
+) -> Result<Instance> {
+    let path = vacant_instance_path(minecraft_dir.as_ref(), name)?;
+    let instance = Instance {
+        name: name.to_string(),
+        path,
+        config,
+    };
+    for subdirectory in GAME_SUBDIRECTORIES {
+        fs::create_dir_all(instance.game_directory().join(subdirectory))?;
+    }
+    instance.save()?;
+    Ok(instance)
+}
+
+/// Loads one instance by name.
+///
+/// # Errors
+///
+/// Returns [`LauncherError::InstanceNotFound`] if the instance has no
+/// `instance.json`, or another [`crate::LauncherError`] if it cannot be read
+/// or decoded.
+pub fn load_instance(minecraft_dir: impl AsRef<Path>, name: &str) -> Result<Instance> {
+    let path = instance_path(minecraft_dir.as_ref(), name)?;
+    let bytes = match fs::read(path.join(INSTANCE_FILE)) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            return Err(LauncherError::InstanceNotFound {
+                name: name.to_string(),
+            });
+        }
+        Err(err) => return Err(err.into()),
+    };
+    Ok(Instance {
+        name: name.to_string(),
+        path,
+        config: serde_json::from_slice(&bytes)?,
+    })
+}
+
+/// Lists instances sorted by name.
+///
+/// Directories without `instance.json` are ignored.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the instances directory cannot be read
+/// or an `instance.json` cannot be decoded.
+pub fn list_instances(minecraft_dir: impl AsRef<Path>) -> Result<Vec<Instance>> {
+    let minecraft_dir = minecraft_dir.as_ref();
+    let entries = match fs::read_dir(instances_directory(minecraft_dir)) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let mut instances = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let Ok(name) = entry.file_name().into_string() else {
+            continue;
+        };
+        if validate_name(&name).is_err() || !entry.path().join(INSTANCE_FILE).is_file() {
+            continue;
+        }
+        instances.push(load_instance(minecraft_dir, &name)?);
+    }
+    instances.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(instances)
+}
+
+/// Renames an instance and its directory.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if either name is invalid, the source does
+/// not exist, the new name is taken, or the directory cannot be renamed.
+pub fn rename_instance(
+    minecraft_dir: impl AsRef<Path>,
+    name: &str,
+    new_name: &str,
+) -> Result<Instance> {
+    let minecraft_dir = minecraft_dir.as_ref();
+    let instance = load_instance(minecraft_dir, name)?;
+    let path = vacant_instance_path(minecraft_dir, new_name)?;
+    fs::rename(&instance.path, &path)?;
+    Ok(Instance {
+        name: new_name.to_string(),
+        path,
+        config: instance.config,
+    })
+}
+
+/// Copies an instance, including its game directory, under a new name.
+///
+/// A partially written copy is removed if copying fails.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if either name is invalid, the source does
+/// not exist, the new name is taken, or a file cannot be copied.
+pub fn clone_instance(
+    minecraft_dir: impl AsRef<Path>,
+    name: &str,
+    new_name: &str,
+) -> Result<Instance> {
+    let minecraft_dir = minecraft_dir.as_ref();
+    let instance = load_instance(minecraft_dir, name)?;
+    let path = vacant_instance_path(minecraft_dir, new_name)?;
+    if let Err(err) = copy_dir(&instance.path, &path) {
+        let _ = fs::remove_dir_all(&path);
+        return Err(err);
+    }
+    Ok(Instance {
+        name: new_name.to_string(),
+        path,
+        config: instance.config,
+    })
+}
+
+/// Deletes an instance directory, including its saves.
+///
+/// # Errors
+///
+/// Returns [`LauncherError::InstanceNotFound`] if the instance does not exist,
+/// or another [`crate::LauncherError`] if it cannot be removed.
+pub fn delete_instance(minecraft_dir: impl AsRef<Path>, name: &str) -> Result<()> {
+    let instance = load_instance(minecraft_dir, name)?;
+    fs::remove_dir_all(instance.path)?;
+    Ok(())
+}
+
+fn instance_path(minecraft_dir: &Path, name: &str) -> Result<PathBuf> {
+    validate_name(name)?;
+    Ok(instances_directory(minecraft_dir).join(name))
+}
+
+fn vacant_instance_path(minecraft_dir: &Path, name: &str) -> Result<PathBuf> {
+    let path = instance_path(minecraft_dir, name)?;
+    if path.exists() {
+        return Err(LauncherError::InstanceExists {
+            name: name.to_string(),
+        });
+    }
+    fs::create_dir_all(instances_directory(minecraft_dir))?;
+    Ok(path)
+}
+
+/// Accepts names that are a single portable directory name.
+///
+/// Characters Windows rejects in file names are refused everywhere so an
+/// instances directory can move between systems, and names starting with a
+/// dot are reserved for hidden and temporary entries.
+fn validate_name(name: &str) -> Result<()> {
+    let single_component = matches!(
+        Path::new(name).components().collect::<Vec<_>>().as_slice(),
+        [Component::Normal(_)]
+    );
+    let portable = !name.starts_with('.')
+        && name.trim() == name
+        && !name.ends_with('.')
+        && !name
+            .chars()
+            .any(|c| c.is_control() || r#"<>:"/\|?*"#.contains(c));
+    if single_component && portable {
+        Ok(())
+    } else {
+        Err(LauncherError::InvalidInstanceName {
+            name: name.to_string(),
+        })
+    }
+}
+
+/// Copies `source` into `destination`, merging with existing directories and
+/// overwriting existing files.
+pub(crate) fn copy_dir(source: &Path, destination: &Path) -> Result<()> {
+    fs::create_dir_all(destination)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let target = destination.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
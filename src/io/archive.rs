@@ -17,19 +17,51 @@ use crate::{io::paths::safe_join, Result};
 /// Returns [`crate::LauncherError`] if the archive cannot be read, a file cannot
 /// be written, or an entry path is unsafe.
 pub fn extract_zip_safely(zip_path: impl AsRef<Path>, destination: impl AsRef<Path>) -> Result<()> {
+    extract_zip_directory_safely(zip_path, "", destination)
+}
+
+/// Extracts the entries below `directory` inside a ZIP archive, relative to
+/// that directory, with the same path checks as [`extract_zip_safely`].
+///
+/// An empty `directory` extracts the whole archive. Nothing is written when the
+/// archive has no entries below `directory`.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the archive cannot be read, a file cannot
+/// be written, or an entry path is unsafe.
+pub fn extract_zip_directory_safely(
+    zip_path: impl AsRef<Path>,
+    directory: &str,
+    destination: impl AsRef<Path>,
+) -> Result<()> {
     let file = File::open(zip_path)?;
     let mut archive = ZipArchive::new(file)?;
     let destination = destination.as_ref();
-    fs::create_dir_all(destination)?;
+    let prefix = directory.trim_matches('/');
+    if prefix.is_empty() {
+        fs::create_dir_all(destination)?;
+    }
 
     for index in 0..archive.len() {
         let mut entry = archive.by_index(index)?;
-        let Some(enclosed) = entry.enclosed_name().map(|path| path.to_path_buf()) else {
-            let unsafe_name = entry.name().to_string();
-            safe_join(destination, unsafe_name)?;
-            continue;
+        let name = entry.name().to_string();
+        let relative = if prefix.is_empty() {
+            name.as_str()
+        } else {
+            match name
+                .strip_prefix(prefix)
+                .and_then(|rest| rest.strip_prefix('/'))
+            {
+                Some(rest) if !rest.is_empty() => rest,
+                _ => continue,
+            }
         };
-        let output = safe_join(destination, enclosed)?;
+        if entry.enclosed_name().is_none() {
+            safe_join(destination, &name)?;
+            continue;
+        }
+        let output = safe_join(destination, relative)?;
 
         if entry.is_dir() {
             fs::create_dir_all(&output)?;
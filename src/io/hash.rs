@@ -7,6 +7,7 @@ use std::{
 };
 
 use sha1::{Digest, Sha1};
+use sha2::{Sha256, Sha512};
 
 use crate::Result;
 
@@ -16,9 +17,31 @@ use crate::Result;
 ///
 /// Returns [`crate::LauncherError`] if the file cannot be read.
 pub fn sha1_file(path: impl AsRef<Path>) -> Result<String> {
+    digest_file::<Sha1>(path.as_ref())
+}
+
+/// Calculates the SHA-256 digest of a file as lowercase hexadecimal.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the file cannot be read.
+pub fn sha256_file(path: impl AsRef<Path>) -> Result<String> {
+    digest_file::<Sha256>(path.as_ref())
+}
+
+/// Calculates the SHA-512 digest of a file as lowercase hexadecimal.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the file cannot be read.
+pub fn sha512_file(path: impl AsRef<Path>) -> Result<String> {
+    digest_file::<Sha512>(path.as_ref())
+}
+
+fn digest_file<D: Digest>(path: &Path) -> Result<String> {
     let file = File::open(path)?;
     let mut reader = BufReader::new(file);
-    let mut hasher = Sha1::new();
+    let mut hasher = D::new();
     let mut buffer = [0_u8; 64 * 1024];
 
     loop {
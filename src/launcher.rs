@@ -1,38 +1,81 @@
 //! High-level launcher facade.
 //!
 //! [`Launcher`] owns a Minecraft directory and coordinates the common workflow:
-//! install a profile, load its merged version metadata, and build a Java
-//! command from that metadata.
+//! install a profile, load its merged version metadata, build a Java command
+//! from that metadata, and launch it as a supervised [`GameProcess`].
 
-use std::path::{Path, PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use crate::{
+    account::Account,
     command::builder::{build_launch_command, LaunchCommand, LaunchOptions},
+    compatibility::CompatibilityPolicy,
     core::version::VersionJson,
     install::{
         client::{
-            fetch_vanilla_version, install_version_files, load_version_json, write_version_json,
+            fetch_vanilla_version_with_mirrors, install_version_files_with_options,
+            load_version_json, write_version_json,
         },
-        loader::{run_loader_installer, write_loader_profile, InstallerInvocation},
-        request::{InstallRequest, InstallResult},
+        forge::install_from_installer,
+        gc::{collect_garbage, plan_garbage_collection, GcReport},
+        loader::{write_loader_profile, InstallerInvocation},
+        request::{InstallRequest, InstallResult, JavaInstallPolicy},
+        runtime::install_runtime_with_options,
+        uninstall::{uninstall_version, UninstallOptions, UninstallResult},
+        verify::{repair_version_files, verify_version_files, InstallVerification},
+    },
+    instance::{
+        clone_instance, content::ContentManager, copy_dir, create_instance, delete_instance,
+        list_instances, load_instance, rename_instance, Instance, InstanceConfig,
     },
     loader::{
         common::{LoaderSpec, LoaderVersion},
         LoaderKind,
     },
-    net::download::{execute_plan, DownloadPlan, DownloadTask},
-    progress::{ProgressEvent, ProgressReporter},
+    modpack::curseforge::{
+        curseforge_loader, extract_curseforge_overrides, plan_curseforge_downloads,
+        read_curseforge_manifest, CurseForgeResolver,
+    },
+    modpack::mrpack::{
+        export_mrpack, extract_mrpack_overrides, mrpack_alternative_urls, mrpack_install_request,
+        mrpack_loader, plan_mrpack_downloads, read_mrpack_index, MrpackDownloadResolver,
+        MrpackExportOptions,
+    },
+    modpack::multimc::{read_multimc_instance, MultiMcImport},
+    mods::{
+        dependencies::{check_mods, ModDiagnostic, ModEnvironment},
+        metadata::{scan_mods_directory, ModFile},
+        updates::{
+            apply_mod_update, check_mod_updates, AppliedModUpdate, ModUpdate, ModUpdateProvider,
+        },
+    },
+    net::{
+        download::{execute_plan_with_options, DownloadOptions, DownloadPlan, DownloadTask},
+        mirror::MirrorConfig,
+    },
+    platform::Platform,
+    process::GameProcess,
+    progress::{InstallStage, ProgressEvent, ProgressReporter},
+    types::{
+        mrpack_types::MrpackIndex, CurseForgeInstallOptions, MrpackInstallOptions,
+        MultiMcInstallOptions,
+    },
     LauncherError, Result,
 };
 
 /// Facade for installing and launching Minecraft profiles inside one directory.
 ///
-/// A `Launcher` is cheap to clone and only stores the root Minecraft directory.
-/// The directory is expected to follow the standard launcher layout with
-/// `versions`, `libraries`, `assets`, and `runtime` children as needed.
+/// A `Launcher` is cheap to clone and only stores the root Minecraft directory
+/// plus download and mirror settings. The directory is expected to follow the
+/// standard launcher layout with `versions`, `libraries`, `assets`, and
+/// `runtime` children as needed.
 #[derive(Debug, Clone)]
 pub struct Launcher {
     minecraft_dir: PathBuf,
+    download_options: DownloadOptions,
 }
 
 impl Launcher {
@@ -42,14 +85,60 @@ impl Launcher {
     pub fn new(minecraft_dir: impl Into<PathBuf>) -> Self {
         Self {
             minecraft_dir: minecraft_dir.into(),
+            download_options: DownloadOptions::default(),
         }
     }
 
+    /// Replaces the options used when executing install downloads.
+    ///
+    /// ```
+    /// use mc_launcher_core::{net::download::DownloadOptions, prelude::*};
+    ///
+    /// let launcher = Launcher::new(".minecraft").with_download_options(DownloadOptions {
+    ///     workers: 16,
+    ///     ..Default::default()
+    /// });
+    /// assert_eq!(launcher.download_options().workers, 16);
+    /// ```
+    pub fn with_download_options(mut self, options: DownloadOptions) -> Self {
+        self.download_options = options;
+        self
+    }
+
+    /// Replaces the mirrors used for metadata requests and install downloads.
+    ///
+    /// This is shorthand for setting [`DownloadOptions::mirrors`].
+    ///
+    /// ```
+    /// use mc_launcher_core::{
+    ///     net::mirror::{Mirror, MirrorConfig},
+    ///     prelude::*,
+    /// };
+    ///
+    /// let launcher =
+    ///     Launcher::new(".minecraft").with_mirrors(MirrorConfig::with_fallback(vec![Mirror::bmclapi()]));
+    /// assert_eq!(launcher.mirrors().mirrors[0].name, "BMCLAPI");
+    /// ```
+    pub fn with_mirrors(mut self, mirrors: MirrorConfig) -> Self {
+        self.download_options.mirrors = mirrors;
+        self
+    }
+
     /// Returns the Minecraft directory managed by this launcher.
     pub fn minecraft_dir(&self) -> &Path {
         &self.minecraft_dir
     }
 
+    /// Returns the options used when executing install downloads.
+    pub fn download_options(&self) -> &DownloadOptions {
+        &self.download_options
+    }
+
+    /// Returns the mirrors used for metadata requests and install downloads.
+    pub fn mirrors(&self) -> &MirrorConfig {
+        &self.download_options.mirrors
+    }
+
     /// Installs a vanilla or loader-backed Minecraft profile.
     ///
     /// This is a convenience wrapper around [`Launcher::install_with_progress`]
@@ -67,8 +156,11 @@ impl Launcher {
     /// Installs a profile and reports progress as tasks are processed.
     ///
     /// Vanilla, Fabric, and Quilt installs are handled with Rust-native
-    /// metadata planning. Forge and NeoForge currently download the installer
-    /// jar and invoke it with `java`.
+    /// metadata planning. Forge and NeoForge installer jars are read with
+    /// [`crate::install::forge`], which downloads their libraries and runs each
+    /// client processor with Java. With [`JavaInstallPolicy::Auto`] the Mojang
+    /// runtime required by the Minecraft version is installed first and used for
+    /// the processors; otherwise `java` from `PATH` is used.
     ///
     /// # Errors
     ///
@@ -79,86 +171,99 @@ impl Launcher {
         request: InstallRequest,
         reporter: &mut dyn ProgressReporter,
     ) -> Result<InstallResult> {
-        if let Some(loader) = request.loader.clone() {
-            match loader {
-                LoaderSpec::Fabric { version } => {
-                    self.install_vanilla_version(&request.minecraft_version, reporter)?;
-                    let loader_version = resolve_fabric_loader_version(version)?;
-                    let profile = crate::loader::fabric::fetch_profile(
-                        &request.minecraft_version,
-                        &loader_version,
-                    )?;
-                    let version_id = version_id(&profile, "loader profile")?.to_string();
-                    write_loader_profile(&self.minecraft_dir, &profile)?;
-                    let merged = self.load_version(&version_id)?;
-                    install_version_files(&merged, &self.minecraft_dir, reporter)?;
-                    return Ok(InstallResult { version_id });
-                }
-                LoaderSpec::Quilt { version } => {
-                    self.install_vanilla_version(&request.minecraft_version, reporter)?;
-                    let loader_version = resolve_quilt_loader_version(version)?;
-                    let profile = crate::loader::quilt::fetch_profile(
-                        &request.minecraft_version,
-                        &loader_version,
-                    )?;
-                    let version_id = version_id(&profile, "loader profile")?.to_string();
-                    write_loader_profile(&self.minecraft_dir, &profile)?;
-                    let merged = self.load_version(&version_id)?;
-                    install_version_files(&merged, &self.minecraft_dir, reporter)?;
-                    return Ok(InstallResult { version_id });
-                }
-                LoaderSpec::Forge { version } => {
-                    self.install_vanilla_version(&request.minecraft_version, reporter)?;
-                    let loader_version =
-                        resolve_forge_loader_version(&request.minecraft_version, version)?;
-                    let installer_path = download_installer(
-                        &self.minecraft_dir,
-                        "forge",
-                        &loader_version,
-                        &crate::loader::forge::installer_url(&loader_version),
-                    )?;
-                    run_loader_installer(&InstallerInvocation {
+        let vanilla = self.install_vanilla_version(&request.minecraft_version, reporter)?;
+        let java_executable = self.install_java(request.java, &vanilla, reporter)?;
+        let installer_java = java_executable
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("java"));
+
+        let version_id = match request.loader {
+            None => request.minecraft_version,
+            Some(LoaderSpec::Fabric { version }) => {
+                let loader_version = resolve_fabric_loader_version(version, self.mirrors())?;
+                let profile = crate::loader::fabric::fetch_profile_with_mirrors(
+                    &request.minecraft_version,
+                    &loader_version,
+                    self.mirrors(),
+                )?;
+                let version_id = version_id(&profile, "loader profile")?.to_string();
+                write_loader_profile(&self.minecraft_dir, &profile)?;
+                let merged = self.load_version(&version_id)?;
+                self.install_files(&merged, reporter)?;
+                version_id
+            }
+            Some(LoaderSpec::Quilt { version }) => {
+                let loader_version = resolve_quilt_loader_version(version, self.mirrors())?;
+                let profile = crate::loader::quilt::fetch_profile_with_mirrors(
+                    &request.minecraft_version,
+                    &loader_version,
+                    self.mirrors(),
+                )?;
+                let version_id = version_id(&profile, "loader profile")?.to_string();
+                write_loader_profile(&self.minecraft_dir, &profile)?;
+                let merged = self.load_version(&version_id)?;
+                self.install_files(&merged, reporter)?;
+                version_id
+            }
+            Some(LoaderSpec::Forge { version }) => {
+                let loader_version = resolve_forge_loader_version(
+                    &request.minecraft_version,
+                    version,
+                    self.mirrors(),
+                )?;
+                let installer_path = download_installer(
+                    &self.minecraft_dir,
+                    &self.download_options,
+                    "forge",
+                    &loader_version,
+                    &crate::loader::forge::installer_url(&loader_version),
+                )?;
+                let version_id = install_from_installer(
+                    &InstallerInvocation {
                         loader: LoaderKind::Forge,
-                        java_executable: PathBuf::from("java"),
+                        java_executable: installer_java,
                         installer_path,
                         minecraft_dir: self.minecraft_dir.clone(),
-                    })?;
-                    let version_id =
-                        crate::loader::forge::forge_installed_version_id(&loader_version)?;
-                    let merged = self.load_version(&version_id)?;
-                    install_version_files(&merged, &self.minecraft_dir, reporter)?;
-                    return Ok(InstallResult { version_id });
-                }
-                LoaderSpec::NeoForge { version } => {
-                    self.install_vanilla_version(&request.minecraft_version, reporter)?;
-                    let loader_version =
-                        resolve_neoforge_loader_version(&request.minecraft_version, version)?;
-                    let installer_path = download_installer(
-                        &self.minecraft_dir,
-                        "neoforge",
-                        &loader_version,
-                        &crate::loader::neoforge::installer_url(&loader_version),
-                    )?;
-                    run_loader_installer(&InstallerInvocation {
+                    },
+                    &self.download_options,
+                    reporter,
+                )?;
+                let merged = self.load_version(&version_id)?;
+                self.install_files(&merged, reporter)?;
+                version_id
+            }
+            Some(LoaderSpec::NeoForge { version }) => {
+                let loader_version = resolve_neoforge_loader_version(
+                    &request.minecraft_version,
+                    version,
+                    self.mirrors(),
+                )?;
+                let installer_path = download_installer(
+                    &self.minecraft_dir,
+                    &self.download_options,
+                    "neoforge",
+                    &loader_version,
+                    &crate::loader::neoforge::installer_url(&loader_version),
+                )?;
+                let version_id = install_from_installer(
+                    &InstallerInvocation {
                         loader: LoaderKind::NeoForge,
-                        java_executable: PathBuf::from("java"),
+                        java_executable: installer_java,
                         installer_path,
                         minecraft_dir: self.minecraft_dir.clone(),
-                    })?;
-                    let version_id = crate::loader::neoforge::neoforge_installed_version_id(
-                        &request.minecraft_version,
-                        &loader_version,
-                    );
-                    let merged = self.load_version(&version_id)?;
-                    install_version_files(&merged, &self.minecraft_dir, reporter)?;
-                    return Ok(InstallResult { version_id });
-                }
+                    },
+                    &self.download_options,
+                    reporter,
+                )?;
+                let merged = self.load_version(&version_id)?;
+                self.install_files(&merged, reporter)?;
+                version_id
             }
-        }
+        };
 
-        self.install_vanilla_version(&request.minecraft_version, reporter)?;
         Ok(InstallResult {
-            version_id: request.minecraft_version,
+            version_id,
+            java_executable,
         })
     }
 
@@ -179,6 +284,432 @@ impl Launcher {
         build_launch_command(version, self.minecraft_dir.clone(), options)
     }
 
+    /// Builds a launch command for `version` and spawns the game.
+    ///
+    /// The returned [`GameProcess`] streams output lines and exposes the pid,
+    /// exit status, and play duration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the command cannot be built or the Java
+    /// process cannot be started.
+    pub fn launch(&self, version: &VersionJson, options: LaunchOptions) -> Result<GameProcess> {
+        GameProcess::spawn(&self.build_launch_command_from_version(version, options)?)
+    }
+
+    /// Creates an instance under `<minecraft_dir>/instances`.
+    ///
+    /// The instance's version is not installed; pass
+    /// [`InstanceConfig::install_request`] to [`Launcher::install`] for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the name is invalid or taken, or the
+    /// instance cannot be written.
+    pub fn create_instance(&self, name: &str, config: InstanceConfig) -> Result<Instance> {
+        create_instance(&self.minecraft_dir, name, config)
+    }
+
+    /// Lists instances sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if an instance cannot be read.
+    pub fn instances(&self) -> Result<Vec<Instance>> {
+        list_instances(&self.minecraft_dir)
+    }
+
+    /// Loads one instance by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError::InstanceNotFound`] if it does not exist, or
+    /// another error if it cannot be read.
+    pub fn instance(&self, name: &str) -> Result<Instance> {
+        load_instance(&self.minecraft_dir, name)
+    }
+
+    /// Renames an instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the instance does not exist, the new name
+    /// is invalid or taken, or the directory cannot be renamed.
+    pub fn rename_instance(&self, name: &str, new_name: &str) -> Result<Instance> {
+        rename_instance(&self.minecraft_dir, name, new_name)
+    }
+
+    /// Copies an instance under a new name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the instance does not exist, the new name
+    /// is invalid or taken, or a file cannot be copied.
+    pub fn clone_instance(&self, name: &str, new_name: &str) -> Result<Instance> {
+        clone_instance(&self.minecraft_dir, name, new_name)
+    }
+
+    /// Deletes an instance and its game directory.
+    ///
+    /// Shared version profiles, libraries, and assets are kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the instance does not exist or cannot be
+    /// removed.
+    pub fn delete_instance(&self, name: &str) -> Result<()> {
+        delete_instance(&self.minecraft_dir, name)
+    }
+
+    /// Launches an instance with the options stored in its `instance.json`.
+    ///
+    /// The instance's profile must already be installed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the instance or its profile cannot be
+    /// loaded, or the game cannot be started.
+    pub fn launch_instance(&self, name: &str, account: Account) -> Result<GameProcess> {
+        let instance = self.instance(name)?;
+        let version = self.load_version(&instance.config.version_id)?;
+        self.launch(&version, instance.launch_options(account))
+    }
+
+    /// Lists the files in an instance's `mods` directory with the metadata of
+    /// the mods they declare.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the instance cannot be loaded or its mods
+    /// directory cannot be listed.
+    pub fn instance_mods(&self, name: &str) -> Result<Vec<ModFile>> {
+        scan_mods_directory(self.instance(name)?.game_directory().join("mods"))
+    }
+
+    /// Checks an instance's mods against each other and against the
+    /// Minecraft and loader versions of its profile.
+    ///
+    /// See [`check_mods`] for what is reported. An empty list means no
+    /// problems were found.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the instance or its profile cannot be
+    /// loaded, or its mods directory cannot be listed.
+    pub fn check_instance_mods(&self, name: &str) -> Result<Vec<ModDiagnostic>> {
+        let instance = self.instance(name)?;
+        let environment =
+            ModEnvironment::from_version(&self.load_version(&instance.config.version_id)?)?;
+        let files = scan_mods_directory(instance.game_directory().join("mods"))?;
+        Ok(check_mods(&files, &environment))
+    }
+
+    /// Lists the mods of an instance for which `provider` knows a newer
+    /// release compatible with the instance's Minecraft version and loader.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the instance or its profile cannot be
+    /// loaded, a mod cannot be hashed, or the provider fails.
+    pub fn check_instance_mod_updates(
+        &self,
+        name: &str,
+        provider: &mut dyn ModUpdateProvider,
+    ) -> Result<Vec<ModUpdate>> {
+        let instance = self.instance(name)?;
+        let environment =
+            ModEnvironment::from_version(&self.load_version(&instance.config.version_id)?)?;
+        check_mod_updates(
+            instance.game_directory().join("mods"),
+            &environment,
+            provider,
+        )
+    }
+
+    /// Applies an update found by [`Launcher::check_instance_mod_updates`].
+    ///
+    /// This is a convenience wrapper around
+    /// [`Launcher::update_instance_mod_with_progress`] that ignores progress
+    /// events.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the update does not belong to the
+    /// instance, or the download or file swap fails.
+    pub fn update_instance_mod(&self, name: &str, update: &ModUpdate) -> Result<AppliedModUpdate> {
+        let mut reporter = |_event: ProgressEvent| {};
+        self.update_instance_mod_with_progress(name, update, &mut reporter)
+    }
+
+    /// Applies an update found by [`Launcher::check_instance_mod_updates`]
+    /// with the launcher's download options, keeping the old file for
+    /// [`crate::mods::updates::rollback_mod_update`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the update does not belong to the
+    /// instance, or the download or file swap fails.
+    pub fn update_instance_mod_with_progress(
+        &self,
+        name: &str,
+        update: &ModUpdate,
+        reporter: &mut dyn ProgressReporter,
+    ) -> Result<AppliedModUpdate> {
+        let mods = self.instance(name)?.game_directory().join("mods");
+        if update.path.parent() != Some(mods.as_path()) {
+            return Err(LauncherError::UnsafePath {
+                base: mods,
+                path: update.path.clone(),
+            });
+        }
+        apply_mod_update(update, &self.download_options, reporter)
+    }
+
+    /// Returns a manager for an instance's mods, resource packs, and shader
+    /// packs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the instance cannot be loaded.
+    pub fn instance_content(&self, name: &str) -> Result<ContentManager> {
+        Ok(self.instance(name)?.content())
+    }
+
+    /// Imports a Modrinth `.mrpack` into a new instance.
+    ///
+    /// This is a convenience wrapper around
+    /// [`Launcher::import_mrpack_with_progress`] that ignores progress events.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the pack is invalid, the instance name is
+    /// invalid or taken, or an install, download, or extraction step fails.
+    pub fn import_mrpack(
+        &self,
+        path: impl AsRef<Path>,
+        instance_name: &str,
+        options: &MrpackInstallOptions,
+    ) -> Result<Instance> {
+        let mut reporter = |_event: ProgressEvent| {};
+        self.import_mrpack_with_progress(path, instance_name, options, &mut reporter)
+    }
+
+    /// Imports a Modrinth `.mrpack` into a new instance and reports progress.
+    ///
+    /// The Minecraft version and loader named by the pack's `dependencies` are
+    /// installed unless [`MrpackInstallOptions::skip_dependencies_install`] is
+    /// set. Client files are downloaded into the instance's game directory
+    /// with checksum verification, then `overrides/` and `client-overrides/`
+    /// are extracted over them. The instance is removed again if any step
+    /// fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the pack is invalid, the instance name is
+    /// invalid or taken, or an install, download, or extraction step fails.
+    pub fn import_mrpack_with_progress(
+        &self,
+        path: impl AsRef<Path>,
+        instance_name: &str,
+        options: &MrpackInstallOptions,
+        reporter: &mut dyn ProgressReporter,
+    ) -> Result<Instance> {
+        let path = path.as_ref();
+        let index = read_mrpack_index(path)?;
+        self.import_modpack(
+            instance_name,
+            InstanceConfig::with_loader(
+                mrpack_install_request(&index)?.minecraft_version,
+                mrpack_loader(&index)?,
+            ),
+            options.skip_dependencies_install.unwrap_or(false),
+            reporter,
+            |game_dir, reporter| {
+                let plan = plan_mrpack_downloads(&index, game_dir, options)?;
+                let mut download_options = self.download_options.clone();
+                download_options
+                    .alternatives
+                    .extend(mrpack_alternative_urls(&index));
+                execute_plan_with_options(&plan, &download_options, reporter)?;
+                extract_mrpack_overrides(path, game_dir)
+            },
+        )
+    }
+
+    /// Imports a CurseForge modpack zip into a new instance.
+    ///
+    /// This is a convenience wrapper around
+    /// [`Launcher::import_curseforge_with_progress`] that ignores progress
+    /// events.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the manifest is invalid, the instance name
+    /// is invalid or taken, a file cannot be resolved, or an install, download,
+    /// or extraction step fails.
+    pub fn import_curseforge(
+        &self,
+        path: impl AsRef<Path>,
+        instance_name: &str,
+        resolver: &mut dyn CurseForgeResolver,
+        options: &CurseForgeInstallOptions,
+    ) -> Result<Instance> {
+        let mut reporter = |_event: ProgressEvent| {};
+        self.import_curseforge_with_progress(path, instance_name, resolver, options, &mut reporter)
+    }
+
+    /// Imports a CurseForge modpack zip into a new instance and reports
+    /// progress.
+    ///
+    /// The Minecraft version and primary loader from `manifest.json` are
+    /// installed unless [`CurseForgeInstallOptions::skip_dependencies_install`]
+    /// is set. Each required project file is resolved through `resolver` and
+    /// downloaded into the instance's game directory, then the overrides
+    /// directory is extracted over them. The instance is removed again if any
+    /// step fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the manifest is invalid, the instance name
+    /// is invalid or taken, a file cannot be resolved, or an install, download,
+    /// or extraction step fails.
+    pub fn import_curseforge_with_progress(
+        &self,
+        path: impl AsRef<Path>,
+        instance_name: &str,
+        resolver: &mut dyn CurseForgeResolver,
+        options: &CurseForgeInstallOptions,
+        reporter: &mut dyn ProgressReporter,
+    ) -> Result<Instance> {
+        let path = path.as_ref();
+        let manifest = read_curseforge_manifest(path)?;
+        self.import_modpack(
+            instance_name,
+            InstanceConfig::with_loader(
+                manifest.minecraft.version.clone(),
+                curseforge_loader(&manifest)?,
+            ),
+            options.skip_dependencies_install.unwrap_or(false),
+            reporter,
+            |game_dir, reporter| {
+                let plan = plan_curseforge_downloads(&manifest, game_dir, resolver)?;
+                execute_plan_with_options(&plan, &self.download_options, reporter)?;
+                extract_curseforge_overrides(path, &manifest, game_dir)
+            },
+        )
+    }
+
+    /// Imports a MultiMC or Prism Launcher instance directory into a new
+    /// instance.
+    ///
+    /// This is a convenience wrapper around
+    /// [`Launcher::import_multimc_with_progress`] that ignores progress events.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the source instance cannot be read, the
+    /// instance name is invalid or taken, or an install or copy step fails.
+    pub fn import_multimc(
+        &self,
+        path: impl AsRef<Path>,
+        instance_name: &str,
+        options: &MultiMcInstallOptions,
+    ) -> Result<MultiMcImport> {
+        let mut reporter = |_event: ProgressEvent| {};
+        self.import_multimc_with_progress(path, instance_name, options, &mut reporter)
+    }
+
+    /// Imports a MultiMC or Prism Launcher instance directory into a new
+    /// instance and reports progress.
+    ///
+    /// The Minecraft version and loader from `mmc-pack.json` are installed
+    /// unless [`MultiMcInstallOptions::skip_dependencies_install`] is set, the
+    /// overridden settings from `instance.cfg` are kept, and the source game
+    /// directory is copied into the new instance. Components that cannot be
+    /// mapped are returned in [`MultiMcImport::unmapped_components`]. The
+    /// instance is removed again if any step fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the source instance cannot be read, the
+    /// instance name is invalid or taken, or an install or copy step fails.
+    pub fn import_multimc_with_progress(
+        &self,
+        path: impl AsRef<Path>,
+        instance_name: &str,
+        options: &MultiMcInstallOptions,
+        reporter: &mut dyn ProgressReporter,
+    ) -> Result<MultiMcImport> {
+        let source = read_multimc_instance(path)?;
+        let instance = self.import_modpack(
+            instance_name,
+            source.config,
+            options.skip_dependencies_install.unwrap_or(false),
+            reporter,
+            |game_dir, _reporter| match &source.game_directory {
+                Some(source) => copy_dir(source, game_dir),
+                None => Ok(()),
+            },
+        )?;
+        Ok(MultiMcImport {
+            instance,
+            unmapped_components: source.unmapped_components,
+        })
+    }
+
+    /// Exports an instance as a Modrinth `.mrpack` at `destination`.
+    ///
+    /// Mods, resource packs, and shader packs that `resolver` returns download
+    /// URLs for are listed in the index; every other selected file is packed
+    /// into `overrides/`. See [`export_mrpack`] for the selection rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the instance cannot be loaded, the resolver
+    /// fails, or the archive cannot be written.
+    pub fn export_mrpack(
+        &self,
+        instance_name: &str,
+        destination: impl AsRef<Path>,
+        options: &MrpackExportOptions,
+        resolver: &mut dyn MrpackDownloadResolver,
+    ) -> Result<MrpackIndex> {
+        let instance = self.instance(instance_name)?;
+        export_mrpack(&instance, destination, options, resolver)
+    }
+
+    fn import_modpack(
+        &self,
+        instance_name: &str,
+        config: InstanceConfig,
+        skip_dependencies_install: bool,
+        reporter: &mut dyn ProgressReporter,
+        install_files: impl FnOnce(&Path, &mut dyn ProgressReporter) -> Result<()>,
+    ) -> Result<Instance> {
+        let mut instance = self.create_instance(instance_name, config)?;
+
+        let result = (|| {
+            if !skip_dependencies_install {
+                let install =
+                    self.install_with_progress(instance.config.install_request(), reporter)?;
+                instance.config.version_id = install.version_id;
+                if instance.config.java_executable.is_none() {
+                    instance.config.java_executable = install.java_executable;
+                }
+                instance.save()?;
+            }
+            reporter.report(ProgressEvent::StageStarted {
+                stage: InstallStage::ModpackFiles,
+            });
+            install_files(&instance.game_directory(), reporter)
+        })();
+        if let Err(err) = result {
+            let _ = fs::remove_dir_all(&instance.path);
+            return Err(err);
+        }
+        Ok(instance)
+    }
+
     /// Loads and merges a version JSON from `<minecraft_dir>/versions`.
     ///
     /// If the profile declares `inheritsFrom`, parent metadata is loaded and
@@ -192,14 +723,152 @@ impl Launcher {
         load_version_json(&self.minecraft_dir, version_id)
     }
 
+    /// Checks the installed files of a version without downloading anything.
+    ///
+    /// The merged profile is checked: client jar, libraries, asset index and
+    /// objects, logging configuration, and extracted natives.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the profile cannot be loaded or an existing
+    /// file cannot be read.
+    pub fn verify(&self, version_id: &str) -> Result<InstallVerification> {
+        let version = self.load_version(version_id)?;
+        verify_version_files(&version, &self.minecraft_dir, Platform::current())
+    }
+
+    /// Re-downloads missing or corrupt files of an installed version.
+    ///
+    /// Returns the verification report found before the repair.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if verification or a download fails.
+    pub fn repair(&self, version_id: &str) -> Result<InstallVerification> {
+        let mut reporter = |_event: ProgressEvent| {};
+        self.repair_with_progress(version_id, &mut reporter)
+    }
+
+    /// Repairs an installed version and reports download progress.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if verification or a download fails.
+    pub fn repair_with_progress(
+        &self,
+        version_id: &str,
+        reporter: &mut dyn ProgressReporter,
+    ) -> Result<InstallVerification> {
+        let version = self.load_version(version_id)?;
+        repair_version_files(
+            &version,
+            &self.minecraft_dir,
+            Platform::current(),
+            &self.download_options,
+            reporter,
+        )
+    }
+
+    /// Removes an installed version, refusing if other profiles inherit from it.
+    ///
+    /// Shared libraries and assets are kept; use
+    /// [`Launcher::uninstall_with_options`] to cascade to dependent profiles or
+    /// collect garbage afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError::VersionInUse`] if another installed profile
+    /// inherits from the version, [`LauncherError::InvalidVersionId`] if it is
+    /// not installed, or another error if removal fails.
+    pub fn uninstall(&self, version_id: &str) -> Result<UninstallResult> {
+        self.uninstall_with_options(version_id, &UninstallOptions::default())
+    }
+
+    /// Removes an installed version with explicit dependent handling and
+    /// optional garbage collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if the version is not installed, is still
+    /// inherited and dependents are refused, or removal or collection fails.
+    pub fn uninstall_with_options(
+        &self,
+        version_id: &str,
+        options: &UninstallOptions,
+    ) -> Result<UninstallResult> {
+        uninstall_version(&self.minecraft_dir, version_id, options)
+    }
+
+    /// Lists libraries, assets, installers, and natives no installed version
+    /// uses, with their sizes, without deleting anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if an installed version cannot be loaded or a
+    /// directory cannot be read.
+    pub fn plan_garbage_collection(&self) -> Result<GcReport> {
+        plan_garbage_collection(&self.minecraft_dir)
+    }
+
+    /// Deletes libraries, assets, installers, and natives no installed version
+    /// uses.
+    ///
+    /// Returns the deleted items; [`GcReport::reclaimable_bytes`] is the space
+    /// freed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LauncherError`] if an installed version cannot be loaded or a
+    /// file cannot be deleted.
+    pub fn collect_garbage(&self) -> Result<GcReport> {
+        collect_garbage(&self.minecraft_dir)
+    }
+
     fn install_vanilla_version(
         &self,
         version_id: &str,
         reporter: &mut dyn ProgressReporter,
-    ) -> Result<()> {
-        let version = fetch_vanilla_version(version_id)?;
+    ) -> Result<VersionJson> {
+        let version = fetch_vanilla_version_with_mirrors(version_id, self.mirrors())?;
         write_version_json(&self.minecraft_dir, &version)?;
-        install_version_files(&version, &self.minecraft_dir, reporter)
+        self.install_files(&version, reporter)?;
+        Ok(version)
+    }
+
+    fn install_java(
+        &self,
+        policy: JavaInstallPolicy,
+        version: &VersionJson,
+        reporter: &mut dyn ProgressReporter,
+    ) -> Result<Option<PathBuf>> {
+        let Some(java_version) = &version.java_version else {
+            return Ok(None);
+        };
+        match policy {
+            JavaInstallPolicy::Never => Ok(None),
+            JavaInstallPolicy::Auto => install_runtime_with_options(
+                &java_version.component,
+                &self.minecraft_dir,
+                Platform::current(),
+                &self.download_options,
+                reporter,
+            ),
+        }
+    }
+
+    fn install_files(
+        &self,
+        version: &VersionJson,
+        reporter: &mut dyn ProgressReporter,
+    ) -> Result<()> {
+        install_version_files_with_options(
+            version,
+            &self.minecraft_dir,
+            Platform::current(),
+            CompatibilityPolicy::Auto,
+            &self.download_options,
+            reporter,
+        )
     }
 }
 
@@ -212,11 +881,12 @@ fn version_id<'a>(version: &'a VersionJson, context: &str) -> Result<&'a str> {
             field: "id".to_string(),
         })
 }
-fn resolve_fabric_loader_version(version: LoaderVersion) -> Result<String> {
+
+fn resolve_fabric_loader_version(version: LoaderVersion, mirrors: &MirrorConfig) -> Result<String> {
     match version {
         LoaderVersion::Exact(version) => Ok(version),
         LoaderVersion::Latest | LoaderVersion::LatestStable => {
-            let versions = crate::loader::fabric::list_loader_versions()?;
+            let versions = crate::loader::fabric::list_loader_versions_with_mirrors(mirrors)?;
             Ok(crate::loader::fabric::latest_stable_loader(&versions)?
                 .version
                 .clone())
@@ -224,11 +894,11 @@ fn resolve_fabric_loader_version(version: LoaderVersion) -> Result<String> {
     }
 }
 
-fn resolve_quilt_loader_version(version: LoaderVersion) -> Result<String> {
+fn resolve_quilt_loader_version(version: LoaderVersion, mirrors: &MirrorConfig) -> Result<String> {
     match version {
         LoaderVersion::Exact(version) => Ok(version),
         LoaderVersion::Latest | LoaderVersion::LatestStable => {
-            let versions = crate::loader::quilt::list_loader_versions()?;
+            let versions = crate::loader::quilt::list_loader_versions_with_mirrors(mirrors)?;
             Ok(crate::loader::quilt::latest_loader(&versions)?
                 .version
                 .clone())
@@ -236,11 +906,15 @@ fn resolve_quilt_loader_version(version: LoaderVersion) -> Result<String> {
     }
 }
 
-fn resolve_forge_loader_version(minecraft_version: &str, version: LoaderVersion) -> Result<String> {
+fn resolve_forge_loader_version(
+    minecraft_version: &str,
+    version: LoaderVersion,
+    mirrors: &MirrorConfig,
+) -> Result<String> {
     match version {
         LoaderVersion::Exact(version) => Ok(version),
         LoaderVersion::Latest | LoaderVersion::LatestStable => {
-            let versions = crate::loader::forge::list_forge_versions()?;
+            let versions = crate::loader::forge::list_forge_versions_with_mirrors(mirrors)?;
             Ok(
                 crate::loader::forge::latest_for_minecraft(&versions, minecraft_version)?
                     .to_string(),
@@ -252,11 +926,12 @@ fn resolve_forge_loader_version(minecraft_version: &str, version: LoaderVersion)
 fn resolve_neoforge_loader_version(
     minecraft_version: &str,
     version: LoaderVersion,
+    mirrors: &MirrorConfig,
 ) -> Result<String> {
     match version {
         LoaderVersion::Exact(version) => Ok(version),
         LoaderVersion::Latest | LoaderVersion::LatestStable => {
-            let versions = crate::loader::neoforge::list_neoforge_versions()?;
+            let versions = crate::loader::neoforge::list_neoforge_versions_with_mirrors(mirrors)?;
             Ok(
                 crate::loader::neoforge::latest_for_minecraft(&versions, minecraft_version)?
                     .to_string(),
@@ -267,6 +942,7 @@ fn resolve_neoforge_loader_version(
 
 fn download_installer(
     minecraft_dir: &Path,
+    options: &DownloadOptions,
     loader_name: &str,
     loader_version: &str,
     url: &str,
@@ -280,10 +956,11 @@ fn download_installer(
             url: url.to_string(),
             destination: destination.clone(),
             checksum: None,
+            size: None,
             label: format!("{loader_name} installer {loader_version}"),
         }],
     };
     let mut reporter = |_event: ProgressEvent| {};
-    execute_plan(&plan, &mut reporter)?;
+    execute_plan_with_options(&plan, options, &mut reporter)?;
     Ok(destination)
 }
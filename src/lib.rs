@@ -8,7 +8,8 @@
 //! - downloading client jars, libraries, assets, and native libraries;
 //! - merging inherited version metadata into a launchable [`core::version::VersionJson`];
 //! - building a [`command::builder::LaunchCommand`] that can be passed to
-//!   [`std::process::Command`];
+//!   [`std::process::Command`], or launched as a supervised
+//!   [`process::GameProcess`];
 //! - applying compatibility metadata for older Minecraft versions on macOS
 //!   Apple Silicon.
 //!
@@ -18,12 +19,10 @@
 //!
 //! # Quick Start
 //!
-//! Install Fabric, load the resulting profile, build a launch command, and run
-//! it with an offline account:
+//! Install Fabric, load the resulting profile, and launch it with an offline
+//! account:
 //!
 //! ```no_run
-//! use std::process::Command;
-//!
 //! use mc_launcher_core::prelude::*;
 //!
 //! fn main() -> mc_launcher_core::Result<()> {
@@ -39,19 +38,18 @@
 //!     })?;
 //!     let version = launcher.load_version(&install.version_id)?;
 //!
-//!     let command = launcher.build_launch_command_from_version(
+//!     let mut game = launcher.launch(
 //!         &version,
 //!         LaunchOptions {
 //!             account: Account::offline("Steve"),
+//!             java_executable: install.java_executable,
 //!             ..Default::default()
 //!         },
 //!     )?;
-//!
-//!     let mut child = Command::new(&command.executable)
-//!         .args(&command.args)
-//!         .current_dir(&command.working_dir)
-//!         .spawn()?;
-//!     child.wait()?;
+//!     for line in game.lines() {
+//!         println!("{}", line.line);
+//!     }
+//!     game.wait()?;
 //!     Ok(())
 //! }
 //! ```
@@ -64,18 +62,22 @@
 //!   installation work.
 //! - [`command`] turns version metadata and launch options into Java process
 //!   arguments.
+//! - [`process`] spawns a launch command and supervises the running game.
 //! - [`compatibility`] adjusts metadata for known platform gaps such as legacy
 //!   macOS arm64 LWJGL support.
 //! - [`auth`] contains offline and Microsoft account helpers.
+//! - [`modrinth`] is a typed client for the Modrinth v2 API.
 //! - [`core`], [`io`], and [`net`] hold lower-level primitives used by the
 //!   facade.
 //!
 //! # Java Runtime
 //!
-//! The crate does not currently bundle or manage a production Java runtime for
-//! the new facade. Use [`command::builder::LaunchOptions::java_executable`] to
-//! point at the runtime your launcher selected. Older compatibility wrappers in
-//! [`runtime`] are retained for existing callers.
+//! With [`install::JavaInstallPolicy::Auto`], installs provision the Mojang
+//! runtime named by the version metadata through [`install::runtime`] and
+//! return it as [`install::InstallResult::java_executable`]. Pass that path, or
+//! a runtime your launcher selected, to
+//! [`command::builder::LaunchOptions::java_executable`]. Older compatibility
+//! wrappers in [`runtime`] are retained for existing callers.
 //!
 //! # Error Handling
 //!
@@ -93,12 +95,17 @@ pub mod core;
 pub mod error;
 pub mod forge;
 pub mod install;
+pub mod instance;
 pub mod io;
 pub mod launcher;
 pub mod loader;
+pub mod modpack;
+pub mod modrinth;
+pub mod mods;
 pub mod net;
 pub mod platform;
 pub mod prelude;
+pub mod process;
 pub mod progress;
 pub mod runtime;
 pub mod types;
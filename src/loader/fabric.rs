@@ -2,9 +2,14 @@
 
 use serde::Deserialize;
 
-use crate::{core::version::VersionJson, net::http, LauncherError, Result};
-
-const FABRIC_META_BASE: &str = "https://meta.fabricmc.net/v2";
+use crate::{
+    core::version::VersionJson,
+    net::{
+        http,
+        mirror::{Endpoints, MirrorConfig},
+    },
+    LauncherError, Result,
+};
 
 /// Fabric loader version entry from Fabric Meta.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
@@ -42,7 +47,20 @@ pub fn latest_stable_loader(versions: &[FabricLoaderVersion]) -> Result<&FabricL
 ///
 /// Returns [`crate::LauncherError`] if the metadata request or decoding fails.
 pub fn list_loader_versions() -> Result<Vec<FabricLoaderVersion>> {
-    http::get_json(&format!("{FABRIC_META_BASE}/versions/loader"))
+    list_loader_versions_with_mirrors(&MirrorConfig::official())
+}
+
+/// Fetches Fabric loader versions through the given mirrors.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if every candidate request or decoding
+/// fails.
+pub fn list_loader_versions_with_mirrors(
+    mirrors: &MirrorConfig,
+) -> Result<Vec<FabricLoaderVersion>> {
+    let base = Endpoints::official().fabric_meta;
+    http::get_json_with_mirrors(&format!("{base}/versions/loader"), mirrors)
 }
 
 /// Fetches a Fabric loader profile for a Minecraft and loader version.
@@ -51,7 +69,29 @@ pub fn list_loader_versions() -> Result<Vec<FabricLoaderVersion>> {
 ///
 /// Returns [`crate::LauncherError`] if the metadata request or decoding fails.
 pub fn fetch_profile(minecraft_version: &str, loader_version: &str) -> Result<VersionJson> {
-    http::get_json(&format!(
-        "{FABRIC_META_BASE}/versions/loader/{minecraft_version}/{loader_version}/profile/json"
-    ))
+    fetch_profile_with_mirrors(minecraft_version, loader_version, &MirrorConfig::official())
+}
+
+/// Fetches a Fabric loader profile through the given mirrors.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if every candidate request or decoding
+/// fails.
+pub fn fetch_profile_with_mirrors(
+    minecraft_version: &str,
+    loader_version: &str,
+    mirrors: &MirrorConfig,
+) -> Result<VersionJson> {
+    let base = Endpoints::official().fabric_meta;
+    http::get_json_with_mirrors(
+        &format!("{base}/versions/loader/{minecraft_version}/{loader_version}/profile/json"),
+        mirrors,
+    )
+}
+
+/// Returns the profile id the Fabric installer writes for a Minecraft and
+/// loader version, such as `fabric-loader-0.15.0-1.20.1`.
+pub fn fabric_installed_version_id(minecraft_version: &str, loader_version: &str) -> String {
+    format!("fabric-loader-{loader_version}-{minecraft_version}")
 }
@@ -2,10 +2,13 @@
 
 use regex::Regex;
 
-use crate::{net::http, LauncherError, Result};
-
-const FORGE_METADATA_URL: &str =
-    "https://maven.minecraftforge.net/net/minecraftforge/forge/maven-metadata.xml";
+use crate::{
+    net::{
+        http,
+        mirror::{Endpoints, MirrorConfig},
+    },
+    LauncherError, Result,
+};
 
 /// Parsed Maven metadata from the Forge repository.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -61,7 +64,19 @@ fn capture_one(xml: &str, pattern: &str, field: &str) -> Result<String> {
 ///
 /// Returns [`crate::LauncherError`] if the metadata request or parsing fails.
 pub fn list_forge_versions() -> Result<Vec<String>> {
-    Ok(parse_maven_metadata(&http::get_text(FORGE_METADATA_URL)?)?.versions)
+    list_forge_versions_with_mirrors(&MirrorConfig::official())
+}
+
+/// Fetches all Forge versions through the given mirrors.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if every candidate request fails or the
+/// metadata cannot be parsed.
+pub fn list_forge_versions_with_mirrors(mirrors: &MirrorConfig) -> Result<Vec<String>> {
+    let base = Endpoints::official().forge_maven;
+    let url = format!("{base}/net/minecraftforge/forge/maven-metadata.xml");
+    Ok(parse_maven_metadata(&http::get_text_with_mirrors(&url, mirrors)?)?.versions)
 }
 
 /// Returns the newest advertised Forge version for a Minecraft version.
@@ -101,9 +116,13 @@ pub fn forge_installed_version_id(forge_version: &str) -> Result<String> {
 }
 
 /// Returns the Forge installer jar URL for a Forge version.
+///
+/// The URL points at the official Maven repository; downloads are rewritten by
+/// the active [`MirrorConfig`].
 pub fn installer_url(forge_version: &str) -> String {
     format!(
-        "https://maven.minecraftforge.net/net/minecraftforge/forge/{0}/forge-{0}-installer.jar",
+        "{}/net/minecraftforge/forge/{1}/forge-{1}-installer.jar",
+        Endpoints::official().forge_maven,
         forge_version
     )
 }
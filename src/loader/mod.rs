@@ -4,6 +4,8 @@
 //! metadata APIs. Forge and NeoForge expose metadata helpers plus installer URLs
 //! used by the high-level [`crate::launcher::Launcher`] facade.
 
+use serde::{Deserialize, Serialize};
+
 pub mod common;
 pub mod fabric;
 pub mod forge;
@@ -11,7 +13,10 @@ pub mod neoforge;
 pub mod quilt;
 
 /// Supported mod loader families.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Serialized in lowercase, for example `"neoforge"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum LoaderKind {
     /// Fabric loader.
     Fabric,
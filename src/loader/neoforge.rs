@@ -2,13 +2,13 @@
 
 use crate::{
     loader::{forge::MavenMetadata, LoaderKind},
-    net::http,
+    net::{
+        http,
+        mirror::{Endpoints, MirrorConfig},
+    },
     LauncherError, Result,
 };
 
-const NEOFORGE_METADATA_URL: &str =
-    "https://maven.neoforged.net/releases/net/neoforged/neoforge/maven-metadata.xml";
-
 /// Parses NeoForge Maven metadata XML.
 ///
 /// # Errors
@@ -24,7 +24,19 @@ pub fn parse_maven_metadata(xml: &str) -> Result<MavenMetadata> {
 ///
 /// Returns [`crate::LauncherError`] if the metadata request or parsing fails.
 pub fn list_neoforge_versions() -> Result<Vec<String>> {
-    Ok(parse_maven_metadata(&http::get_text(NEOFORGE_METADATA_URL)?)?.versions)
+    list_neoforge_versions_with_mirrors(&MirrorConfig::official())
+}
+
+/// Fetches all NeoForge versions through the given mirrors.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if every candidate request fails or the
+/// metadata cannot be parsed.
+pub fn list_neoforge_versions_with_mirrors(mirrors: &MirrorConfig) -> Result<Vec<String>> {
+    let base = Endpoints::official().neoforge_maven;
+    let url = format!("{base}/net/neoforged/neoforge/maven-metadata.xml");
+    Ok(parse_maven_metadata(&http::get_text_with_mirrors(&url, mirrors)?)?.versions)
 }
 
 /// Returns the newest advertised NeoForge version for a Minecraft version.
@@ -96,9 +108,13 @@ pub fn neoforge_installed_version_id(_minecraft_version: &str, neoforge_version:
 }
 
 /// Returns the NeoForge installer jar URL for a NeoForge version.
+///
+/// The URL points at the official Maven repository; downloads are rewritten by
+/// the active [`MirrorConfig`].
 pub fn installer_url(neoforge_version: &str) -> String {
     format!(
-        "https://maven.neoforged.net/releases/net/neoforged/neoforge/{0}/neoforge-{0}-installer.jar",
+        "{}/net/neoforged/neoforge/{1}/neoforge-{1}-installer.jar",
+        Endpoints::official().neoforge_maven,
         neoforge_version
     )
 }
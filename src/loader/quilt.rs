@@ -4,9 +4,14 @@ use std::collections::HashMap;
 
 use serde::Deserialize;
 
-use crate::{core::version::VersionJson, net::http, LauncherError, Result};
-
-const QUILT_META_BASE: &str = "https://meta.quiltmc.org/v3";
+use crate::{
+    core::version::VersionJson,
+    net::{
+        http,
+        mirror::{Endpoints, MirrorConfig},
+    },
+    LauncherError, Result,
+};
 
 /// Quilt loader version entry from Quilt Meta.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
@@ -47,7 +52,20 @@ pub fn latest_loader(versions: &[QuiltLoaderVersion]) -> Result<&QuiltLoaderVers
 ///
 /// Returns [`crate::LauncherError`] if the metadata request or decoding fails.
 pub fn list_loader_versions() -> Result<Vec<QuiltLoaderVersion>> {
-    http::get_json(&format!("{QUILT_META_BASE}/versions/loader"))
+    list_loader_versions_with_mirrors(&MirrorConfig::official())
+}
+
+/// Fetches Quilt loader versions through the given mirrors.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if every candidate request or decoding
+/// fails.
+pub fn list_loader_versions_with_mirrors(
+    mirrors: &MirrorConfig,
+) -> Result<Vec<QuiltLoaderVersion>> {
+    let base = Endpoints::official().quilt_meta;
+    http::get_json_with_mirrors(&format!("{base}/versions/loader"), mirrors)
 }
 
 /// Fetches a Quilt loader profile for a Minecraft and loader version.
@@ -56,7 +74,29 @@ pub fn list_loader_versions() -> Result<Vec<QuiltLoaderVersion>> {
 ///
 /// Returns [`crate::LauncherError`] if the metadata request or decoding fails.
 pub fn fetch_profile(minecraft_version: &str, loader_version: &str) -> Result<VersionJson> {
-    http::get_json(&format!(
-        "{QUILT_META_BASE}/versions/loader/{minecraft_version}/{loader_version}/profile/json"
-    ))
+    fetch_profile_with_mirrors(minecraft_version, loader_version, &MirrorConfig::official())
+}
+
+/// Fetches a Quilt loader profile through the given mirrors.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if every candidate request or decoding
+/// fails.
+pub fn fetch_profile_with_mirrors(
+    minecraft_version: &str,
+    loader_version: &str,
+    mirrors: &MirrorConfig,
+) -> Result<VersionJson> {
+    let base = Endpoints::official().quilt_meta;
+    http::get_json_with_mirrors(
+        &format!("{base}/versions/loader/{minecraft_version}/{loader_version}/profile/json"),
+        mirrors,
+    )
+}
+
+/// Returns the profile id the Quilt installer writes for a Minecraft and
+/// loader version, such as `quilt-loader-0.26.0-1.20.1`.
+pub fn quilt_installed_version_id(minecraft_version: &str, loader_version: &str) -> String {
+    format!("quilt-loader-{loader_version}-{minecraft_version}")
 }
@@ -0,0 +1,199 @@
+//! CurseForge modpack zips.
+//!
+//! A CurseForge pack is a ZIP archive with a `manifest.json` naming the
+//! Minecraft version, the loader, and project/file IDs to download, plus an
+//! overrides directory copied into the game directory as-is. The manifest does
+//! not contain download URLs, so files are resolved through a
+//! [`CurseForgeResolver`]. [`crate::launcher::Launcher::import_curseforge`]
+//! combines the helpers here into a complete import into a new instance.
+
+use std::{fs::File, io::Read, path::Path};
+
+use zip::ZipArchive;
+
+use crate::{
+    install::request::{InstallRequest, JavaInstallPolicy},
+    instance::InstanceLoader,
+    io::{archive::extract_zip_directory_safely, paths::safe_join},
+    loader::LoaderKind,
+    net::download::{Checksum, DownloadPlan, DownloadTask},
+    types::curseforge_types::{CurseForgeFile, CurseForgeManifest},
+    LauncherError, Result,
+};
+
+/// Name of the manifest at the root of a CurseForge pack.
+pub const CURSEFORGE_MANIFEST: &str = "manifest.json";
+
+/// Loader ID prefixes used in `minecraft.modLoaders`.
+const LOADER_PREFIXES: [(&str, LoaderKind); 4] = [
+    ("forge-", LoaderKind::Forge),
+    ("fabric-", LoaderKind::Fabric),
+    ("quilt-", LoaderKind::Quilt),
+    ("neoforge-", LoaderKind::NeoForge),
+];
+
+/// Download location of one manifest file, as returned by a
+/// [`CurseForgeResolver`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurseForgeDownload {
+    /// Download URL.
+    pub url: String,
+    /// Destination relative to the game directory, such as
+    /// `mods/jei-1.20.1-15.2.0.jar`.
+    pub path: String,
+    /// SHA-1 digest as lowercase hexadecimal, when known.
+    pub sha1: Option<String>,
+    /// Size in bytes, when known.
+    pub size: Option<u64>,
+}
+
+/// Maps CurseForge project and file IDs to downloads.
+///
+/// Implement this on top of the CurseForge API or a local cache. Closures
+/// taking a [`CurseForgeFile`] implement this trait.
+pub trait CurseForgeResolver {
+    /// Returns where a manifest file is downloaded from and installed to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the file cannot be resolved; the
+    /// import is aborted.
+    fn resolve(&mut self, file: &CurseForgeFile) -> Result<CurseForgeDownload>;
+}
+
+impl<F> CurseForgeResolver for F
+where
+    F: FnMut(&CurseForgeFile) -> Result<CurseForgeDownload>,
+{
+    fn resolve(&mut self, file: &CurseForgeFile) -> Result<CurseForgeDownload> {
+        self(file)
+    }
+}
+
+/// Reads and validates `manifest.json` from a CurseForge pack.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the archive or manifest cannot be read,
+/// or the manifest is not a version 1 Minecraft modpack.
+pub fn read_curseforge_manifest(path: impl AsRef<Path>) -> Result<CurseForgeManifest> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+    let mut bytes = Vec::new();
+    archive
+        .by_name(CURSEFORGE_MANIFEST)?
+        .read_to_end(&mut bytes)?;
+    let manifest: CurseForgeManifest = serde_json::from_slice(&bytes)?;
+    if manifest.manifest_type != "minecraftModpack" || manifest.manifest_version != 1 {
+        return Err(LauncherError::Other {
+            message: format!(
+                "unsupported CurseForge manifest: type {:?}, version {}",
+                manifest.manifest_type, manifest.manifest_version
+            ),
+        });
+    }
+    Ok(manifest)
+}
+
+/// Returns the loader a pack is installed with, if any.
+///
+/// The primary entry of `minecraft.modLoaders` is used, or the only entry when
+/// none is marked primary. Forge versions are returned in the
+/// `<minecraft>-<forge>` form Forge installers are published under.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the loader ID is not recognised or
+/// several loaders are listed without a single primary one.
+pub fn curseforge_loader(manifest: &CurseForgeManifest) -> Result<Option<InstanceLoader>> {
+    let loaders = &manifest.minecraft.mod_loaders;
+    let primary: Vec<_> = loaders.iter().filter(|loader| loader.primary).collect();
+    let loader = match (primary.as_slice(), loaders.as_slice()) {
+        ([primary], _) => primary,
+        ([], []) => return Ok(None),
+        ([], [only]) => only,
+        _ => {
+            return Err(LauncherError::Other {
+                message: "CurseForge manifest lists more than one primary loader".to_string(),
+            })
+        }
+    };
+    let Some((kind, version)) = LOADER_PREFIXES.iter().find_map(|(prefix, kind)| {
+        loader
+            .id
+            .strip_prefix(prefix)
+            .map(|version| (*kind, version))
+    }) else {
+        return Err(LauncherError::Other {
+            message: format!("unsupported CurseForge loader: {}", loader.id),
+        });
+    };
+    Ok(Some(InstanceLoader::new(
+        kind,
+        &manifest.minecraft.version,
+        version,
+    )))
+}
+
+/// Maps a pack's `minecraft` block to the install request for its profile.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the loader cannot be determined.
+pub fn curseforge_install_request(manifest: &CurseForgeManifest) -> Result<InstallRequest> {
+    Ok(InstallRequest {
+        minecraft_version: manifest.minecraft.version.clone(),
+        loader: curseforge_loader(manifest)?
+            .as_ref()
+            .map(InstanceLoader::spec),
+        java: JavaInstallPolicy::Auto,
+    })
+}
+
+/// Resolves the required files of a pack and plans their downloads into
+/// `game_dir`.
+///
+/// Files marked `required: false` are disabled in the pack and skipped. Each
+/// download is checked against the SHA-1 digest and size the resolver
+/// reports, when it reports them.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the resolver fails or a resolved path
+/// escapes `game_dir`.
+pub fn plan_curseforge_downloads(
+    manifest: &CurseForgeManifest,
+    game_dir: impl AsRef<Path>,
+    resolver: &mut dyn CurseForgeResolver,
+) -> Result<DownloadPlan> {
+    let game_dir = game_dir.as_ref();
+    let mut plan = DownloadPlan::default();
+    for file in manifest.files.iter().filter(|file| file.required) {
+        let download = resolver.resolve(file)?;
+        plan.tasks.push(DownloadTask {
+            url: download.url,
+            destination: safe_join(game_dir, &download.path)?,
+            checksum: download.sha1.map(Checksum::Sha1),
+            size: download.size,
+            label: download.path,
+        });
+    }
+    Ok(plan)
+}
+
+/// Extracts the pack's overrides directory into `game_dir`.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the archive cannot be read, a file
+/// cannot be written, or an entry path is unsafe.
+pub fn extract_curseforge_overrides(
+    path: impl AsRef<Path>,
+    manifest: &CurseForgeManifest,
+    game_dir: impl AsRef<Path>,
+) -> Result<()> {
+    let overrides = manifest.overrides.trim_matches('/');
+    if overrides.is_empty() {
+        return Ok(());
+    }
+    extract_zip_directory_safely(path, overrides, game_dir)
+}
@@ -0,0 +1,9 @@
+//! Modpack and foreign launcher instance import and export.
+//!
+//! Modpacks and instances from other launchers are unpacked into
+//! [`crate::instance`] directories; the facade entry points live on
+//! [`crate::launcher::Launcher`].
+
+pub mod curseforge;
+pub mod mrpack;
+pub mod multimc;
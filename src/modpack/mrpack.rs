@@ -0,0 +1,481 @@
+//! Modrinth `.mrpack` modpacks.
+//!
+//! An `.mrpack` is a ZIP archive with a `modrinth.index.json` describing the
+//! Minecraft and loader versions and the files to download, plus `overrides/`
+//! and `client-overrides/` trees copied into the game directory as-is.
+//! [`crate::launcher::Launcher::import_mrpack`] combines the helpers here into
+//! a complete import into a new instance, and [`export_mrpack`] writes an
+//! instance back out as a pack.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use zip::{write::SimpleFileOptions, ZipArchive, ZipWriter};
+
+use crate::{
+    install::request::{InstallRequest, JavaInstallPolicy},
+    instance::{Instance, InstanceConfig, InstanceLoader},
+    io::{
+        archive::extract_zip_directory_safely,
+        hash::{sha1_file, sha512_file},
+        paths::safe_join,
+    },
+    loader::LoaderKind,
+    net::download::{Checksum, DownloadPlan, DownloadTask},
+    types::{
+        mrpack_types::{MrpackEnvSupport, MrpackFile, MrpackHashes, MrpackIndex},
+        MrpackInformation, MrpackInstallOptions,
+    },
+    LauncherError, Result,
+};
+
+/// Name of the index file at the root of an `.mrpack`.
+pub const MRPACK_INDEX: &str = "modrinth.index.json";
+
+/// Directory copied into the game directory on every side.
+pub const OVERRIDES: &str = "overrides";
+
+/// Directory copied into the game directory on the client, after
+/// [`OVERRIDES`].
+pub const CLIENT_OVERRIDES: &str = "client-overrides";
+
+/// Game directory folders whose files may be exported as downloads instead of
+/// overrides.
+pub const EXPORT_CONTENT_DIRECTORIES: [&str; 3] = ["mods", "resourcepacks", "shaderpacks"];
+
+/// Patterns excluded from exports by [`MrpackExportOptions::new`].
+pub const DEFAULT_EXPORT_EXCLUDES: [&str; 3] = ["logs", "crash-reports", "screenshots"];
+
+/// Dependency keys naming each loader, as written by Modrinth.
+const LOADER_DEPENDENCIES: [(&str, LoaderKind); 4] = [
+    ("fabric-loader", LoaderKind::Fabric),
+    ("quilt-loader", LoaderKind::Quilt),
+    ("forge", LoaderKind::Forge),
+    ("neoforge", LoaderKind::NeoForge),
+];
+
+/// Reads and validates `modrinth.index.json` from an `.mrpack`.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the archive or index cannot be read, or
+/// the index is not a format 1 Minecraft pack.
+pub fn read_mrpack_index(path: impl AsRef<Path>) -> Result<MrpackIndex> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+    let mut bytes = Vec::new();
+    archive.by_name(MRPACK_INDEX)?.read_to_end(&mut bytes)?;
+    let index: MrpackIndex = serde_json::from_slice(&bytes)?;
+    if index.game != "minecraft" || index.format_version != 1 {
+        return Err(LauncherError::Other {
+            message: format!(
+                "unsupported mrpack: game {:?}, format version {}",
+                index.game, index.format_version
+            ),
+        });
+    }
+    Ok(index)
+}
+
+/// Reads the name, versions, and optional files of an `.mrpack`.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the index cannot be read or names no
+/// Minecraft version.
+pub fn read_mrpack_information(path: impl AsRef<Path>) -> Result<MrpackInformation> {
+    let index = read_mrpack_index(path)?;
+    Ok(MrpackInformation {
+        minecraft_version: minecraft_version(&index)?.to_string(),
+        name: index.name,
+        summary: index.summary.unwrap_or_default(),
+        version_id: index.version_id,
+        format_version: index.format_version as i32,
+        optional_files: index
+            .files
+            .iter()
+            .filter(|file| client_support(file) == MrpackEnvSupport::Optional)
+            .map(|file| file.path.clone())
+            .collect(),
+    })
+}
+
+/// Returns the loader a pack depends on, if any.
+///
+/// Forge versions are returned in the `<minecraft>-<forge>` form Forge
+/// installers are published under.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the pack names an unknown dependency or
+/// more than one loader.
+pub fn mrpack_loader(index: &MrpackIndex) -> Result<Option<InstanceLoader>> {
+    let mut loader = None;
+    for (key, version) in &index.dependencies {
+        if key == "minecraft" {
+            continue;
+        }
+        let Some((_, kind)) = LOADER_DEPENDENCIES.iter().find(|(name, _)| name == key) else {
+            return Err(LauncherError::Other {
+                message: format!("unsupported mrpack dependency: {key}"),
+            });
+        };
+        if loader.is_some() {
+            return Err(LauncherError::Other {
+                message: "mrpack depends on more than one loader".to_string(),
+            });
+        }
+        loader = Some(InstanceLoader::new(
+            *kind,
+            minecraft_version(index)?,
+            version,
+        ));
+    }
+    Ok(loader)
+}
+
+/// Maps a pack's `dependencies` to the install request for its profile.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the Minecraft version is missing or the
+/// loader dependencies are invalid.
+pub fn mrpack_install_request(index: &MrpackIndex) -> Result<InstallRequest> {
+    Ok(InstallRequest {
+        minecraft_version: minecraft_version(index)?.to_string(),
+        loader: mrpack_loader(index)?.as_ref().map(InstanceLoader::spec),
+        java: JavaInstallPolicy::Auto,
+    })
+}
+
+/// Plans the client file downloads of a pack into `game_dir`.
+///
+/// Files unsupported on the client are skipped, and optional files are only
+/// included when listed in [`MrpackInstallOptions::optional_files`]. Each file
+/// is checked against its size and SHA-512 digest, or SHA-1 when the pack
+/// omits SHA-512, and downloaded from its first URL. Passing
+/// [`mrpack_alternative_urls`] as
+/// [`crate::net::download::DownloadOptions::alternatives`] falls back to the
+/// others.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if a file path escapes `game_dir` or a
+/// file has no download URL.
+pub fn plan_mrpack_downloads(
+    index: &MrpackIndex,
+    game_dir: impl AsRef<Path>,
+    options: &MrpackInstallOptions,
+) -> Result<DownloadPlan> {
+    let game_dir = game_dir.as_ref();
+    let selected = options.optional_files.as_deref().unwrap_or_default();
+    let mut plan = DownloadPlan::default();
+    for file in &index.files {
+        match client_support(file) {
+            MrpackEnvSupport::Required => {}
+            MrpackEnvSupport::Optional if selected.contains(&file.path) => {}
+            MrpackEnvSupport::Optional | MrpackEnvSupport::Unsupported => continue,
+        }
+        let url = file
+            .downloads
+            .first()
+            .ok_or_else(|| LauncherError::MissingField {
+                context: format!("{MRPACK_INDEX} file {}", file.path),
+                field: "downloads".to_string(),
+            })?;
+        let checksum = if file.hashes.sha512.is_empty() {
+            Checksum::Sha1(file.hashes.sha1.clone())
+        } else {
+            Checksum::Sha512(file.hashes.sha512.clone())
+        };
+        plan.tasks.push(DownloadTask {
+            url: url.clone(),
+            destination: safe_join(game_dir, &file.path)?,
+            checksum: Some(checksum),
+            size: Some(file.file_size),
+            label: file.path.clone(),
+        });
+    }
+    Ok(plan)
+}
+
+/// Returns the download URLs after the first of every pack file with more than
+/// one, keyed by the first URL.
+///
+/// The mrpack format lists every URL of a file as an equivalent source.
+pub fn mrpack_alternative_urls(index: &MrpackIndex) -> HashMap<String, Vec<String>> {
+    index
+        .files
+        .iter()
+        .filter_map(|file| match file.downloads.as_slice() {
+            [first, rest @ ..] if !rest.is_empty() => Some((first.clone(), rest.to_vec())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Extracts `overrides/` and then `client-overrides/` into `game_dir`.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the archive cannot be read, a file
+/// cannot be written, or an entry path is unsafe.
+pub fn extract_mrpack_overrides(path: impl AsRef<Path>, game_dir: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    for directory in [OVERRIDES, CLIENT_OVERRIDES] {
+        extract_zip_directory_safely(path, directory, game_dir.as_ref())?;
+    }
+    Ok(())
+}
+
+/// Settings for [`export_mrpack`].
+///
+/// `include` and `exclude` hold glob patterns matched against `/`-separated
+/// paths relative to the game directory. `*` matches within one path segment,
+/// `?` matches one character, and `**` matches any number of segments. A
+/// pattern that matches a directory also matches everything below it. An
+/// empty `include` list includes every file; `exclude` wins over `include`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MrpackExportOptions {
+    /// Pack name written to the index.
+    pub name: String,
+    /// Pack version written to the index, such as `1.2.0`.
+    pub version_id: String,
+    /// Optional short description.
+    pub summary: Option<String>,
+    /// Patterns of files to export; empty means all files.
+    pub include: Vec<String>,
+    /// Patterns of files to leave out.
+    pub exclude: Vec<String>,
+}
+
+impl MrpackExportOptions {
+    /// Creates options that export everything except
+    /// [`DEFAULT_EXPORT_EXCLUDES`].
+    pub fn new(name: impl Into<String>, version_id: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version_id: version_id.into(),
+            summary: None,
+            include: Vec::new(),
+            exclude: DEFAULT_EXPORT_EXCLUDES.map(str::to_string).to_vec(),
+        }
+    }
+
+    fn selects(&self, path: &str) -> bool {
+        let matches = |pattern: &String| glob_matches_path(pattern, path);
+        (self.include.is_empty() || self.include.iter().any(matches))
+            && !self.exclude.iter().any(matches)
+    }
+}
+
+/// Supplies download URLs for files exported by [`export_mrpack`].
+///
+/// Closures taking the file's path relative to the game directory and its
+/// digests implement this trait.
+pub trait MrpackDownloadResolver {
+    /// Returns the URLs a file can be downloaded from, or `None` to pack the
+    /// file into `overrides/` instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the lookup fails; the export is
+    /// aborted.
+    fn resolve(&mut self, path: &str, hashes: &MrpackHashes) -> Result<Option<Vec<String>>>;
+}
+
+impl<F> MrpackDownloadResolver for F
+where
+    F: FnMut(&str, &MrpackHashes) -> Result<Option<Vec<String>>>,
+{
+    fn resolve(&mut self, path: &str, hashes: &MrpackHashes) -> Result<Option<Vec<String>>> {
+        self(path, hashes)
+    }
+}
+
+/// Returns the `dependencies` of a pack built from an instance.
+///
+/// Forge versions stored in the `<minecraft>-<forge>` form are written without
+/// the Minecraft prefix, as Modrinth expects.
+pub fn mrpack_dependencies(config: &InstanceConfig) -> BTreeMap<String, String> {
+    let mut dependencies = BTreeMap::new();
+    dependencies.insert("minecraft".to_string(), config.minecraft_version.clone());
+    if let Some(loader) = &config.loader {
+        let key = LOADER_DEPENDENCIES
+            .iter()
+            .find(|(_, kind)| *kind == loader.kind)
+            .map(|(key, _)| *key);
+        if let Some(key) = key {
+            dependencies.insert(
+                key.to_string(),
+                loader.pack_version(&config.minecraft_version).to_string(),
+            );
+        }
+    }
+    dependencies
+}
+
+/// Writes an instance's game directory to `destination` as an `.mrpack`.
+///
+/// The Minecraft version and loader come from the instance's settings. Files
+/// selected by `options` below [`EXPORT_CONTENT_DIRECTORIES`] are hashed and
+/// offered to `resolver`; those it returns URLs for are listed in `files`, and
+/// every other selected file is stored under `overrides/`. The archive is
+/// written to a `.part` file and renamed into place once complete.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the game directory cannot be read, a
+/// path is not valid UTF-8, the resolver fails, or the archive cannot be
+/// written.
+pub fn export_mrpack(
+    instance: &Instance,
+    destination: impl AsRef<Path>,
+    options: &MrpackExportOptions,
+    resolver: &mut dyn MrpackDownloadResolver,
+) -> Result<MrpackIndex> {
+    let destination = destination.as_ref();
+    let game_dir = instance.game_directory();
+    let mut paths = Vec::new();
+    if game_dir.is_dir() {
+        collect_files(&game_dir, "", &mut paths)?;
+    }
+    paths.retain(|path| options.selects(path));
+
+    let mut index = MrpackIndex {
+        format_version: 1,
+        game: "minecraft".to_string(),
+        version_id: options.version_id.clone(),
+        name: options.name.clone(),
+        summary: options.summary.clone(),
+        files: Vec::new(),
+        dependencies: mrpack_dependencies(&instance.config),
+    };
+    let mut overrides = Vec::new();
+    for path in paths {
+        let file = game_dir.join(&path);
+        let content = path
+            .split_once('/')
+            .is_some_and(|(directory, _)| EXPORT_CONTENT_DIRECTORIES.contains(&directory));
+        if content {
+            let hashes = MrpackHashes {
+                sha1: sha1_file(&file)?,
+                sha512: sha512_file(&file)?,
+            };
+            match resolver.resolve(&path, &hashes)? {
+                Some(downloads) if !downloads.is_empty() => {
+                    index.files.push(MrpackFile {
+                        file_size: fs::metadata(&file)?.len(),
+                        path,
+                        hashes,
+                        env: None,
+                        downloads,
+                    });
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        overrides.push((path, file));
+    }
+
+    let partial = partial_path(destination);
+    let result = write_mrpack(&partial, &index, &overrides)
+        .and_then(|()| fs::rename(&partial, destination).map_err(Into::into));
+    if result.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+    result.map(|()| index)
+}
+
+fn write_mrpack(path: &Path, index: &MrpackIndex, overrides: &[(String, PathBuf)]) -> Result<()> {
+    let mut zip = ZipWriter::new(File::create(path)?);
+    let options = SimpleFileOptions::default().large_file(true);
+    zip.start_file(MRPACK_INDEX, options)?;
+    zip.write_all(&serde_json::to_vec_pretty(index)?)?;
+    for (name, file) in overrides {
+        zip.start_file(format!("{OVERRIDES}/{name}"), options)?;
+        io::copy(&mut File::open(file)?, &mut zip)?;
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+fn collect_files(directory: &Path, prefix: &str, paths: &mut Vec<String>) -> Result<()> {
+    let mut entries = fs::read_dir(directory)?.collect::<io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let name = entry.file_name();
+        let Some(name) = name.to_str() else {
+            return Err(LauncherError::Other {
+                message: format!("cannot export non UTF-8 path {}", entry.path().display()),
+            });
+        };
+        let relative = format!("{prefix}{name}");
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(&entry.path(), &format!("{relative}/"), paths)?;
+        } else if file_type.is_file() {
+            paths.push(relative);
+        }
+    }
+    Ok(())
+}
+
+fn partial_path(destination: &Path) -> PathBuf {
+    let mut name = destination
+        .file_name()
+        .map(OsString::from)
+        .unwrap_or_default();
+    name.push(".part");
+    destination.with_file_name(name)
+}
+
+fn glob_matches_path(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').filter(|part| !part.is_empty()).collect();
+    let path: Vec<&str> = path.split('/').collect();
+    (1..=path.len()).any(|len| glob_matches_segments(&pattern, &path[..len]))
+}
+
+fn glob_matches_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => {
+            (0..=path.len()).any(|skip| glob_matches_segments(rest, &path[skip..]))
+        }
+        Some((first, rest)) => path.split_first().is_some_and(|(segment, path)| {
+            let pattern: Vec<char> = first.chars().collect();
+            let segment: Vec<char> = segment.chars().collect();
+            glob_matches_segment(&pattern, &segment) && glob_matches_segments(rest, path)
+        }),
+    }
+}
+
+fn glob_matches_segment(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| glob_matches_segment(rest, &name[skip..])),
+        Some(('?', rest)) => !name.is_empty() && glob_matches_segment(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && glob_matches_segment(rest, &name[1..]),
+    }
+}
+
+fn minecraft_version(index: &MrpackIndex) -> Result<&str> {
+    index
+        .dependencies
+        .get("minecraft")
+        .map(String::as_str)
+        .ok_or_else(|| LauncherError::MissingField {
+            context: MRPACK_INDEX.to_string(),
+            field: "dependencies.minecraft".to_string(),
+        })
+}
+
+fn client_support(file: &MrpackFile) -> MrpackEnvSupport {
+    file.env
+        .map_or(MrpackEnvSupport::Required, |env| env.client)
+}
@@ -0,0 +1,259 @@
+//! MultiMC and Prism Launcher instances.
+//!
+//! A MultiMC-style instance directory holds `instance.cfg` with per-instance
+//! settings, `mmc-pack.json` listing components such as `net.minecraft` and
+//! `net.fabricmc.fabric-loader`, optional `patches/<uid>.json` overrides, and
+//! the game directory in `.minecraft` or `minecraft`.
+//! [`read_multimc_instance`] maps this onto [`InstanceConfig`], and
+//! [`crate::launcher::Launcher::import_multimc`] copies it into a new instance.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    install::request::InstallRequest,
+    instance::{Instance, InstanceConfig, InstanceLoader, Resolution},
+    loader::LoaderKind,
+    types::multimc_types::{MmcComponent, MmcPack},
+    LauncherError, Result,
+};
+
+/// Settings file of a MultiMC instance.
+pub const INSTANCE_CFG: &str = "instance.cfg";
+
+/// Component list of a MultiMC instance.
+pub const MMC_PACK: &str = "mmc-pack.json";
+
+/// Game directory names inside a MultiMC instance, in order of preference.
+pub const GAME_DIRECTORIES: [&str; 2] = [".minecraft", "minecraft"];
+
+/// Component id of Minecraft itself.
+const MINECRAFT_UID: &str = "net.minecraft";
+
+/// Component ids naming each loader.
+const LOADER_UIDS: [(&str, LoaderKind); 4] = [
+    ("net.fabricmc.fabric-loader", LoaderKind::Fabric),
+    ("org.quiltmc.quilt-loader", LoaderKind::Quilt),
+    ("net.minecraftforge", LoaderKind::Forge),
+    ("net.neoforged", LoaderKind::NeoForge),
+];
+
+/// Components the loader installers provide on their own.
+const IMPLIED_UIDS: [&str; 4] = [
+    "org.lwjgl",
+    "org.lwjgl3",
+    "net.fabricmc.intermediary",
+    "org.quiltmc.hashed",
+];
+
+/// A MultiMC or Prism Launcher instance mapped onto this crate's settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiMcInstance {
+    /// Display name from `instance.cfg`, or the directory name.
+    pub name: String,
+    /// Settings for the imported instance, including JVM arguments, memory,
+    /// Java path, and window size when the source instance overrides them.
+    pub config: InstanceConfig,
+    /// Game directory of the source instance, if it has one.
+    pub game_directory: Option<PathBuf>,
+    /// Components that could not be mapped and are not imported.
+    pub unmapped_components: Vec<UnmappedComponent>,
+}
+
+impl MultiMcInstance {
+    /// Returns the install request that produces the instance's profile.
+    pub fn install_request(&self) -> InstallRequest {
+        self.config.install_request()
+    }
+}
+
+/// A component of `mmc-pack.json` the import cannot reproduce, such as a jar
+/// mod or a component with a custom patch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmappedComponent {
+    /// The component as listed in `mmc-pack.json`.
+    pub component: MmcComponent,
+    /// Custom patch overriding the component's metadata, if present.
+    pub patch: Option<PathBuf>,
+}
+
+/// Result of [`crate::launcher::Launcher::import_multimc`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiMcImport {
+    /// The new instance.
+    pub instance: Instance,
+    /// Components of the source instance that were not imported.
+    pub unmapped_components: Vec<UnmappedComponent>,
+}
+
+/// Reads a MultiMC or Prism Launcher instance directory.
+///
+/// Known components are mapped to the Minecraft version and loader, and the
+/// components loader installers provide themselves, such as LWJGL and
+/// intermediary mappings, are accepted silently. Every other component is
+/// listed in [`MultiMcInstance::unmapped_components`], as is every component
+/// with a custom patch in `patches/`, since the stock metadata is installed in
+/// its place. Instances without `mmc-pack.json` fall back to the legacy
+/// `IntendedVersion` setting.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the files cannot be read or parsed, no
+/// Minecraft version is named, or more than one loader is listed.
+pub fn read_multimc_instance(path: impl AsRef<Path>) -> Result<MultiMcInstance> {
+    let path = path.as_ref();
+    let cfg = parse_instance_cfg(&fs::read_to_string(path.join(INSTANCE_CFG))?);
+    let setting = |key: &str| cfg.get(key).map(String::as_str).filter(|v| !v.is_empty());
+    let enabled = |key: &str| setting(key) == Some("true");
+
+    let (mut config, unmapped_components) = match fs::read(path.join(MMC_PACK)) {
+        Ok(bytes) => map_components(path, &serde_json::from_slice(&bytes)?)?,
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            let version =
+                setting("IntendedVersion").ok_or_else(|| LauncherError::MissingField {
+                    context: path.join(INSTANCE_CFG).display().to_string(),
+                    field: "IntendedVersion".to_string(),
+                })?;
+            (InstanceConfig::vanilla(version), Vec::new())
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    if enabled("OverrideJavaLocation") || enabled("OverrideJava") {
+        config.java_executable = setting("JavaPath").map(PathBuf::from);
+    }
+    if enabled("OverrideJavaArgs") || enabled("OverrideJava") {
+        config.jvm_args = setting("JvmArgs").map(split_args).unwrap_or_default();
+    }
+    if enabled("OverrideMemory") {
+        config.min_memory_mb = setting("MinMemAlloc").and_then(|v| v.parse().ok());
+        config.max_memory_mb = setting("MaxMemAlloc").and_then(|v| v.parse().ok());
+    }
+    if enabled("OverrideWindow") {
+        let width = setting("MinecraftWinWidth").and_then(|v| v.parse().ok());
+        let height = setting("MinecraftWinHeight").and_then(|v| v.parse().ok());
+        if let (Some(width), Some(height)) = (width, height) {
+            config.resolution = Some(Resolution { width, height });
+        }
+    }
+    config.icon = setting("iconKey")
+        .filter(|key| *key != "default")
+        .map(str::to_string);
+
+    Ok(MultiMcInstance {
+        name: setting("name").map(str::to_string).unwrap_or_else(|| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        }),
+        config,
+        game_directory: GAME_DIRECTORIES
+            .iter()
+            .map(|name| path.join(name))
+            .find(|dir| dir.is_dir()),
+        unmapped_components,
+    })
+}
+
+fn map_components(path: &Path, pack: &MmcPack) -> Result<(InstanceConfig, Vec<UnmappedComponent>)> {
+    let mut minecraft = None;
+    let mut loader: Option<(LoaderKind, String)> = None;
+    let mut unmapped = Vec::new();
+    for component in &pack.components {
+        let version = component
+            .version
+            .clone()
+            .or_else(|| component.cached_version.clone());
+        let patch = path.join("patches").join(format!("{}.json", component.uid));
+        let patch = patch.is_file().then_some(patch);
+        let kind = LOADER_UIDS
+            .iter()
+            .find(|(uid, _)| *uid == component.uid)
+            .map(|(_, kind)| *kind);
+        let mapped = match (component.uid.as_str(), kind, version) {
+            (MINECRAFT_UID, _, Some(version)) => {
+                minecraft = Some(version);
+                true
+            }
+            (_, Some(kind), Some(version)) => {
+                if loader.is_some() {
+                    return Err(LauncherError::Other {
+                        message: format!("{MMC_PACK} lists more than one loader"),
+                    });
+                }
+                loader = Some((kind, version));
+                true
+            }
+            (uid, None, _) => IMPLIED_UIDS.contains(&uid),
+            _ => false,
+        };
+        if mapped && patch.is_none() {
+            continue;
+        }
+        unmapped.push(UnmappedComponent {
+            component: component.clone(),
+            patch,
+        });
+    }
+
+    let minecraft = minecraft.ok_or_else(|| LauncherError::MissingField {
+        context: path.join(MMC_PACK).display().to_string(),
+        field: MINECRAFT_UID.to_string(),
+    })?;
+    let loader = loader.map(|(kind, version)| InstanceLoader::new(kind, &minecraft, &version));
+    Ok((InstanceConfig::with_loader(minecraft, loader), unmapped))
+}
+
+/// Parses the `key=value` lines of an `instance.cfg`, ignoring sections and
+/// comments and unquoting quoted values.
+fn parse_instance_cfg(text: &str) -> BTreeMap<String, String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with(['[', '#', ';']))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim();
+            let value = match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+                Some(quoted) => quoted.replace("\\\"", "\"").replace("\\\\", "\\"),
+                None => value.to_string(),
+            };
+            (key.trim().to_string(), value)
+        })
+        .collect()
+}
+
+/// Splits a JVM argument string on whitespace, keeping quoted runs together.
+fn split_args(args: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut in_arg = false;
+    for c in args.chars() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    result.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        result.push(current);
+    }
+    result
+}
@@ -0,0 +1,505 @@
+//! Typed client for the Modrinth v2 API.
+//!
+//! [`ModrinthClient`] searches projects, lists project versions, and looks up
+//! versions by file hash. Version files convert into [`DownloadTask`]s that
+//! carry the published size and digest, so they can be passed straight to
+//! [`crate::net::download::execute_plan`]. The base URL is configurable for
+//! self-hosted mirrors and tests.
+
+use std::{collections::HashMap, path::Path};
+
+use reqwest::{blocking::Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    io::paths::safe_join,
+    net::{
+        download::{Checksum, DownloadTask},
+        http,
+    },
+    LauncherError, Result,
+};
+
+/// Base URL of the official Modrinth v2 API.
+pub const MODRINTH_API: &str = "https://api.modrinth.com/v2";
+
+/// Project types accepted by the `project_type` search facet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModrinthProjectType {
+    /// Mods loaded by a mod loader.
+    Mod,
+    /// Modpacks distributed as `.mrpack`.
+    Modpack,
+    /// Resource packs.
+    ResourcePack,
+    /// Shader packs.
+    Shader,
+    /// Data packs.
+    Datapack,
+    /// Server plugins.
+    Plugin,
+}
+
+impl ModrinthProjectType {
+    /// Returns the name Modrinth uses for the type.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Mod => "mod",
+            Self::Modpack => "modpack",
+            Self::ResourcePack => "resourcepack",
+            Self::Shader => "shader",
+            Self::Datapack => "datapack",
+            Self::Plugin => "plugin",
+        }
+    }
+}
+
+/// Hash algorithms accepted by the version file endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModrinthHashAlgorithm {
+    /// SHA-1, as listed in `hashes.sha1`.
+    Sha1,
+    /// SHA-512, as listed in `hashes.sha512`.
+    Sha512,
+}
+
+impl ModrinthHashAlgorithm {
+    /// Returns the name Modrinth uses for the algorithm.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sha1 => "sha1",
+            Self::Sha512 => "sha512",
+        }
+    }
+}
+
+/// Project search parameters.
+///
+/// Each non-empty filter becomes one facet group: values inside a group are
+/// alternatives, and groups must all match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModrinthSearchQuery {
+    /// Free-text query; empty lists every project matching the facets.
+    pub query: String,
+    /// Loaders such as `fabric` or `neoforge`.
+    pub loaders: Vec<String>,
+    /// Minecraft versions such as `1.20.1`.
+    pub game_versions: Vec<String>,
+    /// Project type.
+    pub project_type: Option<ModrinthProjectType>,
+    /// Sort order such as `relevance`, `downloads`, or `updated`; Modrinth
+    /// sorts by relevance when unset.
+    pub index: Option<String>,
+    /// Number of results to skip.
+    pub offset: u32,
+    /// Maximum number of results; Modrinth returns 10 when unset.
+    pub limit: Option<u32>,
+}
+
+/// One page of search results.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ModrinthSearchResults {
+    /// Matching projects.
+    pub hits: Vec<ModrinthSearchHit>,
+    /// Number of results skipped.
+    pub offset: u32,
+    /// Page size.
+    pub limit: u32,
+    /// Total number of matching projects.
+    pub total_hits: u32,
+}
+
+/// Project summary returned by search.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ModrinthSearchHit {
+    /// Project id.
+    pub project_id: String,
+    /// Project type, such as `mod`.
+    pub project_type: String,
+    /// URL slug.
+    pub slug: String,
+    /// Display name.
+    pub title: String,
+    /// Short description.
+    #[serde(default)]
+    pub description: String,
+    /// Author username.
+    #[serde(default)]
+    pub author: String,
+    /// Categories and loaders.
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Supported Minecraft versions.
+    #[serde(default)]
+    pub versions: Vec<String>,
+    /// Total download count.
+    #[serde(default)]
+    pub downloads: u64,
+    /// Icon URL.
+    #[serde(default)]
+    pub icon_url: Option<String>,
+    /// Id of the newest version.
+    #[serde(default)]
+    pub latest_version: Option<String>,
+}
+
+/// Version list filters for [`ModrinthClient::project_versions`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModrinthVersionFilter {
+    /// Loaders the version must support; any loader when empty.
+    pub loaders: Vec<String>,
+    /// Minecraft versions the version must support; any version when empty.
+    pub game_versions: Vec<String>,
+}
+
+/// Release channel of a version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModrinthVersionType {
+    /// Stable release.
+    Release,
+    /// Beta.
+    Beta,
+    /// Alpha.
+    Alpha,
+}
+
+/// One published version of a project.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ModrinthVersion {
+    /// Version id.
+    pub id: String,
+    /// Id of the project the version belongs to.
+    pub project_id: String,
+    /// Display name.
+    pub name: String,
+    /// Version number, such as `0.5.3`.
+    pub version_number: String,
+    /// Release channel.
+    pub version_type: ModrinthVersionType,
+    /// Supported loaders.
+    #[serde(default)]
+    pub loaders: Vec<String>,
+    /// Supported Minecraft versions.
+    #[serde(default)]
+    pub game_versions: Vec<String>,
+    /// Publication time in RFC 3339 format.
+    #[serde(default)]
+    pub date_published: String,
+    /// Dependencies on other projects or versions.
+    #[serde(default)]
+    pub dependencies: Vec<ModrinthDependency>,
+    /// Files of the version.
+    #[serde(default)]
+    pub files: Vec<ModrinthVersionFile>,
+}
+
+impl ModrinthVersion {
+    /// Returns the file marked primary, or the first file when none is.
+    pub fn primary_file(&self) -> Option<&ModrinthVersionFile> {
+        self.files
+            .iter()
+            .find(|file| file.primary)
+            .or_else(|| self.files.first())
+    }
+}
+
+/// Kind of relation a version has to a dependency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModrinthDependencyType {
+    /// Must be installed.
+    Required,
+    /// Supported but not needed.
+    Optional,
+    /// Must not be installed alongside.
+    Incompatible,
+    /// Bundled inside the version's file.
+    Embedded,
+}
+
+/// Dependency of a version.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ModrinthDependency {
+    /// Specific version depended on.
+    #[serde(default)]
+    pub version_id: Option<String>,
+    /// Project depended on.
+    #[serde(default)]
+    pub project_id: Option<String>,
+    /// File name of a dependency not hosted on Modrinth.
+    #[serde(default)]
+    pub file_name: Option<String>,
+    /// Kind of relation.
+    pub dependency_type: ModrinthDependencyType,
+}
+
+/// Downloadable file of a version.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ModrinthVersionFile {
+    /// Published digests.
+    pub hashes: ModrinthFileHashes,
+    /// Download URL.
+    pub url: String,
+    /// File name.
+    pub filename: String,
+    /// Whether this is the version's main file.
+    #[serde(default)]
+    pub primary: bool,
+    /// Size in bytes.
+    pub size: u64,
+}
+
+impl ModrinthVersionFile {
+    /// Returns a task downloading the file into `directory` under its
+    /// published name, checked against its size and SHA-512 digest, or SHA-1
+    /// when no SHA-512 digest is published.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the file name escapes `directory`.
+    pub fn download_task(&self, directory: impl AsRef<Path>) -> Result<DownloadTask> {
+        let checksum = if self.hashes.sha512.is_empty() {
+            Checksum::Sha1(self.hashes.sha1.clone())
+        } else {
+            Checksum::Sha512(self.hashes.sha512.clone())
+        };
+        Ok(DownloadTask {
+            url: self.url.clone(),
+            destination: safe_join(directory.as_ref(), &self.filename)?,
+            checksum: Some(checksum),
+            size: Some(self.size),
+            label: self.filename.clone(),
+        })
+    }
+}
+
+/// Digests of a version file as lowercase hexadecimal.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ModrinthFileHashes {
+    /// SHA-1 digest.
+    #[serde(default)]
+    pub sha1: String,
+    /// SHA-512 digest.
+    #[serde(default)]
+    pub sha512: String,
+}
+
+/// Blocking Modrinth v2 API client.
+#[derive(Debug, Clone)]
+pub struct ModrinthClient {
+    base_url: String,
+    client: Client,
+}
+
+impl ModrinthClient {
+    /// Creates a client for the official API at [`MODRINTH_API`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the HTTP client cannot be
+    /// constructed.
+    pub fn new() -> Result<Self> {
+        Self::with_base_url(MODRINTH_API)
+    }
+
+    /// Creates a client for an API rooted at `base_url`, such as
+    /// `https://api.modrinth.com/v2`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the HTTP client cannot be
+    /// constructed.
+    pub fn with_base_url(base_url: impl Into<String>) -> Result<Self> {
+        Ok(Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            client: http::client()?,
+        })
+    }
+
+    /// Returns the API base URL.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Searches projects.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the request fails, returns an error
+    /// status, or the response cannot be decoded.
+    pub fn search(&self, query: &ModrinthSearchQuery) -> Result<ModrinthSearchResults> {
+        let mut facets = Vec::new();
+        let mut facet = |key: &str, values: &[String]| {
+            if !values.is_empty() {
+                facets.push(
+                    values
+                        .iter()
+                        .map(|value| format!("{key}:{value}"))
+                        .collect::<Vec<_>>(),
+                );
+            }
+        };
+        facet("categories", &query.loaders);
+        facet("versions", &query.game_versions);
+        if let Some(project_type) = query.project_type {
+            facet("project_type", &[project_type.as_str().to_string()]);
+        }
+
+        let mut url = self.url("/search")?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            if !query.query.is_empty() {
+                pairs.append_pair("query", &query.query);
+            }
+            if !facets.is_empty() {
+                pairs.append_pair("facets", &serde_json::to_string(&facets)?);
+            }
+            if let Some(index) = &query.index {
+                pairs.append_pair("index", index);
+            }
+            if query.offset > 0 {
+                pairs.append_pair("offset", &query.offset.to_string());
+            }
+            if let Some(limit) = query.limit {
+                pairs.append_pair("limit", &limit.to_string());
+            }
+        }
+        Ok(self.client.get(url).send()?.error_for_status()?.json()?)
+    }
+
+    /// Lists the versions of a project, newest first.
+    ///
+    /// `project` is a project id or slug.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the request fails, returns an error
+    /// status, or the response cannot be decoded.
+    pub fn project_versions(
+        &self,
+        project: &str,
+        filter: &ModrinthVersionFilter,
+    ) -> Result<Vec<ModrinthVersion>> {
+        let mut url = self.segments_url(&["project", project, "version"])?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            if !filter.loaders.is_empty() {
+                pairs.append_pair("loaders", &serde_json::to_string(&filter.loaders)?);
+            }
+            if !filter.game_versions.is_empty() {
+                pairs.append_pair(
+                    "game_versions",
+                    &serde_json::to_string(&filter.game_versions)?,
+                );
+            }
+        }
+        Ok(self.client.get(url).send()?.error_for_status()?.json()?)
+    }
+
+    /// Returns the version that published a file with the given digest, or
+    /// `None` if Modrinth does not know the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the request fails, returns an error
+    /// status other than 404, or the response cannot be decoded.
+    pub fn version_from_hash(
+        &self,
+        hash: &str,
+        algorithm: ModrinthHashAlgorithm,
+    ) -> Result<Option<ModrinthVersion>> {
+        let mut url = self.segments_url(&["version_file", hash])?;
+        url.query_pairs_mut()
+            .append_pair("algorithm", algorithm.as_str());
+        let response = self.client.get(url).send()?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(response.error_for_status()?.json()?))
+    }
+
+    /// Looks up several files at once, returning the versions keyed by the
+    /// digests Modrinth knows.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the request fails, returns an error
+    /// status, or the response cannot be decoded.
+    pub fn versions_from_hashes(
+        &self,
+        hashes: &[String],
+        algorithm: ModrinthHashAlgorithm,
+    ) -> Result<HashMap<String, ModrinthVersion>> {
+        if hashes.is_empty() {
+            return Ok(HashMap::new());
+        }
+        Ok(self
+            .client
+            .post(self.url("/version_files")?)
+            .json(&serde_json::json!({
+                "hashes": hashes,
+                "algorithm": algorithm.as_str(),
+            }))
+            .send()?
+            .error_for_status()?
+            .json()?)
+    }
+
+    /// Finds the newest version matching `filter` of each project that
+    /// published a file with one of the given digests, keyed by those digests.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the request fails, returns an error
+    /// status, or the response cannot be decoded.
+    pub fn latest_versions_from_hashes(
+        &self,
+        hashes: &[String],
+        algorithm: ModrinthHashAlgorithm,
+        filter: &ModrinthVersionFilter,
+    ) -> Result<HashMap<String, ModrinthVersion>> {
+        if hashes.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let mut body = serde_json::json!({
+            "hashes": hashes,
+            "algorithm": algorithm.as_str(),
+        });
+        if !filter.loaders.is_empty() {
+            body["loaders"] = serde_json::to_value(&filter.loaders)?;
+        }
+        if !filter.game_versions.is_empty() {
+            body["game_versions"] = serde_json::to_value(&filter.game_versions)?;
+        }
+        Ok(self
+            .client
+            .post(self.url("/version_files/update")?)
+            .json(&body)
+            .send()?
+            .error_for_status()?
+            .json()?)
+    }
+
+    fn url(&self, path: &str) -> Result<Url> {
+        let url = format!("{}{path}", self.base_url);
+        Url::parse(&url).map_err(|err| LauncherError::Other {
+            message: format!("invalid Modrinth API URL {url}: {err}"),
+        })
+    }
+
+    /// Builds an API URL from path segments, escaping each one.
+    fn segments_url(&self, segments: &[&str]) -> Result<Url> {
+        let mut url = self.url("")?;
+        url.path_segments_mut()
+            .map_err(|()| LauncherError::Other {
+                message: format!("invalid Modrinth API URL {}", self.base_url),
+            })?
+            .pop_if_empty()
+            .extend(segments);
+        Ok(url)
+    }
+}
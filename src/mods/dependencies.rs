@@ -0,0 +1,538 @@
+//! Pre-launch checks of installed mods.
+//!
+//! [`check_mods`] compares the mods found by
+//! [`super::metadata::scan_mods_directory`] with each other and with the
+//! Minecraft and loader versions of the profile they will run on, and reports
+//! every problem the loader would otherwise only find after the game has
+//! started booting: missing or mismatched dependencies, mods that break each
+//! other, duplicates, and mods built for a different loader.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    core::version::{ArgumentValue, VersionJson},
+    loader::LoaderKind,
+    LauncherError, Result,
+};
+
+use super::{
+    metadata::{ModDependency, ModFile, ModMetadata},
+    versions::{fabric_predicate_matches, maven_range_matches},
+};
+
+/// Minecraft and loader versions mods are checked against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModEnvironment {
+    /// Minecraft version, such as `1.20.1`.
+    pub minecraft_version: String,
+    /// Loader of the profile; `None` for vanilla.
+    pub loader: Option<LoaderKind>,
+    /// Loader version, such as `0.15.11` or `47.2.0`, when known.
+    pub loader_version: Option<String>,
+}
+
+impl ModEnvironment {
+    /// Reads the environment from merged version metadata.
+    ///
+    /// The Minecraft version is the profile's parent, or its own id when it
+    /// does not inherit from one. The loader and its version are detected from
+    /// the loader libraries and the FML launch arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the profile has no id.
+    pub fn from_version(version: &VersionJson) -> Result<Self> {
+        let minecraft_version = version
+            .inherits_from
+            .clone()
+            .or_else(|| version.id.clone())
+            .ok_or_else(|| LauncherError::MissingField {
+                context: "version JSON".to_string(),
+                field: "id".to_string(),
+            })?;
+        let (loader, loader_version) = match detect_loader(version, &minecraft_version) {
+            Some((loader, loader_version)) => (Some(loader), loader_version),
+            None => (None, None),
+        };
+        Ok(Self {
+            minecraft_version,
+            loader,
+            loader_version,
+        })
+    }
+
+    /// Returns whether mods with metadata for `loader` load in this
+    /// environment. Quilt loads Fabric mods and NeoForge loads Forge-format
+    /// mods; vanilla loads none.
+    pub fn accepts(&self, loader: LoaderKind) -> bool {
+        matches!(
+            (self.loader, loader),
+            (Some(LoaderKind::Fabric), LoaderKind::Fabric)
+                | (
+                    Some(LoaderKind::Quilt),
+                    LoaderKind::Quilt | LoaderKind::Fabric
+                )
+                | (Some(LoaderKind::Forge), LoaderKind::Forge)
+                | (
+                    Some(LoaderKind::NeoForge),
+                    LoaderKind::NeoForge | LoaderKind::Forge
+                )
+        )
+    }
+
+    /// Ids the game and loader provide themselves, with their versions when
+    /// known.
+    fn provided(&self) -> Vec<(&'static str, Option<String>)> {
+        let mut provided = vec![
+            ("minecraft", Some(self.minecraft_version.clone())),
+            ("java", None),
+        ];
+        let loader_version = self.loader_version.clone();
+        match self.loader {
+            Some(LoaderKind::Fabric) => provided.push(("fabricloader", loader_version)),
+            Some(LoaderKind::Quilt) => {
+                provided.push(("quilt_loader", loader_version));
+                provided.push(("fabricloader", None));
+            }
+            Some(LoaderKind::Forge) => {
+                provided.push(("forge", loader_version));
+                provided.extend([("Forge", None), ("FML", None), ("mcp", None)]);
+            }
+            Some(LoaderKind::NeoForge) => {
+                provided.push(("neoforge", loader_version));
+                provided.push(("forge", None));
+            }
+            None => {}
+        }
+        provided
+    }
+}
+
+/// How serious a [`ModDiagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DiagnosticSeverity {
+    /// The game may start, but the mods may misbehave.
+    Warning,
+    /// The loader will refuse to start, or the mod will not load.
+    Error,
+}
+
+impl fmt::Display for DiagnosticSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// A problem found by [`check_mods`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModProblem {
+    /// A required mod is not installed.
+    MissingDependency {
+        /// The requirement.
+        dependency: ModDependency,
+    },
+    /// A required mod is installed, but not in an accepted version.
+    DependencyVersionMismatch {
+        /// The requirement.
+        dependency: ModDependency,
+        /// Installed versions of the required mod.
+        found: Vec<String>,
+    },
+    /// An installed mod is one this mod declares it breaks with.
+    Breaks {
+        /// The declared relation.
+        dependency: ModDependency,
+        /// Installed version of the other mod, when known.
+        found: Option<String>,
+    },
+    /// An installed mod is one this mod declares it conflicts with.
+    Conflicts {
+        /// The declared relation.
+        dependency: ModDependency,
+        /// Installed version of the other mod, when known.
+        found: Option<String>,
+    },
+    /// The file has no metadata for the profile's loader.
+    WrongLoader {
+        /// Loader of the profile; `None` for vanilla.
+        expected: Option<LoaderKind>,
+        /// Loaders the file has metadata for.
+        found: Vec<LoaderKind>,
+    },
+    /// Another file declares the same mod id.
+    Duplicate {
+        /// The file that declared the id first.
+        other: PathBuf,
+    },
+    /// The file could not be read.
+    Unreadable {
+        /// Why the file could not be read.
+        error: String,
+    },
+}
+
+/// One finding of [`check_mods`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModDiagnostic {
+    /// Whether the problem blocks the mod from loading.
+    pub severity: DiagnosticSeverity,
+    /// The file the problem belongs to.
+    pub file: PathBuf,
+    /// The mod the problem belongs to, when the file could be read.
+    pub mod_id: Option<String>,
+    /// What is wrong.
+    pub problem: ModProblem,
+}
+
+impl ModDiagnostic {
+    /// Returns whether this diagnostic is an error.
+    pub fn is_error(&self) -> bool {
+        self.severity == DiagnosticSeverity::Error
+    }
+}
+
+impl fmt::Display for ModDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.severity)?;
+        match &self.mod_id {
+            Some(id) => write!(f, "{id} ({})", self.file.display())?,
+            None => write!(f, "{}", self.file.display())?,
+        }
+        match &self.problem {
+            ModProblem::MissingDependency { dependency } => {
+                write!(
+                    f,
+                    " requires {}, which is not installed",
+                    relation(dependency)
+                )
+            }
+            ModProblem::DependencyVersionMismatch { dependency, found } => write!(
+                f,
+                " requires {}, but {} is installed",
+                relation(dependency),
+                found.join(", ")
+            ),
+            ModProblem::Breaks { dependency, found } => {
+                write!(f, " breaks with {}", relation(dependency))?;
+                installed(f, found.as_deref())
+            }
+            ModProblem::Conflicts { dependency, found } => {
+                write!(f, " conflicts with {}", relation(dependency))?;
+                installed(f, found.as_deref())
+            }
+            ModProblem::WrongLoader { expected, found } => {
+                let found = found.iter().map(loader_name).collect::<Vec<_>>();
+                write!(
+                    f,
+                    " is built for {}, not {}",
+                    found.join(", "),
+                    expected.as_ref().map_or("vanilla", loader_name)
+                )
+            }
+            ModProblem::Duplicate { other } => {
+                write!(f, " is also installed by {}", other.display())
+            }
+            ModProblem::Unreadable { error } => write!(f, " could not be read: {error}"),
+        }
+    }
+}
+
+fn relation(dependency: &ModDependency) -> String {
+    if dependency.versions.is_empty() {
+        dependency.id.clone()
+    } else {
+        format!("{} {}", dependency.id, dependency.versions.join(" || "))
+    }
+}
+
+fn installed(f: &mut fmt::Formatter<'_>, found: Option<&str>) -> fmt::Result {
+    match found {
+        Some(version) => write!(f, ", and {version} is installed"),
+        None => f.write_str(", which is installed"),
+    }
+}
+
+fn loader_name(loader: &LoaderKind) -> &'static str {
+    match loader {
+        LoaderKind::Fabric => "Fabric",
+        LoaderKind::Quilt => "Quilt",
+        LoaderKind::Forge => "Forge",
+        LoaderKind::NeoForge => "NeoForge",
+    }
+}
+
+/// Checks installed mods against each other and against `environment`.
+///
+/// Disabled files and files without mod metadata are ignored. For every other
+/// file this reports, in file order:
+///
+/// - unreadable files, as warnings;
+/// - files without metadata for the profile's loader, as errors;
+/// - mod ids already declared by an earlier file, as errors;
+/// - `depends` entries that are not installed or not in an accepted version,
+///   and `breaks` entries that are installed, as errors;
+/// - `conflicts` entries that are installed, as warnings.
+///
+/// Nested jar-in-jar mods, ids listed in `provides`, and the ids of Minecraft,
+/// Java, and the loader satisfy dependencies. Requirements whose syntax cannot
+/// be interpreted, or that name a version that is not known, are assumed to
+/// hold.
+pub fn check_mods(files: &[ModFile], environment: &ModEnvironment) -> Vec<ModDiagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut active: Vec<(&Path, Vec<&ModMetadata>)> = Vec::new();
+    for file in files.iter().filter(|file| file.enabled) {
+        if let Some(error) = &file.error {
+            diagnostics.push(ModDiagnostic {
+                severity: DiagnosticSeverity::Warning,
+                file: file.path.clone(),
+                mod_id: None,
+                problem: ModProblem::Unreadable {
+                    error: error.clone(),
+                },
+            });
+            continue;
+        }
+        let Some(first) = file.mods.first() else {
+            continue;
+        };
+        let native = file
+            .mods
+            .iter()
+            .filter(|metadata| Some(metadata.loader) == environment.loader)
+            .collect::<Vec<_>>();
+        let mods = if native.is_empty() {
+            file.mods
+                .iter()
+                .filter(|metadata| environment.accepts(metadata.loader))
+                .collect()
+        } else {
+            native
+        };
+        if mods.is_empty() {
+            let mut found = Vec::new();
+            for metadata in &file.mods {
+                if !found.contains(&metadata.loader) {
+                    found.push(metadata.loader);
+                }
+            }
+            diagnostics.push(ModDiagnostic {
+                severity: DiagnosticSeverity::Error,
+                file: file.path.clone(),
+                mod_id: Some(first.id.clone()),
+                problem: ModProblem::WrongLoader {
+                    expected: environment.loader,
+                    found,
+                },
+            });
+            continue;
+        }
+        active.push((&file.path, mods));
+    }
+
+    let mut installed: BTreeMap<&str, Vec<(Option<&str>, &Path)>> = BTreeMap::new();
+    let mut declared: BTreeMap<&str, &Path> = BTreeMap::new();
+    for (path, mods) in &active {
+        for metadata in mods {
+            if let Some(other) = declared.get(metadata.id.as_str()) {
+                if other != path {
+                    diagnostics.push(ModDiagnostic {
+                        severity: DiagnosticSeverity::Error,
+                        file: path.to_path_buf(),
+                        mod_id: Some(metadata.id.clone()),
+                        problem: ModProblem::Duplicate {
+                            other: other.to_path_buf(),
+                        },
+                    });
+                }
+            } else {
+                declared.insert(&metadata.id, path);
+            }
+            register(&mut installed, metadata, path);
+        }
+    }
+    let provided = environment.provided();
+    for (id, version) in &provided {
+        installed
+            .entry(id)
+            .or_default()
+            .push((version.as_deref(), Path::new("")));
+    }
+
+    for (path, mods) in &active {
+        for metadata in mods {
+            let mut report = |severity, problem| {
+                diagnostics.push(ModDiagnostic {
+                    severity,
+                    file: path.to_path_buf(),
+                    mod_id: Some(metadata.id.clone()),
+                    problem,
+                });
+            };
+            let others = |id: &str| {
+                installed
+                    .get(id)
+                    .into_iter()
+                    .flatten()
+                    .filter(|(_, other)| other != path)
+                    .map(|(version, _)| *version)
+                    .collect::<Vec<_>>()
+            };
+            for dependency in &metadata.depends {
+                let found = others(&dependency.id);
+                if found.is_empty() {
+                    report(
+                        DiagnosticSeverity::Error,
+                        ModProblem::MissingDependency {
+                            dependency: dependency.clone(),
+                        },
+                    );
+                } else if !found
+                    .iter()
+                    .any(|version| satisfies(metadata.loader, *version, dependency))
+                {
+                    report(
+                        DiagnosticSeverity::Error,
+                        ModProblem::DependencyVersionMismatch {
+                            dependency: dependency.clone(),
+                            found: found.iter().flatten().map(|v| v.to_string()).collect(),
+                        },
+                    );
+                }
+            }
+            for (dependency, severity) in metadata
+                .breaks
+                .iter()
+                .map(|dependency| (dependency, DiagnosticSeverity::Error))
+                .chain(
+                    metadata
+                        .conflicts
+                        .iter()
+                        .map(|dependency| (dependency, DiagnosticSeverity::Warning)),
+                )
+            {
+                let Some(version) = others(&dependency.id)
+                    .into_iter()
+                    .find(|version| satisfies(metadata.loader, *version, dependency))
+                else {
+                    continue;
+                };
+                let dependency = dependency.clone();
+                let found = version.map(str::to_string);
+                report(
+                    severity,
+                    match severity {
+                        DiagnosticSeverity::Error => ModProblem::Breaks { dependency, found },
+                        DiagnosticSeverity::Warning => ModProblem::Conflicts { dependency, found },
+                    },
+                );
+            }
+        }
+    }
+    diagnostics
+}
+
+/// Records a mod, the ids it provides, and its nested mods as installed.
+fn register<'a>(
+    installed: &mut BTreeMap<&'a str, Vec<(Option<&'a str>, &'a Path)>>,
+    metadata: &'a ModMetadata,
+    path: &'a Path,
+) {
+    let version = Some(metadata.version.as_str());
+    installed
+        .entry(&metadata.id)
+        .or_default()
+        .push((version, path));
+    for id in &metadata.provides {
+        installed.entry(id).or_default().push((version, path));
+    }
+    for nested in &metadata.nested {
+        register(installed, nested, path);
+    }
+}
+
+/// Returns whether an installed version satisfies a relation, reading the
+/// version requirements in the syntax of the declaring mod's loader.
+fn satisfies(loader: LoaderKind, version: Option<&str>, dependency: &ModDependency) -> bool {
+    let Some(version) = version else {
+        return true;
+    };
+    dependency.versions.is_empty()
+        || dependency.versions.iter().any(|requirement| {
+            match loader {
+                LoaderKind::Fabric | LoaderKind::Quilt => {
+                    fabric_predicate_matches(version, requirement)
+                }
+                LoaderKind::Forge | LoaderKind::NeoForge => {
+                    maven_range_matches(version, requirement)
+                }
+            }
+            .unwrap_or(true)
+        })
+}
+
+/// Detects the loader of a profile from its libraries and FML arguments.
+fn detect_loader(
+    version: &VersionJson,
+    minecraft_version: &str,
+) -> Option<(LoaderKind, Option<String>)> {
+    let strip_minecraft = |loader_version: &str| {
+        let loader_version = loader_version
+            .strip_prefix(&format!("{minecraft_version}-"))
+            .unwrap_or(loader_version);
+        loader_version
+            .strip_suffix(&format!("-{minecraft_version}"))
+            .unwrap_or(loader_version)
+            .to_string()
+    };
+    let mut forge = None;
+    for library in &version.libraries {
+        let mut parts = library.name.split(':');
+        let (Some(group), Some(artifact), Some(library_version)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let found = match (group, artifact) {
+            ("net.fabricmc", "fabric-loader") => (LoaderKind::Fabric, library_version.to_string()),
+            ("org.quiltmc", "quilt-loader") => (LoaderKind::Quilt, library_version.to_string()),
+            ("net.neoforged", "neoforge") => (LoaderKind::NeoForge, library_version.to_string()),
+            ("net.neoforged", "forge") => (LoaderKind::NeoForge, strip_minecraft(library_version)),
+            ("net.minecraftforge", "forge" | "minecraftforge") => {
+                forge = Some(strip_minecraft(library_version));
+                continue;
+            }
+            _ => continue,
+        };
+        return Some((found.0, Some(found.1)));
+    }
+
+    let game_args = version
+        .arguments
+        .game
+        .iter()
+        .filter_map(|argument| match argument {
+            ArgumentValue::String(value) => Some(value.as_str()),
+            ArgumentValue::Ruled { .. } => None,
+        })
+        .collect::<Vec<_>>();
+    let argument = |name: &str| {
+        game_args
+            .windows(2)
+            .find(|pair| pair[0] == name)
+            .map(|pair| pair[1].to_string())
+    };
+    if let Some(neoforge) = argument("--fml.neoForgeVersion") {
+        return Some((LoaderKind::NeoForge, Some(neoforge)));
+    }
+    if let Some(forge_version) = argument("--fml.forgeVersion") {
+        return Some((LoaderKind::Forge, Some(forge_version)));
+    }
+    forge.map(|forge| (LoaderKind::Forge, Some(forge)))
+}
@@ -0,0 +1,639 @@
+//! Mod metadata embedded in mod jars.
+//!
+//! Fabric, Quilt, Forge, and NeoForge each describe a mod in their own file:
+//! `fabric.mod.json`, `quilt.mod.json`, `META-INF/mods.toml`,
+//! `META-INF/neoforge.mods.toml`, or the legacy Forge `mcmod.info`. This
+//! module reads all of them into [`ModMetadata`], including mods bundled as
+//! nested jars through Fabric/Quilt `jars` entries or Forge's
+//! `META-INF/jarjar/metadata.json`.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{Cursor, ErrorKind, Read, Seek},
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+use zip::{result::ZipError, ZipArchive};
+
+use crate::{loader::LoaderKind, Result};
+
+/// Suffix appended to a mod file name to keep the loader from loading it.
+pub const DISABLED_SUFFIX: &str = ".disabled";
+
+/// Maximum depth of jar-in-jar nesting that is followed.
+const MAX_NESTING: usize = 8;
+
+/// Metadata of one mod, read from whichever format its jar provides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModMetadata {
+    /// Mod id, such as `sodium`.
+    pub id: String,
+    /// Display name; the id when the metadata has none.
+    pub name: String,
+    /// Mod version.
+    pub version: String,
+    /// Description.
+    pub description: String,
+    /// Loader the metadata was written for; legacy `mcmod.info` reports
+    /// [`LoaderKind::Forge`].
+    pub loader: LoaderKind,
+    /// Author and contributor names.
+    pub authors: Vec<String>,
+    /// Mods that must be present.
+    pub depends: Vec<ModDependency>,
+    /// Mods that must not be present.
+    pub breaks: Vec<ModDependency>,
+    /// Mods that work badly alongside this one but do not stop it loading.
+    pub conflicts: Vec<ModDependency>,
+    /// Other ids this mod also satisfies dependencies on.
+    pub provides: Vec<String>,
+    /// Icon image bytes, usually PNG.
+    pub icon: Option<Vec<u8>>,
+    /// Mods bundled inside this mod's jar.
+    pub nested: Vec<ModMetadata>,
+}
+
+/// A relation to another mod.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModDependency {
+    /// Id of the other mod.
+    pub id: String,
+    /// Accepted versions in the loader's own syntax: semantic version
+    /// predicates such as `>=0.5.0` for Fabric and Quilt, Maven ranges such as
+    /// `[47,)` for Forge and NeoForge. Any version matches when empty, and the
+    /// relation holds when any entry matches.
+    pub versions: Vec<String>,
+}
+
+/// A file in a mods directory and the mods it declares.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModFile {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Whether the file is loaded, that is, not suffixed with
+    /// [`DISABLED_SUFFIX`].
+    pub enabled: bool,
+    /// Mods declared by the file; empty for plain libraries.
+    pub mods: Vec<ModMetadata>,
+    /// Why the file could not be read, if it could not.
+    pub error: Option<String>,
+}
+
+/// Reads the metadata of every mod declared by a jar.
+///
+/// A jar carrying metadata for several loaders yields one entry per format.
+/// Jars without any metadata yield an empty list.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the jar or one of its metadata files
+/// cannot be read or parsed.
+pub fn read_mod_metadata(path: impl AsRef<Path>) -> Result<Vec<ModMetadata>> {
+    read_archive(&mut ZipArchive::new(File::open(path)?)?, 0)
+}
+
+/// Lists the `.jar` and `.zip` files in a mods directory, enabled or
+/// disabled, with their metadata.
+///
+/// Files that cannot be read are listed with [`ModFile::error`] set instead of
+/// failing the scan. A missing directory yields an empty list.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the directory cannot be listed.
+pub fn scan_mods_directory(directory: impl AsRef<Path>) -> Result<Vec<ModFile>> {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let enabled = !name.ends_with(DISABLED_SUFFIX);
+        let name = name.strip_suffix(DISABLED_SUFFIX).unwrap_or(&name);
+        if !name.ends_with(".jar") && !name.ends_with(".zip") {
+            continue;
+        }
+        let path = entry.path();
+        let (mods, error) = match read_mod_metadata(&path) {
+            Ok(mods) => (mods, None),
+            Err(err) => (Vec::new(), Some(err.to_string())),
+        };
+        files.push(ModFile {
+            path,
+            enabled,
+            mods,
+            error,
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn read_archive<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    depth: usize,
+) -> Result<Vec<ModMetadata>> {
+    let mut mods = Vec::new();
+    if let Some(bytes) = read_entry(archive, "fabric.mod.json")? {
+        let json: FabricModJson = serde_json::from_slice(&bytes)?;
+        let mut metadata = fabric_metadata(archive, json.clone())?;
+        let jars: Vec<String> = json.jars.into_iter().map(|jar| jar.file).collect();
+        metadata.nested = read_nested(archive, &jars, depth)?;
+        mods.push(metadata);
+    }
+    if let Some(bytes) = read_entry(archive, "quilt.mod.json")? {
+        let json: QuiltModJson = serde_json::from_slice(&bytes)?;
+        let jars = json.quilt_loader.jars.clone();
+        let mut metadata = quilt_metadata(archive, json.quilt_loader)?;
+        metadata.nested = read_nested(archive, &jars, depth)?;
+        mods.push(metadata);
+    }
+    for (file, loader) in [
+        ("META-INF/mods.toml", LoaderKind::Forge),
+        ("META-INF/neoforge.mods.toml", LoaderKind::NeoForge),
+    ] {
+        let Some(bytes) = read_entry(archive, file)? else {
+            continue;
+        };
+        let toml: ModsToml = toml::from_str(&String::from_utf8_lossy(&bytes))?;
+        let first = mods.len();
+        mods.extend(mods_toml_metadata(archive, toml, loader)?);
+        if let Some(primary) = mods.get_mut(first) {
+            primary.nested = read_jarjar(archive, depth)?;
+        }
+    }
+    if let Some(bytes) = read_entry(archive, "mcmod.info")? {
+        let info: McmodInfo = serde_json::from_slice(&bytes)?;
+        let entries = match info {
+            McmodInfo::List(entries) => entries,
+            McmodInfo::Versioned { mod_list } => mod_list,
+        };
+        for entry in entries {
+            mods.push(mcmod_metadata(archive, entry)?);
+        }
+    }
+    Ok(mods)
+}
+
+fn read_nested<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    jars: &[String],
+    depth: usize,
+) -> Result<Vec<ModMetadata>> {
+    if depth >= MAX_NESTING {
+        return Ok(Vec::new());
+    }
+    let mut nested = Vec::new();
+    for jar in jars {
+        let Some(bytes) = read_entry(archive, jar)? else {
+            continue;
+        };
+        nested.extend(read_archive(
+            &mut ZipArchive::new(Cursor::new(bytes))?,
+            depth + 1,
+        )?);
+    }
+    Ok(nested)
+}
+
+fn read_jarjar<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    depth: usize,
+) -> Result<Vec<ModMetadata>> {
+    let Some(bytes) = read_entry(archive, "META-INF/jarjar/metadata.json")? else {
+        return Ok(Vec::new());
+    };
+    let jarjar: JarJarMetadata = serde_json::from_slice(&bytes)?;
+    let jars: Vec<String> = jarjar.jars.into_iter().map(|jar| jar.path).collect();
+    read_nested(archive, &jars, depth)
+}
+
+fn read_entry<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Result<Option<Vec<u8>>> {
+    let name = name.trim_start_matches('/');
+    let mut entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(ZipError::FileNotFound) => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes)?;
+    Ok(Some(bytes))
+}
+
+fn read_icon<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    path: Option<&str>,
+) -> Result<Option<Vec<u8>>> {
+    match path.filter(|path| !path.is_empty()) {
+        Some(path) => read_entry(archive, path),
+        None => Ok(None),
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct FabricModJson {
+    id: String,
+    version: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    authors: Vec<FabricPerson>,
+    #[serde(default)]
+    contributors: Vec<FabricPerson>,
+    #[serde(default)]
+    icon: Option<FabricIcon>,
+    #[serde(default)]
+    depends: BTreeMap<String, VersionPredicates>,
+    #[serde(default)]
+    breaks: BTreeMap<String, VersionPredicates>,
+    #[serde(default)]
+    conflicts: BTreeMap<String, VersionPredicates>,
+    #[serde(default)]
+    provides: Vec<String>,
+    #[serde(default)]
+    jars: Vec<FabricJar>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum FabricPerson {
+    Name(String),
+    Detailed { name: String },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum FabricIcon {
+    Path(String),
+    Sizes(BTreeMap<String, String>),
+}
+
+impl FabricIcon {
+    /// Returns the icon path, preferring the largest size.
+    fn path(&self) -> Option<&str> {
+        match self {
+            Self::Path(path) => Some(path),
+            Self::Sizes(sizes) => sizes
+                .iter()
+                .max_by_key(|(size, _)| size.parse::<u32>().unwrap_or(0))
+                .map(|(_, path)| path.as_str()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum VersionPredicates {
+    One(String),
+    Any(Vec<String>),
+}
+
+impl VersionPredicates {
+    fn into_versions(self) -> Vec<String> {
+        let versions = match self {
+            Self::One(version) => vec![version],
+            Self::Any(versions) => versions,
+        };
+        if versions.iter().any(|version| version == "*") {
+            Vec::new()
+        } else {
+            versions
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct FabricJar {
+    file: String,
+}
+
+fn fabric_metadata<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    json: FabricModJson,
+) -> Result<ModMetadata> {
+    let relations = |map: BTreeMap<String, VersionPredicates>| {
+        map.into_iter()
+            .map(|(id, versions)| ModDependency {
+                id,
+                versions: versions.into_versions(),
+            })
+            .collect()
+    };
+    Ok(ModMetadata {
+        name: json.name.unwrap_or_else(|| json.id.clone()),
+        icon: read_icon(archive, json.icon.as_ref().and_then(FabricIcon::path))?,
+        id: json.id,
+        version: json.version,
+        description: json.description.unwrap_or_default(),
+        loader: LoaderKind::Fabric,
+        authors: json
+            .authors
+            .into_iter()
+            .chain(json.contributors)
+            .map(|person| match person {
+                FabricPerson::Name(name) | FabricPerson::Detailed { name } => name,
+            })
+            .collect(),
+        depends: relations(json.depends),
+        breaks: relations(json.breaks),
+        conflicts: relations(json.conflicts),
+        provides: json.provides,
+        nested: Vec::new(),
+    })
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct QuiltModJson {
+    quilt_loader: QuiltLoader,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct QuiltLoader {
+    id: String,
+    version: String,
+    #[serde(default)]
+    metadata: QuiltMetadata,
+    #[serde(default)]
+    depends: Vec<serde_json::Value>,
+    #[serde(default)]
+    breaks: Vec<serde_json::Value>,
+    #[serde(default)]
+    provides: Vec<serde_json::Value>,
+    #[serde(default)]
+    jars: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct QuiltMetadata {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    contributors: BTreeMap<String, serde_json::Value>,
+    #[serde(default)]
+    icon: Option<FabricIcon>,
+}
+
+fn quilt_metadata<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    loader: QuiltLoader,
+) -> Result<ModMetadata> {
+    Ok(ModMetadata {
+        name: loader.metadata.name.unwrap_or_else(|| loader.id.clone()),
+        icon: read_icon(
+            archive,
+            loader.metadata.icon.as_ref().and_then(FabricIcon::path),
+        )?,
+        id: loader.id,
+        version: loader.version,
+        description: loader.metadata.description.unwrap_or_default(),
+        loader: LoaderKind::Quilt,
+        authors: loader.metadata.contributors.into_keys().collect(),
+        depends: loader.depends.iter().filter_map(quilt_relation).collect(),
+        breaks: loader.breaks.iter().filter_map(quilt_relation).collect(),
+        conflicts: Vec::new(),
+        provides: loader
+            .provides
+            .iter()
+            .filter_map(|value| match value {
+                serde_json::Value::String(id) => Some(id.clone()),
+                serde_json::Value::Object(object) => object.get("id")?.as_str().map(str::to_string),
+                _ => None,
+            })
+            .collect(),
+        nested: Vec::new(),
+    })
+}
+
+/// Maps one Quilt `depends` or `breaks` entry. Optional entries and
+/// "any of" arrays do not constrain the mod set on their own and are skipped.
+fn quilt_relation(value: &serde_json::Value) -> Option<ModDependency> {
+    let (id, versions) = match value {
+        serde_json::Value::String(id) => (id.as_str(), Vec::new()),
+        serde_json::Value::Object(object) => {
+            if object.get("optional").and_then(serde_json::Value::as_bool) == Some(true) {
+                return None;
+            }
+            let versions = match object.get("versions") {
+                Some(serde_json::Value::String(version)) => vec![version.clone()],
+                Some(serde_json::Value::Array(versions)) => versions
+                    .iter()
+                    .filter_map(|version| version.as_str().map(str::to_string))
+                    .collect(),
+                _ => Vec::new(),
+            };
+            (object.get("id")?.as_str()?, versions)
+        }
+        _ => return None,
+    };
+    let id = id.rsplit_once(':').map_or(id, |(_, id)| id);
+    Some(ModDependency {
+        id: id.to_string(),
+        versions: VersionPredicates::Any(versions).into_versions(),
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct ModsToml {
+    #[serde(default)]
+    mods: Vec<ModsTomlMod>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, Vec<ModsTomlDependency>>,
+    #[serde(rename = "logoFile", default)]
+    logo_file: Option<String>,
+    #[serde(default)]
+    authors: Option<toml::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModsTomlMod {
+    #[serde(rename = "modId")]
+    mod_id: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(rename = "displayName", default)]
+    display_name: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    authors: Option<toml::Value>,
+    #[serde(rename = "logoFile", default)]
+    logo_file: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModsTomlDependency {
+    #[serde(rename = "modId")]
+    mod_id: String,
+    #[serde(default)]
+    mandatory: Option<bool>,
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    #[serde(rename = "versionRange", default)]
+    version_range: Option<String>,
+}
+
+fn mods_toml_metadata<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    toml: ModsToml,
+    loader: LoaderKind,
+) -> Result<Vec<ModMetadata>> {
+    let jar_version = jar_version(archive)?;
+    let mut dependencies = toml.dependencies;
+    let mut mods = Vec::new();
+    for entry in toml.mods {
+        let mut depends = Vec::new();
+        let mut breaks = Vec::new();
+        let mut conflicts = Vec::new();
+        for dependency in dependencies.remove(&entry.mod_id).unwrap_or_default() {
+            let kind = dependency.kind.map(|kind| kind.to_ascii_lowercase());
+            let target = match (kind.as_deref(), dependency.mandatory) {
+                (Some("required"), _) | (None, Some(true) | None) => &mut depends,
+                (Some("incompatible"), _) => &mut breaks,
+                (Some("discouraged"), _) => &mut conflicts,
+                _ => continue,
+            };
+            target.push(ModDependency {
+                id: dependency.mod_id,
+                versions: dependency
+                    .version_range
+                    .filter(|range| !range.is_empty() && range != "*")
+                    .into_iter()
+                    .collect(),
+            });
+        }
+        let version = match entry.version {
+            Some(version) if version == "${file.jarVersion}" => {
+                jar_version.clone().unwrap_or(version)
+            }
+            Some(version) => version,
+            None => jar_version.clone().unwrap_or_default(),
+        };
+        let logo = entry.logo_file.as_ref().or(toml.logo_file.as_ref());
+        mods.push(ModMetadata {
+            name: entry.display_name.unwrap_or_else(|| entry.mod_id.clone()),
+            icon: read_icon(archive, logo.map(String::as_str))?,
+            id: entry.mod_id,
+            version,
+            description: entry.description.unwrap_or_default().trim().to_string(),
+            loader,
+            authors: toml_authors(entry.authors.as_ref().or(toml.authors.as_ref())),
+            depends,
+            breaks,
+            conflicts,
+            provides: Vec::new(),
+            nested: Vec::new(),
+        });
+    }
+    Ok(mods)
+}
+
+fn toml_authors(value: Option<&toml::Value>) -> Vec<String> {
+    match value {
+        Some(toml::Value::String(authors)) => authors
+            .split(',')
+            .map(str::trim)
+            .filter(|author| !author.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Some(toml::Value::Array(authors)) => authors
+            .iter()
+            .filter_map(|author| author.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns `Implementation-Version` from the jar manifest, which Forge
+/// substitutes for `${file.jarVersion}`.
+fn jar_version<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Result<Option<String>> {
+    let Some(manifest) = read_entry(archive, "META-INF/MANIFEST.MF")? else {
+        return Ok(None);
+    };
+    Ok(String::from_utf8_lossy(&manifest)
+        .lines()
+        .find_map(|line| line.strip_prefix("Implementation-Version:"))
+        .map(|version| version.trim().to_string()))
+}
+
+#[derive(Debug, Deserialize)]
+struct JarJarMetadata {
+    #[serde(default)]
+    jars: Vec<JarJarEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JarJarEntry {
+    path: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum McmodInfo {
+    List(Vec<McmodEntry>),
+    Versioned {
+        #[serde(rename = "modList")]
+        mod_list: Vec<McmodEntry>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct McmodEntry {
+    modid: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(rename = "authorList", alias = "authors", default)]
+    author_list: Vec<String>,
+    #[serde(rename = "logoFile", default)]
+    logo_file: Option<String>,
+    #[serde(rename = "requiredMods", default)]
+    required_mods: Vec<String>,
+}
+
+fn mcmod_metadata<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    entry: McmodEntry,
+) -> Result<ModMetadata> {
+    Ok(ModMetadata {
+        name: entry.name.unwrap_or_else(|| entry.modid.clone()),
+        icon: read_icon(archive, entry.logo_file.as_deref())?,
+        id: entry.modid,
+        version: entry.version.unwrap_or_default(),
+        description: entry.description.unwrap_or_default(),
+        loader: LoaderKind::Forge,
+        authors: entry.author_list,
+        depends: entry
+            .required_mods
+            .iter()
+            .map(|required| match required.split_once('@') {
+                Some((id, range)) => ModDependency {
+                    id: id.to_string(),
+                    versions: vec![range.to_string()],
+                },
+                None => ModDependency {
+                    id: required.clone(),
+                    versions: Vec::new(),
+                },
+            })
+            .collect(),
+        breaks: Vec::new(),
+        conflicts: Vec::new(),
+        provides: Vec::new(),
+        nested: Vec::new(),
+    })
+}
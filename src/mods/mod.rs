@@ -0,0 +1,11 @@
+//! Mods installed in a game directory.
+//!
+//! [`metadata`] reads the mod metadata formats of every supported loader,
+//! [`versions`] matches the version requirements those formats declare, and
+//! [`dependencies`] checks installed mods against each other and the profile
+//! before launch, and [`updates`] finds and applies newer releases.
+
+pub mod dependencies;
+pub mod metadata;
+pub mod updates;
+pub mod versions;
@@ -0,0 +1,396 @@
+//! Update checks and updates for installed mods.
+//!
+//! [`check_mod_updates`] hashes every jar in a mods directory and asks a
+//! [`ModUpdateProvider`] which release each file belongs to and which release
+//! is the newest for the instance's loader and Minecraft version.
+//! [`ModrinthUpdateProvider`] answers from Modrinth; other sources, such as
+//! CurseForge, plug in by implementing the trait or passing a closure.
+//!
+//! [`apply_mod_update`] downloads the new file into a staging directory,
+//! moves the old jar into [`ROLLBACK_DIRECTORY`], and renames the new jar into
+//! place, so the mods directory never holds a partial download and
+//! [`rollback_mod_update`] can restore the previous file.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::DateTime;
+
+use crate::{
+    io::hash::{sha1_file, sha512_file},
+    loader::LoaderKind,
+    modrinth::{
+        ModrinthClient, ModrinthHashAlgorithm, ModrinthVersion, ModrinthVersionFile,
+        ModrinthVersionFilter,
+    },
+    net::download::{
+        execute_plan_with_options, Checksum, DownloadOptions, DownloadPlan, DownloadTask,
+    },
+    progress::ProgressReporter,
+    LauncherError, Result,
+};
+
+use super::{
+    dependencies::ModEnvironment,
+    metadata::{scan_mods_directory, DISABLED_SUFFIX},
+};
+
+/// Directory inside the mods directory that new files are downloaded into
+/// before they replace the old ones.
+pub const STAGING_DIRECTORY: &str = ".updates";
+
+/// Directory inside the mods directory that replaced files are kept in.
+pub const ROLLBACK_DIRECTORY: &str = ".rollback";
+
+/// An installed mod file and its digests as lowercase hexadecimal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashedModFile {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// SHA-1 digest.
+    pub sha1: String,
+    /// SHA-512 digest, computed only for providers that ask for it through
+    /// [`ModUpdateProvider::needs_sha512`].
+    pub sha512: Option<String>,
+}
+
+/// One downloadable release of a mod.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModRelease {
+    /// Project id at the provider.
+    pub project_id: String,
+    /// Version id at the provider.
+    pub version_id: String,
+    /// Version number, such as `0.5.3`.
+    pub version_number: String,
+    /// File name of the release's jar.
+    pub file_name: String,
+    /// Download URL.
+    pub url: String,
+    /// Expected digest of the jar.
+    pub checksum: Option<Checksum>,
+    /// Expected size of the jar in bytes.
+    pub size: Option<u64>,
+    /// Publication time in RFC 3339 format, or empty when unknown.
+    pub date_published: String,
+}
+
+impl ModRelease {
+    /// Describes the primary file of a Modrinth version, or `None` if the
+    /// version has no files.
+    pub fn from_modrinth(version: &ModrinthVersion) -> Option<Self> {
+        version
+            .primary_file()
+            .map(|file| Self::from_modrinth_file(version, file))
+    }
+
+    fn from_modrinth_file(version: &ModrinthVersion, file: &ModrinthVersionFile) -> Self {
+        let checksum = if !file.hashes.sha512.is_empty() {
+            Some(Checksum::Sha512(file.hashes.sha512.clone()))
+        } else if !file.hashes.sha1.is_empty() {
+            Some(Checksum::Sha1(file.hashes.sha1.clone()))
+        } else {
+            None
+        };
+        Self {
+            project_id: version.project_id.clone(),
+            version_id: version.id.clone(),
+            version_number: version.version_number.clone(),
+            file_name: file.filename.clone(),
+            url: file.url.clone(),
+            checksum,
+            size: Some(file.size),
+            date_published: version.date_published.clone(),
+        }
+    }
+
+    /// Returns whether this is a different release published after `other`.
+    ///
+    /// Publication times are compared as instants, so differing offsets and
+    /// fractional seconds order correctly. A missing or unparsable time on
+    /// either release means this one is not considered newer.
+    pub fn is_newer_than(&self, other: &ModRelease) -> bool {
+        let published =
+            |release: &ModRelease| DateTime::parse_from_rfc3339(&release.date_published);
+        match (published(self), published(other)) {
+            (Ok(this), Ok(other_time)) => self.version_id != other.version_id && this > other_time,
+            _ => false,
+        }
+    }
+}
+
+/// What a provider knows about one installed file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModReleaseLookup {
+    /// The release the installed file belongs to.
+    pub current: ModRelease,
+    /// The newest release compatible with the instance.
+    pub latest: ModRelease,
+}
+
+/// Source of mod releases for [`check_mod_updates`].
+///
+/// Closures with the same signature as [`ModUpdateProvider::lookup`]
+/// implement this trait and receive SHA-1 digests only.
+pub trait ModUpdateProvider {
+    /// Returns whether [`ModUpdateProvider::lookup`] reads
+    /// [`HashedModFile::sha512`]. Files are only hashed a second time when it
+    /// does; the default is `false`.
+    fn needs_sha512(&self) -> bool {
+        false
+    }
+
+    /// Looks up installed files, returning the releases of every file the
+    /// provider knows, keyed by [`HashedModFile::path`]. Files the provider
+    /// does not know are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the provider cannot be queried.
+    fn lookup(
+        &mut self,
+        files: &[HashedModFile],
+        environment: &ModEnvironment,
+    ) -> Result<BTreeMap<PathBuf, ModReleaseLookup>>;
+}
+
+impl<F> ModUpdateProvider for F
+where
+    F: FnMut(&[HashedModFile], &ModEnvironment) -> Result<BTreeMap<PathBuf, ModReleaseLookup>>,
+{
+    fn lookup(
+        &mut self,
+        files: &[HashedModFile],
+        environment: &ModEnvironment,
+    ) -> Result<BTreeMap<PathBuf, ModReleaseLookup>> {
+        self(files, environment)
+    }
+}
+
+/// [`ModUpdateProvider`] backed by the Modrinth API.
+///
+/// Files are identified by SHA-1 digest, and the newest release is the newest
+/// version of the same project that supports the instance's Minecraft version
+/// and loader; Quilt instances also accept Fabric versions.
+#[derive(Debug, Clone)]
+pub struct ModrinthUpdateProvider {
+    client: ModrinthClient,
+}
+
+impl ModrinthUpdateProvider {
+    /// Creates a provider that queries `client`.
+    pub fn new(client: ModrinthClient) -> Self {
+        Self { client }
+    }
+}
+
+impl ModUpdateProvider for ModrinthUpdateProvider {
+    fn lookup(
+        &mut self,
+        files: &[HashedModFile],
+        environment: &ModEnvironment,
+    ) -> Result<BTreeMap<PathBuf, ModReleaseLookup>> {
+        let hashes = files
+            .iter()
+            .map(|file| file.sha1.clone())
+            .collect::<Vec<_>>();
+        let current = self
+            .client
+            .versions_from_hashes(&hashes, ModrinthHashAlgorithm::Sha1)?;
+        let filter = ModrinthVersionFilter {
+            loaders: match environment.loader {
+                Some(LoaderKind::Fabric) => vec!["fabric".to_string()],
+                Some(LoaderKind::Quilt) => vec!["quilt".to_string(), "fabric".to_string()],
+                Some(LoaderKind::Forge) => vec!["forge".to_string()],
+                Some(LoaderKind::NeoForge) => vec!["neoforge".to_string()],
+                None => Vec::new(),
+            },
+            game_versions: vec![environment.minecraft_version.clone()],
+        };
+        let latest = self.client.latest_versions_from_hashes(
+            &hashes,
+            ModrinthHashAlgorithm::Sha1,
+            &filter,
+        )?;
+
+        let mut lookups = BTreeMap::new();
+        for file in files {
+            let (Some(current), Some(latest)) = (current.get(&file.sha1), latest.get(&file.sha1))
+            else {
+                continue;
+            };
+            let installed = current
+                .files
+                .iter()
+                .find(|candidate| candidate.hashes.sha1 == file.sha1)
+                .map(|candidate| ModRelease::from_modrinth_file(current, candidate))
+                .or_else(|| ModRelease::from_modrinth(current));
+            if let (Some(current), Some(latest)) = (installed, ModRelease::from_modrinth(latest)) {
+                lookups.insert(file.path.clone(), ModReleaseLookup { current, latest });
+            }
+        }
+        Ok(lookups)
+    }
+}
+
+/// An installed mod with a newer release available.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModUpdate {
+    /// Path of the installed file.
+    pub path: PathBuf,
+    /// The release the installed file belongs to.
+    pub current: ModRelease,
+    /// The release to update to.
+    pub latest: ModRelease,
+}
+
+/// Result of [`apply_mod_update`], needed to undo it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedModUpdate {
+    /// Path the replaced file had.
+    pub previous: PathBuf,
+    /// Path of the new file.
+    pub installed: PathBuf,
+    /// Where the replaced file is kept.
+    pub rollback: PathBuf,
+}
+
+/// Hashes every `.jar` and `.zip` file in a mods directory, enabled or
+/// disabled, and lists those for which `provider` knows a newer release.
+///
+/// Files are hashed with SHA-1, and with SHA-512 as well only when
+/// [`ModUpdateProvider::needs_sha512`] asks for it.
+///
+/// Only releases for which [`ModRelease::is_newer_than`] holds are listed, so
+/// a provider whose newest compatible release predates the installed file
+/// never offers a downgrade.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the directory cannot be listed, a file
+/// cannot be hashed, or the provider fails.
+pub fn check_mod_updates(
+    directory: impl AsRef<Path>,
+    environment: &ModEnvironment,
+    provider: &mut dyn ModUpdateProvider,
+) -> Result<Vec<ModUpdate>> {
+    let needs_sha512 = provider.needs_sha512();
+    let mut files = Vec::new();
+    for file in scan_mods_directory(directory)? {
+        files.push(HashedModFile {
+            sha1: sha1_file(&file.path)?,
+            sha512: needs_sha512.then(|| sha512_file(&file.path)).transpose()?,
+            path: file.path,
+        });
+    }
+    let mut lookups = provider.lookup(&files, environment)?;
+    Ok(files
+        .into_iter()
+        .filter_map(|file| {
+            let lookup = lookups.remove(&file.path)?;
+            lookup
+                .latest
+                .is_newer_than(&lookup.current)
+                .then_some(ModUpdate {
+                    path: file.path,
+                    current: lookup.current,
+                    latest: lookup.latest,
+                })
+        })
+        .collect())
+}
+
+/// Downloads the new release of a mod and swaps it in for the old file.
+///
+/// The download goes to [`STAGING_DIRECTORY`] and is verified first; only then
+/// is the old file moved into [`ROLLBACK_DIRECTORY`], replacing any earlier
+/// copy with the same name, and the new file renamed into the mods
+/// directory. A disabled mod stays disabled.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the new file name is not a plain file
+/// name, another file already has that name, or the download or a rename
+/// fails. The old file is left in place on failure.
+pub fn apply_mod_update(
+    update: &ModUpdate,
+    options: &DownloadOptions,
+    reporter: &mut dyn ProgressReporter,
+) -> Result<AppliedModUpdate> {
+    let directory = update.path.parent().ok_or_else(|| LauncherError::Other {
+        message: format!("{} is not in a mods directory", update.path.display()),
+    })?;
+    let file_name = update.latest.file_name.as_str();
+    if Path::new(file_name).file_name() != Some(file_name.as_ref()) {
+        return Err(LauncherError::UnsafePath {
+            base: directory.to_path_buf(),
+            path: PathBuf::from(file_name),
+        });
+    }
+    let previous_name = update
+        .path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    let installed = if previous_name.ends_with(DISABLED_SUFFIX) {
+        directory.join(format!("{file_name}{DISABLED_SUFFIX}"))
+    } else {
+        directory.join(file_name)
+    };
+    if installed != update.path && installed.exists() {
+        return Err(LauncherError::Other {
+            message: format!("{} already exists", installed.display()),
+        });
+    }
+
+    let staging = directory.join(STAGING_DIRECTORY);
+    let staged = staging.join(file_name);
+    let plan = DownloadPlan {
+        tasks: vec![DownloadTask {
+            url: update.latest.url.clone(),
+            destination: staged.clone(),
+            checksum: update.latest.checksum.clone(),
+            size: update.latest.size,
+            label: file_name.to_string(),
+        }],
+    };
+    execute_plan_with_options(&plan, options, reporter)?;
+
+    let rollback = directory.join(ROLLBACK_DIRECTORY).join(&previous_name);
+    fs::create_dir_all(directory.join(ROLLBACK_DIRECTORY))?;
+    fs::rename(&update.path, &rollback)?;
+    if let Err(err) = fs::rename(&staged, &installed) {
+        fs::rename(&rollback, &update.path)?;
+        return Err(err.into());
+    }
+    let _ = fs::remove_dir(&staging);
+    Ok(AppliedModUpdate {
+        previous: update.path.clone(),
+        installed,
+        rollback,
+    })
+}
+
+/// Undoes [`apply_mod_update`], removing the new file and restoring the old
+/// one from [`ROLLBACK_DIRECTORY`].
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the rollback copy is missing or a file
+/// cannot be removed or renamed.
+pub fn rollback_mod_update(applied: &AppliedModUpdate) -> Result<()> {
+    if !applied.rollback.is_file() {
+        return Err(LauncherError::Other {
+            message: format!("rollback copy {} is missing", applied.rollback.display()),
+        });
+    }
+    if applied.installed != applied.previous && applied.installed.exists() {
+        fs::remove_file(&applied.installed)?;
+    }
+    fs::rename(&applied.rollback, &applied.previous)?;
+    Ok(())
+}
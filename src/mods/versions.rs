@@ -0,0 +1,311 @@
+//! Version requirement matching for mod dependencies.
+//!
+//! Fabric and Quilt express requirements as semantic version predicates, such
+//! as `>=0.15.0`, `~1.20`, or `1.20.x`; Forge and NeoForge use Maven version
+//! ranges, such as `[47,)` or `[1.20.1,1.21)`. Both functions return `None`
+//! when the requirement or version cannot be interpreted, so callers can skip
+//! checks they cannot decide rather than report false failures.
+
+use std::cmp::Ordering;
+
+/// Checks `version` against a Fabric/Quilt version predicate.
+///
+/// Space-separated predicates must all match. Supported operators are `=`,
+/// `>`, `>=`, `<`, `<=`, `~` (same minor version), and `^` (same major
+/// version); `*` matches anything, and `x` components such as `1.20.x` match
+/// any value. Versions that are not semantic versions only match an exact,
+/// operator-free predicate.
+pub fn fabric_predicate_matches(version: &str, predicate: &str) -> Option<bool> {
+    let mut matches = true;
+    for term in predicate.split_whitespace() {
+        matches &= fabric_term_matches(version, term)?;
+    }
+    Some(matches)
+}
+
+/// Checks `version` against a Maven version range.
+///
+/// Comma-separated ranges such as `[1.0,2.0),[3.0,)` match when any range
+/// matches. A bare version outside brackets is a soft requirement and matches
+/// every version, as in Maven.
+pub fn maven_range_matches(version: &str, range: &str) -> Option<bool> {
+    let range = range.trim();
+    if range.is_empty() || range == "*" {
+        return Some(true);
+    }
+    if !range.starts_with(['[', '(']) {
+        return Some(!range.contains(['[', '(', ']', ')']));
+    }
+    let version = MavenVersion::parse(version);
+    let mut rest = range;
+    let mut matches = false;
+    while !rest.is_empty() {
+        let inclusive_lower = match rest.chars().next()? {
+            '[' => true,
+            '(' => false,
+            _ => return None,
+        };
+        let end = rest.find([']', ')'])?;
+        let inclusive_upper = rest[end..].starts_with(']');
+        let bounds = &rest[1..end];
+        matches |= match bounds.split_once(',') {
+            None => inclusive_lower && inclusive_upper && version == MavenVersion::parse(bounds),
+            Some((lower, upper)) => {
+                let lower = lower.trim();
+                let upper = upper.trim();
+                let above = lower.is_empty() || {
+                    let ordering = version.cmp(&MavenVersion::parse(lower));
+                    ordering == Ordering::Greater
+                        || (inclusive_lower && ordering == Ordering::Equal)
+                };
+                let below = upper.is_empty() || {
+                    let ordering = version.cmp(&MavenVersion::parse(upper));
+                    ordering == Ordering::Less || (inclusive_upper && ordering == Ordering::Equal)
+                };
+                above && below
+            }
+        };
+        rest = rest[end + 1..].trim_start();
+        rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+    }
+    Some(matches)
+}
+
+fn fabric_term_matches(version: &str, term: &str) -> Option<bool> {
+    if term == "*" {
+        return Some(true);
+    }
+    let (operator, target) = [">=", "<=", ">", "<", "=", "~", "^"]
+        .iter()
+        .find_map(|operator| term.strip_prefix(operator).map(|rest| (*operator, rest)))
+        .unwrap_or(("", term));
+    let Some(actual) = SemanticVersion::parse(version) else {
+        return matches!(operator, "" | "=").then_some(version == target);
+    };
+    if target.split(['-', '+']).next()?.split('.').any(is_wildcard) {
+        if !matches!(operator, "" | "=") {
+            return None;
+        }
+        let core = target.split(['-', '+']).next()?;
+        return Some(core.split('.').enumerate().all(|(index, part)| {
+            is_wildcard(part) || part.parse::<u64>().ok() == Some(actual.component(index))
+        }));
+    }
+    let expected = SemanticVersion::parse(target)?;
+    let ordering = actual.cmp(&expected);
+    Some(match operator {
+        "" | "=" => ordering == Ordering::Equal,
+        ">=" => ordering != Ordering::Less,
+        "<=" => ordering != Ordering::Greater,
+        ">" => ordering == Ordering::Greater,
+        "<" => ordering == Ordering::Less,
+        "~" => {
+            ordering != Ordering::Less
+                && actual.component(0) == expected.component(0)
+                && actual.component(1) == expected.component(1)
+        }
+        _ => ordering != Ordering::Less && actual.component(0) == expected.component(0),
+    })
+}
+
+fn is_wildcard(part: &str) -> bool {
+    matches!(part, "x" | "X" | "*")
+}
+
+/// Semantic version with any number of numeric components; build metadata is
+/// ignored.
+#[derive(Debug, PartialEq, Eq)]
+struct SemanticVersion {
+    components: Vec<u64>,
+    prerelease: Option<String>,
+}
+
+impl SemanticVersion {
+    fn parse(version: &str) -> Option<Self> {
+        let version = version.split('+').next()?;
+        let (core, prerelease) = match version.split_once('-') {
+            Some((core, prerelease)) => (core, Some(prerelease.to_string())),
+            None => (version, None),
+        };
+        let components = core
+            .split('.')
+            .map(|part| part.parse().ok())
+            .collect::<Option<Vec<u64>>>()?;
+        Some(Self {
+            components,
+            prerelease,
+        })
+    }
+
+    fn component(&self, index: usize) -> u64 {
+        self.components.get(index).copied().unwrap_or(0)
+    }
+}
+
+impl Ord for SemanticVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.components.len().max(other.components.len());
+        (0..len)
+            .map(|index| self.component(index).cmp(&other.component(index)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| match (&self.prerelease, &other.prerelease) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => compare_prerelease(a, b),
+            })
+    }
+}
+
+impl PartialOrd for SemanticVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn compare_prerelease(a: &str, b: &str) -> Ordering {
+    let mut a = a.split('.');
+    let mut b = b.split('.');
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => {
+                let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+                    (Ok(a), Ok(b)) => a.cmp(&b),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => a.cmp(b),
+                };
+                if ordering.is_ne() {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+/// Maven version ordered like Maven's `ComparableVersion`, simplified to
+/// numeric items and the well-known qualifiers.
+#[derive(Debug)]
+struct MavenVersion {
+    items: Vec<MavenItem>,
+}
+
+#[derive(Debug, Clone)]
+enum MavenItem {
+    Number(u64),
+    Qualifier(String),
+}
+
+impl MavenVersion {
+    fn parse(version: &str) -> Self {
+        let version = version.trim().to_ascii_lowercase();
+        let mut items = Vec::new();
+        for part in version.split(['.', '-', '_']) {
+            let mut token = String::new();
+            let mut digits = None;
+            for c in part.chars() {
+                if digits.is_some_and(|digits| digits != c.is_ascii_digit()) {
+                    items.push(MavenItem::from_token(&token, digits == Some(true)));
+                    token.clear();
+                }
+                digits = Some(c.is_ascii_digit());
+                token.push(c);
+            }
+            if !token.is_empty() {
+                items.push(MavenItem::from_token(&token, digits == Some(true)));
+            }
+        }
+        while items.last().is_some_and(MavenItem::is_null) {
+            items.pop();
+        }
+        Self { items }
+    }
+}
+
+impl MavenItem {
+    fn from_token(token: &str, digits: bool) -> Self {
+        if digits {
+            Self::Number(token.parse().unwrap_or(u64::MAX))
+        } else {
+            Self::Qualifier(
+                match token {
+                    "a" => "alpha",
+                    "b" => "beta",
+                    "m" => "milestone",
+                    "cr" => "rc",
+                    "ga" | "final" | "release" => "",
+                    other => other,
+                }
+                .to_string(),
+            )
+        }
+    }
+
+    fn is_null(&self) -> bool {
+        match self {
+            Self::Number(number) => *number == 0,
+            Self::Qualifier(qualifier) => qualifier.is_empty(),
+        }
+    }
+
+    fn rank(qualifier: &str) -> (u8, &str) {
+        match qualifier {
+            "alpha" => (0, ""),
+            "beta" => (1, ""),
+            "milestone" => (2, ""),
+            "rc" => (3, ""),
+            "snapshot" => (4, ""),
+            "" => (5, ""),
+            "sp" => (6, ""),
+            other => (7, other),
+        }
+    }
+
+    fn compare(item: Option<&Self>, other: Option<&Self>) -> Ordering {
+        match (item, other) {
+            (None, None) => Ordering::Equal,
+            (Some(item), None) => Self::compare_to_null(item),
+            (None, Some(other)) => Self::compare_to_null(other).reverse(),
+            (Some(Self::Number(a)), Some(Self::Number(b))) => a.cmp(b),
+            (Some(Self::Number(_)), Some(Self::Qualifier(_))) => Ordering::Greater,
+            (Some(Self::Qualifier(_)), Some(Self::Number(_))) => Ordering::Less,
+            (Some(Self::Qualifier(a)), Some(Self::Qualifier(b))) => {
+                Self::rank(a).cmp(&Self::rank(b))
+            }
+        }
+    }
+
+    fn compare_to_null(item: &Self) -> Ordering {
+        match item {
+            Self::Number(number) => number.cmp(&0),
+            Self::Qualifier(qualifier) => Self::rank(qualifier).cmp(&Self::rank("")),
+        }
+    }
+}
+
+impl PartialEq for MavenVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MavenVersion {}
+
+impl Ord for MavenVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.items.len().max(other.items.len());
+        (0..len)
+            .map(|index| MavenItem::compare(self.items.get(index), other.items.get(index)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for MavenVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
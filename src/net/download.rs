@@ -1,17 +1,34 @@
 //! Download plans and execution.
 
 use std::{
-    fs::{self, File},
-    io,
-    path::PathBuf,
+    collections::{HashMap, HashSet},
+    fs::{self, File, OpenOptions},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use reqwest::{
+    blocking::Client,
+    header::{CONTENT_RANGE, RANGE},
+    StatusCode,
 };
 
 use crate::{
-    io::hash::sha1_file,
+    io::hash::{sha1_file, sha256_file, sha512_file},
+    net::mirror::MirrorConfig,
     progress::{ProgressEvent, ProgressReporter, SkipReason},
     LauncherError, Result,
 };
 
+/// Default number of concurrent download workers.
+pub const DEFAULT_DOWNLOAD_WORKERS: usize = 8;
+
 /// Supported checksum validation methods for downloaded files.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Checksum {
@@ -19,6 +36,8 @@ pub enum Checksum {
     Sha1(String),
     /// SHA-256 checksum.
     Sha256(String),
+    /// SHA-512 checksum.
+    Sha512(String),
 }
 
 /// One file download.
@@ -30,6 +49,8 @@ pub struct DownloadTask {
     pub destination: PathBuf,
     /// Optional checksum used for skip and validation decisions.
     pub checksum: Option<Checksum>,
+    /// Expected size in bytes, checked before the checksum when known.
+    pub size: Option<u64>,
     /// Human-readable task label reported in progress events.
     pub label: String,
 }
@@ -41,73 +62,487 @@ pub struct DownloadPlan {
     pub tasks: Vec<DownloadTask>,
 }
 
+/// Settings that control how a [`DownloadPlan`] is executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadOptions {
+    /// Maximum number of files downloaded at the same time.
+    ///
+    /// A value of `0` is treated as `1`.
+    pub workers: usize,
+    /// Retry behavior for transient network failures.
+    pub retry: RetryPolicy,
+    /// Mirrors tried, in order, for every task URL.
+    pub mirrors: MirrorConfig,
+    /// Alternative sources keyed by task URL, tried in order after every
+    /// mirror candidate of the task URL has failed.
+    pub alternatives: HashMap<String, Vec<String>>,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self {
+            workers: DEFAULT_DOWNLOAD_WORKERS,
+            retry: RetryPolicy::default(),
+            mirrors: MirrorConfig::default(),
+            alternatives: HashMap::new(),
+        }
+    }
+}
+
+/// Exponential backoff policy for retrying failed downloads.
+///
+/// Connection failures, interrupted transfers, `408`, `429`, and `5xx`
+/// responses are retried. Other HTTP errors and local filesystem failures fail
+/// immediately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts per task, including the first one.
+    ///
+    /// A value of `0` is treated as `1`.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between two attempts.
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Returns a policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the delay to wait after the given failed attempt (1-based).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+        }
+    }
+}
+
+impl Checksum {
+    /// Returns the expected digest as lowercase hexadecimal.
+    pub fn expected(&self) -> &str {
+        match self {
+            Self::Sha1(expected) | Self::Sha256(expected) | Self::Sha512(expected) => expected,
+        }
+    }
+
+    /// Calculates the digest of `path` with this checksum's algorithm.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the file cannot be read.
+    pub fn digest_file(&self, path: impl AsRef<Path>) -> Result<String> {
+        match self {
+            Self::Sha1(_) => sha1_file(path),
+            Self::Sha256(_) => sha256_file(path),
+            Self::Sha512(_) => sha512_file(path),
+        }
+    }
+
+    /// Returns whether the file at `path` matches this checksum.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the file cannot be read.
+    pub fn matches_file(&self, path: impl AsRef<Path>) -> Result<bool> {
+        Ok(self
+            .digest_file(path)?
+            .eq_ignore_ascii_case(self.expected()))
+    }
+}
+
 /// Returns whether an existing destination file can be reused.
 ///
+/// A file whose length differs from [`DownloadTask::size`] is never reused and
+/// is not hashed.
+///
 /// # Errors
 ///
 /// Returns [`crate::LauncherError`] if checksum calculation fails.
 pub fn should_skip_existing(task: &DownloadTask) -> Result<bool> {
-    if !task.destination.is_file() {
+    let Ok(metadata) = fs::metadata(&task.destination) else {
+        return Ok(false);
+    };
+    if !metadata.is_file() || task.size.is_some_and(|size| size != metadata.len()) {
         return Ok(false);
     }
 
     match &task.checksum {
-        Some(Checksum::Sha1(expected)) => Ok(sha1_file(&task.destination)? == *expected),
-        Some(Checksum::Sha256(_)) => Ok(false),
+        Some(checksum) => checksum.matches_file(&task.destination),
         None => Ok(true),
     }
 }
 
-/// Executes a download plan in order.
-///
-/// Existing files with matching checksums are skipped. Each completed SHA-1
-/// download is verified before the next task begins.
+/// Executes a download plan with [`DownloadOptions::default`].
 ///
 /// # Errors
 ///
 /// Returns [`crate::LauncherError`] for network, filesystem, or checksum
 /// failures.
 pub fn execute_plan(plan: &DownloadPlan, reporter: &mut dyn ProgressReporter) -> Result<()> {
-    let client = super::http::client()?;
+    execute_plan_with_options(plan, &DownloadOptions::default(), reporter)
+}
+
+/// Executes a download plan with a pool of worker threads.
+///
+/// Existing files with matching sizes and checksums are skipped and each
+/// completed download is verified, size first and then SHA-1, SHA-256 or
+/// SHA-512, before it is reported as finished. Tasks that target a destination
+/// already claimed by an earlier task in the plan are reported as skipped
+/// instead of being written twice.
+///
+/// Each file is written to a `.part` file next to its destination and renamed
+/// into place only after verification, so an interrupted run never leaves a
+/// truncated file at the destination. A leftover `.part` file is resumed with
+/// an HTTP `Range` request when the server supports it. Transient failures are
+/// retried according to [`DownloadOptions::retry`] and reported as
+/// [`ProgressEvent::TaskRetrying`]. Within one attempt every URL returned by
+/// [`MirrorConfig::candidates`], followed by the task's
+/// [`DownloadOptions::alternatives`], is tried in order before the attempt
+/// counts as failed.
+///
+/// Progress events from all workers are forwarded to `reporter` on the calling
+/// thread. After the first failure no new tasks are started; downloads already
+/// in flight are allowed to finish and the first error is returned.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] for network, filesystem, or checksum
+/// failures.
+pub fn execute_plan_with_options(
+    plan: &DownloadPlan,
+    options: &DownloadOptions,
+    reporter: &mut dyn ProgressReporter,
+) -> Result<()> {
+    let mut destinations = HashSet::new();
+    let mut tasks = Vec::with_capacity(plan.tasks.len());
     for task in &plan.tasks {
-        if should_skip_existing(task)? {
+        if destinations.insert(&task.destination) {
+            tasks.push(task);
+        } else {
             reporter.report(ProgressEvent::TaskSkipped {
                 label: task.label.clone(),
-                reason: if task.checksum.is_some() {
-                    SkipReason::ChecksumMatched
-                } else {
-                    SkipReason::FileExistsWithoutChecksum
-                },
+                reason: SkipReason::DuplicateDestination,
+            });
+        }
+    }
+    if tasks.is_empty() {
+        return Ok(());
+    }
+
+    let client = super::http::client()?;
+    let workers = options.workers.clamp(1, tasks.len());
+    let next = AtomicUsize::new(0);
+    let cancelled = AtomicBool::new(false);
+    let failure = Mutex::new(None);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let (client, tasks, next, cancelled, failure) =
+                (&client, &tasks, &next, &cancelled, &failure);
+            scope.spawn(move || loop {
+                if cancelled.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Some(task) = tasks.get(next.fetch_add(1, Ordering::SeqCst)) else {
+                    break;
+                };
+                let mut forward = |event: ProgressEvent| {
+                    let _ = sender.send(event);
+                };
+                if let Err(err) = run_task(client, task, options, cancelled, &mut forward) {
+                    cancelled.store(true, Ordering::SeqCst);
+                    let mut failure = failure.lock().unwrap_or_else(|err| err.into_inner());
+                    failure.get_or_insert(err);
+                    break;
+                }
             });
-            continue;
         }
+        drop(sender);
 
-        reporter.report(ProgressEvent::TaskStarted {
+        for event in receiver {
+            reporter.report(event);
+        }
+    });
+
+    match failure.into_inner().unwrap_or_else(|err| err.into_inner()) {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+fn run_task(
+    client: &Client,
+    task: &DownloadTask,
+    options: &DownloadOptions,
+    cancelled: &AtomicBool,
+    reporter: &mut dyn ProgressReporter,
+) -> Result<()> {
+    if should_skip_existing(task)? {
+        reporter.report(ProgressEvent::TaskSkipped {
             label: task.label.clone(),
-            path: task.destination.clone(),
+            reason: if task.checksum.is_some() {
+                SkipReason::ChecksumMatched
+            } else {
+                SkipReason::FileExistsWithoutChecksum
+            },
         });
+        return Ok(());
+    }
 
-        if let Some(parent) = task.destination.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        let mut response = client.get(&task.url).send()?.error_for_status()?;
-        let mut file = File::create(&task.destination)?;
-        io::copy(&mut response, &mut file)?;
+    reporter.report(ProgressEvent::TaskStarted {
+        label: task.label.clone(),
+        path: task.destination.clone(),
+    });
 
-        if let Some(Checksum::Sha1(expected)) = &task.checksum {
-            let actual = sha1_file(&task.destination)?;
-            if actual != *expected {
-                return Err(LauncherError::ChecksumMismatch {
-                    path: task.destination.clone(),
-                    expected: expected.clone(),
-                    actual,
+    if let Some(parent) = task.destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let partial = partial_path(&task.destination);
+    let retry = &options.retry;
+    let mut candidates = options.mirrors.candidates(&task.url);
+    for alternative in options.alternatives.get(&task.url).into_iter().flatten() {
+        if !candidates.contains(alternative) {
+            candidates.push(alternative.clone());
+        }
+    }
+    let max_attempts = retry.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match download_from_candidates(client, task, &candidates, &partial, reporter) {
+            Ok(()) => break,
+            Err(Attempt::Fatal(err)) => return Err(err),
+            Err(Attempt::Retry(err)) => {
+                if attempt >= max_attempts || cancelled.load(Ordering::SeqCst) {
+                    return Err(err);
+                }
+                let delay = retry.backoff(attempt);
+                reporter.report(ProgressEvent::TaskRetrying {
+                    label: task.label.clone(),
+                    attempt,
+                    max_attempts,
+                    delay,
+                    reason: err.to_string(),
                 });
+                thread::sleep(delay);
+                attempt += 1;
             }
         }
+    }
+
+    fs::rename(&partial, &task.destination)?;
+    reporter.report(ProgressEvent::TaskFinished {
+        label: task.label.clone(),
+    });
+    Ok(())
+}
+
+/// Outcome of one failed download attempt.
+enum Attempt {
+    /// The failure may succeed when tried again.
+    Retry(LauncherError),
+    /// The failure will not go away by retrying.
+    Fatal(LauncherError),
+}
+
+impl From<std::io::Error> for Attempt {
+    fn from(source: std::io::Error) -> Self {
+        Self::Fatal(source.into())
+    }
+}
+
+impl From<LauncherError> for Attempt {
+    fn from(err: LauncherError) -> Self {
+        Self::Fatal(err)
+    }
+}
+
+fn download_from_candidates(
+    client: &Client,
+    task: &DownloadTask,
+    candidates: &[String],
+    partial: &Path,
+    reporter: &mut dyn ProgressReporter,
+) -> std::result::Result<(), Attempt> {
+    let mut outcome = Ok(());
+    for (index, url) in candidates.iter().enumerate() {
+        outcome = download_attempt(client, task, url, partial, reporter);
+        let has_next = index + 1 < candidates.len();
+        match &outcome {
+            Ok(()) => return outcome,
+            // Mirrors may lag behind or serve bad copies; fall through to the
+            // next candidate for any remote failure.
+            Err(Attempt::Retry(_)) if has_next => {}
+            Err(Attempt::Fatal(
+                LauncherError::Network { .. }
+                | LauncherError::ChecksumMismatch { .. }
+                | LauncherError::SizeMismatch { .. },
+            )) if has_next => {}
+            Err(_) => return outcome,
+        }
+    }
+    outcome
+}
+
+fn download_attempt(
+    client: &Client,
+    task: &DownloadTask,
+    url: &str,
+    partial: &Path,
+    reporter: &mut dyn ProgressReporter,
+) -> std::result::Result<(), Attempt> {
+    let offset = fs::metadata(partial).map(|meta| meta.len()).unwrap_or(0);
+    let mut request = client.get(url);
+    if offset > 0 {
+        request = request.header(RANGE, format!("bytes={offset}-"));
+    }
+    let response = request.send().map_err(network_failure)?;
+    if offset > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        fs::remove_file(partial)?;
+        return Err(Attempt::Retry(LauncherError::Other {
+            message: format!("server rejected resume of {url}"),
+        }));
+    }
+    let mut response = response.error_for_status().map_err(network_failure)?;
+
+    let resumed = offset > 0 && resumes_at(&response, offset);
+    let (mut file, mut received) = if resumed {
+        (OpenOptions::new().append(true).open(partial)?, offset)
+    } else {
+        (File::create(partial)?, 0)
+    };
+    let total = response.content_length().map(|length| length + received);
+    if let (Some(expected), Some(total)) = (task.size, total) {
+        if total != expected {
+            drop(file);
+            return Err(discard_partial(task, partial, resumed, expected, total)?);
+        }
+    }
 
-        reporter.report(ProgressEvent::TaskFinished {
+    let mut buffer = [0_u8; 64 * 1024];
+    loop {
+        let read = match response.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) => return Err(Attempt::Retry(err.into())),
+        };
+        file.write_all(&buffer[..read])?;
+        received += read as u64;
+        reporter.report(ProgressEvent::BytesReceived {
             label: task.label.clone(),
+            received,
+            total,
         });
+        if task.size.is_some_and(|expected| received > expected) {
+            break;
+        }
+    }
+    file.sync_all()?;
+    drop(file);
+
+    if let Some(expected) = task.size {
+        if received < expected {
+            // The connection ended early; keep the partial file so the next
+            // attempt resumes where this one stopped.
+            return Err(Attempt::Retry(LauncherError::SizeMismatch {
+                path: task.destination.clone(),
+                expected,
+                actual: received,
+            }));
+        }
+        if received > expected {
+            return Err(discard_partial(task, partial, resumed, expected, received)?);
+        }
+    }
+
+    if let Some(checksum) = &task.checksum {
+        let actual = checksum.digest_file(partial)?;
+        if !actual.eq_ignore_ascii_case(checksum.expected()) {
+            fs::remove_file(partial)?;
+            let err = LauncherError::ChecksumMismatch {
+                path: task.destination.clone(),
+                expected: checksum.expected().to_string(),
+                actual,
+            };
+            // A stale partial file may have been resumed; start over once more.
+            return Err(if resumed {
+                Attempt::Retry(err)
+            } else {
+                Attempt::Fatal(err)
+            });
+        }
     }
     Ok(())
 }
+
+/// Deletes a partial file whose size cannot match and returns the failure.
+///
+/// A resumed file may have been stale, so it is retried from scratch.
+fn discard_partial(
+    task: &DownloadTask,
+    partial: &Path,
+    resumed: bool,
+    expected: u64,
+    actual: u64,
+) -> std::io::Result<Attempt> {
+    fs::remove_file(partial)?;
+    let err = LauncherError::SizeMismatch {
+        path: task.destination.clone(),
+        expected,
+        actual,
+    };
+    Ok(if resumed {
+        Attempt::Retry(err)
+    } else {
+        Attempt::Fatal(err)
+    })
+}
+
+fn partial_path(destination: &Path) -> PathBuf {
+    let mut name = destination.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    destination.with_file_name(name)
+}
+
+fn resumes_at(response: &reqwest::blocking::Response, offset: u64) -> bool {
+    response.status() == StatusCode::PARTIAL_CONTENT
+        && response
+            .headers()
+            .get(CONTENT_RANGE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with(&format!("bytes {offset}-")))
+}
+
+fn network_failure(source: reqwest::Error) -> Attempt {
+    let retryable = match source.status() {
+        Some(status) => {
+            status.is_server_error()
+                || status == StatusCode::REQUEST_TIMEOUT
+                || status == StatusCode::TOO_MANY_REQUESTS
+        }
+        None => true,
+    };
+    if retryable {
+        Attempt::Retry(source.into())
+    } else {
+        Attempt::Fatal(source.into())
+    }
+}
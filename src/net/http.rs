@@ -2,7 +2,7 @@
 
 use reqwest::blocking::Client;
 
-use crate::Result;
+use crate::{net::mirror::MirrorConfig, Result};
 
 /// Returns the user agent used by crate-managed HTTP requests.
 pub fn user_agent() -> String {
@@ -40,3 +40,47 @@ where
 {
     Ok(client()?.get(url).send()?.error_for_status()?.json()?)
 }
+
+/// Fetches a URL as text, trying each mirror candidate in order.
+///
+/// # Errors
+///
+/// Returns the error from the last candidate when every candidate fails.
+pub fn get_text_with_mirrors(url: &str, mirrors: &MirrorConfig) -> Result<String> {
+    let client = client()?;
+    first_success(url, mirrors, |candidate| {
+        Ok(client.get(candidate).send()?.error_for_status()?.text()?)
+    })
+}
+
+/// Fetches and decodes JSON, trying each mirror candidate in order.
+///
+/// # Errors
+///
+/// Returns the error from the last candidate when every candidate fails.
+pub fn get_json_with_mirrors<T>(url: &str, mirrors: &MirrorConfig) -> Result<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let client = client()?;
+    first_success(url, mirrors, |candidate| {
+        Ok(client.get(candidate).send()?.error_for_status()?.json()?)
+    })
+}
+
+fn first_success<T>(
+    url: &str,
+    mirrors: &MirrorConfig,
+    mut fetch: impl FnMut(&str) -> Result<T>,
+) -> Result<T> {
+    let mut last_error = None;
+    for candidate in mirrors.candidates(url) {
+        match fetch(&candidate) {
+            Ok(value) => return Ok(value),
+            Err(err) => last_error = Some(err),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| crate::LauncherError::Other {
+        message: format!("no mirror candidates for {url}"),
+    }))
+}
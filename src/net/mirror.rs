@@ -0,0 +1,221 @@
+//! Upstream endpoints and mirror rewriting.
+//!
+//! Install planning always produces official upstream URLs. A [`MirrorConfig`]
+//! rewrites those URLs at request time, so the same plan can be served by a
+//! public mirror such as BMCLAPI, a corporate proxy, or a local stand-in server
+//! in tests. Mirrors are tried in order, optionally followed by the official
+//! URL.
+
+/// Base URLs for every upstream service the crate talks to.
+///
+/// [`Endpoints::official`] returns the upstream values. A [`Mirror`] pairs its
+/// own `Endpoints` with the official ones and rewrites any URL that starts with
+/// an official value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoints {
+    /// Mojang version manifest URL.
+    pub version_manifest: String,
+    /// Base URL for content-addressed asset objects.
+    pub resources: String,
+    /// Base URL of Mojang's library Maven repository.
+    pub libraries: String,
+    /// Base URL of the Fabric Meta API.
+    pub fabric_meta: String,
+    /// Base URL of the Fabric Maven repository.
+    pub fabric_maven: String,
+    /// Base URL of the Quilt Meta API.
+    pub quilt_meta: String,
+    /// Base URL of the Forge Maven repository.
+    pub forge_maven: String,
+    /// Base URL of the NeoForge release Maven repository.
+    pub neoforge_maven: String,
+    /// Mojang Java runtime manifest URL.
+    pub java_runtime_manifest: String,
+}
+
+impl Endpoints {
+    /// Returns the official upstream endpoints.
+    pub fn official() -> Self {
+        Self {
+            version_manifest: "https://launchermeta.mojang.com/mc/game/version_manifest_v2.json"
+                .to_string(),
+            resources: "https://resources.download.minecraft.net".to_string(),
+            libraries: "https://libraries.minecraft.net".to_string(),
+            fabric_meta: "https://meta.fabricmc.net/v2".to_string(),
+            fabric_maven: "https://maven.fabricmc.net".to_string(),
+            quilt_meta: "https://meta.quiltmc.org/v3".to_string(),
+            forge_maven: "https://maven.minecraftforge.net".to_string(),
+            neoforge_maven: "https://maven.neoforged.net/releases".to_string(),
+            java_runtime_manifest: "https://launchermeta.mojang.com/v1/products/java-runtime/2ec0cc96c44e5a76b9c8b7c39df7210883d12871/all.json".to_string(),
+        }
+    }
+
+    fn pairs<'a>(&'a self, other: &'a Endpoints) -> [(&'a str, &'a str); 9] {
+        [
+            (&self.version_manifest, &other.version_manifest),
+            (&self.resources, &other.resources),
+            (&self.libraries, &other.libraries),
+            (&self.fabric_meta, &other.fabric_meta),
+            (&self.fabric_maven, &other.fabric_maven),
+            (&self.quilt_meta, &other.quilt_meta),
+            (&self.forge_maven, &other.forge_maven),
+            (&self.neoforge_maven, &other.neoforge_maven),
+            (&self.java_runtime_manifest, &other.java_runtime_manifest),
+        ]
+    }
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self::official()
+    }
+}
+
+/// One mirror that can serve some or all upstream URLs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mirror {
+    /// Name used in logs and UI.
+    pub name: String,
+    /// Replacement endpoints; fields equal to the official value are not
+    /// rewritten.
+    pub endpoints: Endpoints,
+    /// Additional `(official prefix, mirror prefix)` rewrites for URLs that come
+    /// from metadata documents, such as version JSON and client jar hosts.
+    pub rewrites: Vec<(String, String)>,
+}
+
+impl Mirror {
+    /// Creates a mirror from replacement endpoints without extra rewrites.
+    pub fn new(name: impl Into<String>, endpoints: Endpoints) -> Self {
+        Self {
+            name: name.into(),
+            endpoints,
+            rewrites: Vec::new(),
+        }
+    }
+
+    /// Returns the BMCLAPI mirror commonly used by launchers in mainland China.
+    pub fn bmclapi() -> Self {
+        const BASE: &str = "https://bmclapi2.bangbang93.com";
+        let official = Endpoints::official();
+        Self {
+            name: "BMCLAPI".to_string(),
+            endpoints: Endpoints {
+                version_manifest: format!("{BASE}/mc/game/version_manifest_v2.json"),
+                resources: format!("{BASE}/assets"),
+                libraries: format!("{BASE}/maven"),
+                fabric_meta: format!("{BASE}/fabric-meta/v2"),
+                fabric_maven: format!("{BASE}/maven"),
+                forge_maven: format!("{BASE}/maven"),
+                neoforge_maven: format!("{BASE}/maven"),
+                java_runtime_manifest: format!(
+                    "{BASE}/v1/products/java-runtime/2ec0cc96c44e5a76b9c8b7c39df7210883d12871/all.json"
+                ),
+                ..official
+            },
+            rewrites: [
+                "https://launchermeta.mojang.com",
+                "https://launcher.mojang.com",
+                "https://piston-meta.mojang.com",
+                "https://piston-data.mojang.com",
+            ]
+            .into_iter()
+            .map(|host| (host.to_string(), BASE.to_string()))
+            .collect(),
+        }
+    }
+
+    /// Rewrites an official URL for this mirror.
+    ///
+    /// Returns `None` when the mirror does not serve the URL.
+    pub fn rewrite(&self, url: &str) -> Option<String> {
+        let official = Endpoints::official();
+        let extra_pairs = self
+            .rewrites
+            .iter()
+            .map(|(from, to)| (from.as_str(), to.as_str()));
+        for (from, to) in official
+            .pairs(&self.endpoints)
+            .into_iter()
+            .chain(extra_pairs)
+        {
+            if from == to {
+                continue;
+            }
+            if let Some(rest) = url.strip_prefix(from) {
+                if rest.is_empty() || rest.starts_with(['/', '?']) {
+                    return Some(format!("{to}{rest}"));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Ordered mirror selection used for metadata requests and downloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MirrorConfig {
+    /// Mirrors tried in order before the official URL.
+    pub mirrors: Vec<Mirror>,
+    /// Whether the official URL is tried after every mirror has failed.
+    ///
+    /// URLs that no mirror can rewrite always use the official URL.
+    pub fallback_to_official: bool,
+}
+
+impl MirrorConfig {
+    /// Returns a configuration that talks to official upstreams only.
+    pub fn official() -> Self {
+        Self {
+            mirrors: Vec::new(),
+            fallback_to_official: true,
+        }
+    }
+
+    /// Returns a configuration that tries `mirrors` in order, then the official
+    /// URL.
+    pub fn with_fallback(mirrors: Vec<Mirror>) -> Self {
+        Self {
+            mirrors,
+            fallback_to_official: true,
+        }
+    }
+
+    /// Returns the URLs to try for an official URL, in order.
+    ///
+    /// The result is never empty.
+    ///
+    /// ```
+    /// use mc_launcher_core::net::mirror::{Mirror, MirrorConfig};
+    ///
+    /// let config = MirrorConfig::with_fallback(vec![Mirror::bmclapi()]);
+    /// let urls = config.candidates("https://resources.download.minecraft.net/ab/abcdef");
+    /// assert_eq!(
+    ///     urls,
+    ///     vec![
+    ///         "https://bmclapi2.bangbang93.com/assets/ab/abcdef".to_string(),
+    ///         "https://resources.download.minecraft.net/ab/abcdef".to_string(),
+    ///     ]
+    /// );
+    /// ```
+    pub fn candidates(&self, url: &str) -> Vec<String> {
+        let mut urls = Vec::new();
+        for mirror in &self.mirrors {
+            if let Some(rewritten) = mirror.rewrite(url) {
+                if !urls.contains(&rewritten) {
+                    urls.push(rewritten);
+                }
+            }
+        }
+        if urls.is_empty() || (self.fallback_to_official && !urls.iter().any(|u| u == url)) {
+            urls.push(url.to_string());
+        }
+        urls
+    }
+}
+
+impl Default for MirrorConfig {
+    fn default() -> Self {
+        Self::official()
+    }
+}
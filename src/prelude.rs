@@ -6,17 +6,19 @@
 
 pub use crate::{
     account::Account,
-    command::builder::{LaunchCommand, LaunchOptions},
+    command::builder::{LaunchCommand, LaunchOptions, LogConfigPolicy},
     compatibility::{
         CompatibilityPatch, CompatibilityPolicy, CompatibilityResult, JavaRuntimeHint,
         WindowingHint, WindowingStrategy,
     },
     error::{LauncherError, Result},
     install::request::{InstallRequest, InstallResult, JavaInstallPolicy},
+    instance::{Instance, InstanceConfig},
     launcher::Launcher,
     loader::{
         common::{LoaderSpec, LoaderVersion},
         LoaderKind,
     },
+    process::{GameOutputLine, GameOutputStream, GameProcess},
     progress::{ProgressEvent, ProgressReporter},
 };
@@ -0,0 +1,572 @@
+//! Crash report discovery and classification.
+//!
+//! After the game exits unsuccessfully, [`analyze_crash`] looks for the newest
+//! Minecraft crash report (`crash-reports/crash-*.txt`) and JVM fatal error log
+//! (`hs_err_pid*.log`) in the game directory, parses them, and matches their
+//! text, together with any captured game output, against a table of known
+//! failure patterns. Each match becomes a [`CrashDiagnosis`] whose
+//! [`CrashCause`] carries enough detail for a launcher to suggest a fix.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+use crate::Result;
+
+/// Known cause of a game crash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CrashCause {
+    /// The JVM ran out of heap or native memory.
+    OutOfMemory,
+    /// A class was compiled for a newer Java release than the one running.
+    UnsupportedJavaVersion {
+        /// Java major version the class needs, when reported.
+        required: Option<u32>,
+        /// Newest Java major version the running JVM supports, when reported.
+        current: Option<u32>,
+    },
+    /// A mod requires another mod that is not installed.
+    MissingModDependency {
+        /// Mod that declares the dependency, when reported.
+        mod_id: Option<String>,
+        /// Missing mod id.
+        dependency: String,
+    },
+    /// A Mixin could not be applied.
+    MixinFailure {
+        /// Mod that owns the mixin, when reported.
+        mod_id: Option<String>,
+        /// Mixin class or config, when reported.
+        mixin: Option<String>,
+    },
+    /// OpenGL context creation or the graphics driver failed.
+    GraphicsDriver {
+        /// Matched error text.
+        detail: String,
+    },
+    /// The same module, usually Minecraft itself, is on the module path more
+    /// than once.
+    DuplicateModule {
+        /// Matched error text.
+        detail: String,
+    },
+}
+
+impl CrashCause {
+    /// Returns a short, user-facing suggestion for fixing the cause.
+    pub fn suggestion(&self) -> String {
+        match self {
+            Self::OutOfMemory => {
+                "Increase the maximum memory (-Xmx) allocated to the game.".to_string()
+            }
+            Self::UnsupportedJavaVersion {
+                required: Some(required),
+                ..
+            } => format!("Launch the game with Java {required} or newer."),
+            Self::UnsupportedJavaVersion { required: None, .. } => {
+                "Launch the game with a newer Java version.".to_string()
+            }
+            Self::MissingModDependency { dependency, .. } => {
+                format!("Install the missing mod `{dependency}`.")
+            }
+            Self::MixinFailure {
+                mod_id: Some(mod_id),
+                ..
+            } => format!("Update or remove the mod `{mod_id}`; one of its mixins failed."),
+            Self::MixinFailure { mod_id: None, .. } => {
+                "A mod's mixin failed; update or remove recently added mods.".to_string()
+            }
+            Self::GraphicsDriver { .. } => {
+                "Update the graphics driver, or make sure the game uses a GPU with OpenGL support."
+                    .to_string()
+            }
+            Self::DuplicateModule { .. } => {
+                "Remove duplicate Minecraft or mod jars from the mods folder and classpath."
+                    .to_string()
+            }
+        }
+    }
+}
+
+/// One classified crash cause and the text that matched it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrashDiagnosis {
+    /// Classified cause.
+    pub cause: CrashCause,
+    /// Line that matched the rule.
+    pub evidence: String,
+}
+
+/// Mod entry from a crash report's mod list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrashReportMod {
+    /// Mod id.
+    pub id: String,
+    /// Display name, when listed.
+    pub name: Option<String>,
+    /// Mod version, when listed.
+    pub version: Option<String>,
+}
+
+/// Parsed Minecraft crash report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrashReport {
+    /// Report file, if it was read from disk.
+    pub path: Option<PathBuf>,
+    /// `Time:` value.
+    pub time: Option<String>,
+    /// `Description:` value.
+    pub description: Option<String>,
+    /// Exception and stack trace that caused the crash.
+    pub stack_trace: String,
+    /// `-- Title --` sections after the stack trace, in report order.
+    pub sections: Vec<(String, String)>,
+    /// Entries of the `System Details` section. Multi-line values keep their
+    /// lines separated by `\n`.
+    pub system_details: BTreeMap<String, String>,
+    /// Mods from the Forge `Mod List` or the Fabric/Quilt `Fabric Mods` detail.
+    pub mods: Vec<CrashReportMod>,
+    /// Full report text.
+    pub text: String,
+}
+
+impl CrashReport {
+    /// Parses crash report text.
+    pub fn parse(text: &str) -> Self {
+        let mut time = None;
+        let mut description = None;
+        let mut stack_trace = Vec::new();
+        let mut sections: Vec<(String, Vec<&str>)> = Vec::new();
+        let mut in_trace = false;
+
+        for line in text.lines() {
+            if let Some(title) = section_title(line) {
+                in_trace = false;
+                sections.push((title.to_string(), Vec::new()));
+            } else if let Some((_, body)) = sections.last_mut() {
+                body.push(line);
+            } else if let Some(value) = line.strip_prefix("Time: ") {
+                time = Some(value.trim().to_string());
+            } else if let Some(value) = line.strip_prefix("Description: ") {
+                description = Some(value.trim().to_string());
+                in_trace = true;
+            } else if line.starts_with("A detailed walkthrough of the error") {
+                in_trace = false;
+            } else if in_trace {
+                stack_trace.push(line);
+            }
+        }
+
+        let sections: Vec<(String, String)> = sections
+            .into_iter()
+            .map(|(title, body)| (title, body.join("\n").trim_end().to_string()))
+            .collect();
+        let system_details = sections
+            .iter()
+            .find(|(title, _)| title == "System Details")
+            .map(|(_, body)| parse_details(body))
+            .unwrap_or_default();
+        let mods = parse_mods(&system_details);
+
+        Self {
+            path: None,
+            time,
+            description,
+            stack_trace: stack_trace.join("\n").trim().to_string(),
+            sections,
+            system_details,
+            mods,
+            text: text.to_string(),
+        }
+    }
+
+    /// Reads and parses a crash report file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the file cannot be read.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = String::from_utf8_lossy(&fs::read(path)?).into_owned();
+        Ok(Self {
+            path: Some(path.to_path_buf()),
+            ..Self::parse(&text)
+        })
+    }
+}
+
+/// Parsed JVM fatal error log (`hs_err_pid*.log`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JvmCrashLog {
+    /// Log file, if it was read from disk.
+    pub path: Option<PathBuf>,
+    /// Header comment lines describing the error, without their `# ` prefix.
+    pub summary: Vec<String>,
+    /// Native or Java frame where the crash happened, when reported.
+    pub problematic_frame: Option<String>,
+    /// Full log text.
+    pub text: String,
+}
+
+impl JvmCrashLog {
+    /// Parses JVM fatal error log text.
+    pub fn parse(text: &str) -> Self {
+        let mut summary = Vec::new();
+        let mut problematic_frame = None;
+        let mut lines = text.lines();
+        while let Some(line) = lines.next() {
+            let Some(comment) = line.strip_prefix('#') else {
+                break;
+            };
+            let comment = comment.trim();
+            if comment == "Problematic frame:" {
+                problematic_frame = lines
+                    .next()
+                    .and_then(|frame| frame.strip_prefix('#'))
+                    .map(|frame| frame.trim().to_string());
+            } else if !comment.is_empty() {
+                summary.push(comment.to_string());
+            }
+        }
+        Self {
+            path: None,
+            summary,
+            problematic_frame,
+            text: text.to_string(),
+        }
+    }
+
+    /// Reads and parses a JVM fatal error log file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the file cannot be read.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = String::from_utf8_lossy(&fs::read(path)?).into_owned();
+        Ok(Self {
+            path: Some(path.to_path_buf()),
+            ..Self::parse(&text)
+        })
+    }
+}
+
+/// Result of [`analyze_crash`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CrashAnalysis {
+    /// Newest crash report, if one was found.
+    pub crash_report: Option<CrashReport>,
+    /// Newest JVM fatal error log, if one was found.
+    pub jvm_crash: Option<JvmCrashLog>,
+    /// Classified causes, most specific first. Empty when nothing matched.
+    pub diagnoses: Vec<CrashDiagnosis>,
+}
+
+/// Returns the newest `crash-reports/crash-*.txt` in `game_dir`.
+///
+/// Reports last modified before `since` are ignored, so a report left by an
+/// earlier session is not attributed to the current one.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the directory exists but cannot be read.
+pub fn find_latest_crash_report(
+    game_dir: impl AsRef<Path>,
+    since: Option<SystemTime>,
+) -> Result<Option<PathBuf>> {
+    newest_file(&game_dir.as_ref().join("crash-reports"), since, |name| {
+        name.starts_with("crash-") && name.ends_with(".txt")
+    })
+}
+
+/// Returns the newest `hs_err_pid*.log` in `game_dir`.
+///
+/// Logs last modified before `since` are ignored.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the directory exists but cannot be read.
+pub fn find_latest_jvm_crash_log(
+    game_dir: impl AsRef<Path>,
+    since: Option<SystemTime>,
+) -> Result<Option<PathBuf>> {
+    newest_file(game_dir.as_ref(), since, |name| {
+        name.starts_with("hs_err_pid") && name.ends_with(".log")
+    })
+}
+
+/// Finds and parses crash files in `game_dir` and classifies the crash.
+///
+/// `output` is captured game output, which is also classified; it is the only
+/// source of information for crashes that happen before Minecraft can write a
+/// report, such as an unsupported Java version. Pass
+/// [`crate::process::GameProcess::started_at`] as `since` to ignore files from
+/// earlier sessions.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if crash files exist but cannot be read.
+pub fn analyze_crash(
+    game_dir: impl AsRef<Path>,
+    since: Option<SystemTime>,
+    output: Option<&str>,
+) -> Result<CrashAnalysis> {
+    let game_dir = game_dir.as_ref();
+    let crash_report = find_latest_crash_report(game_dir, since)?
+        .map(CrashReport::read)
+        .transpose()?;
+    let jvm_crash = find_latest_jvm_crash_log(game_dir, since)?
+        .map(JvmCrashLog::read)
+        .transpose()?;
+
+    let mut text = String::new();
+    for source in [
+        crash_report.as_ref().map(|report| report.text.as_str()),
+        jvm_crash.as_ref().map(|log| log.text.as_str()),
+        output,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        text.push_str(source);
+        text.push('\n');
+    }
+
+    Ok(CrashAnalysis {
+        crash_report,
+        jvm_crash,
+        diagnoses: classify_crash(&text),
+    })
+}
+
+/// Classifies crash text against the built-in rule table.
+///
+/// Each distinct cause is reported once. Generic rules, such as a bare
+/// `MixinTransformerError`, only apply when no more specific rule found a
+/// cause of the same kind.
+pub fn classify_crash(text: &str) -> Vec<CrashDiagnosis> {
+    let mut diagnoses: Vec<CrashDiagnosis> = Vec::new();
+    for rule in RULES.iter() {
+        for captures in rule.pattern.captures_iter(text) {
+            let cause = (rule.classify)(&captures);
+            let kind = std::mem::discriminant(&cause);
+            let duplicate = diagnoses.iter().any(|diagnosis| {
+                diagnosis.cause == cause
+                    || (rule.fallback && std::mem::discriminant(&diagnosis.cause) == kind)
+            });
+            if !duplicate {
+                let start = captures.get(0).map_or(0, |m| m.start());
+                diagnoses.push(CrashDiagnosis {
+                    cause,
+                    evidence: line_at(text, start).to_string(),
+                });
+            }
+        }
+    }
+    diagnoses
+}
+
+/// One row of the classification table.
+struct CrashRule {
+    pattern: Regex,
+    /// Only used when no earlier rule found a cause of the same kind.
+    fallback: bool,
+    classify: fn(&Captures<'_>) -> CrashCause,
+}
+
+fn rule(pattern: &str, fallback: bool, classify: fn(&Captures<'_>) -> CrashCause) -> CrashRule {
+    CrashRule {
+        pattern: Regex::new(pattern).unwrap(),
+        fallback,
+        classify,
+    }
+}
+
+static RULES: Lazy<Vec<CrashRule>> = Lazy::new(|| {
+    vec![
+        rule(
+            r"java\.lang\.OutOfMemoryError|There is insufficient memory for the Java Runtime Environment",
+            false,
+            |_| CrashCause::OutOfMemory,
+        ),
+        rule(
+            r"UnsupportedClassVersionError(?:[^\n]*?class file version (?P<required>\d+)[^\n]*?up to (?P<current>\d+))?",
+            false,
+            |captures| CrashCause::UnsupportedJavaVersion {
+                required: class_file_major(captures.name("required")),
+                current: class_file_major(captures.name("current")),
+            },
+        ),
+        rule(
+            r"Mod '[^'\n]*' \((?P<mod>[^)\n]+)\)[^\n]*? requires [^\n]*? of (?:mod )?(?:'[^'\n]*' \((?P<dep>[^)\n]+)\)|(?P<bare>[\w.-]+)), which is missing",
+            false,
+            |captures| CrashCause::MissingModDependency {
+                mod_id: capture(captures, "mod"),
+                dependency: capture(captures, "dep")
+                    .or_else(|| capture(captures, "bare"))
+                    .unwrap_or_default(),
+            },
+        ),
+        rule(
+            r"Mod ID: '(?P<dep>[^'\n]+)', Requested by: '(?P<mod>[^'\n]+)'",
+            false,
+            |captures| CrashCause::MissingModDependency {
+                mod_id: capture(captures, "mod"),
+                dependency: capture(captures, "dep").unwrap_or_default(),
+            },
+        ),
+        rule(
+            r"Mixin apply (?:for mod (?P<mod>[\w.-]+) )?failed (?P<mixin>[^\s]+)",
+            false,
+            |captures| CrashCause::MixinFailure {
+                mod_id: capture(captures, "mod"),
+                mixin: capture(captures, "mixin"),
+            },
+        ),
+        rule(
+            r"Mixin \[(?P<mixin>[^\]\n]+)\][^\n]*? FAILED",
+            false,
+            |captures| CrashCause::MixinFailure {
+                mod_id: None,
+                mixin: capture(captures, "mixin"),
+            },
+        ),
+        rule(
+            r"MixinTransformerError|MixinApplyError|InvalidMixinException",
+            true,
+            |_| CrashCause::MixinFailure {
+                mod_id: None,
+                mixin: None,
+            },
+        ),
+        rule(
+            r"Pixel format not accelerated|GLFW error 6554[23]|WGL: The driver does not appear to support OpenGL|Couldn't set pixel format|No OpenGL context|(?:atio6axx|atioglxx|nvoglv(?:32|64)|ig[0-9a-z]+icd(?:32|64))\.dll|libnvidia-glcore\.so|(?:iris|radeonsi|swrast)_dri\.so",
+            false,
+            |captures| CrashCause::GraphicsDriver {
+                detail: captures[0].to_string(),
+            },
+        ),
+        rule(
+            r"ResolutionException: (?:Modules? [^\n]*?(?:export package|reads more than one module)[^\n]*|[^\n]*?duplicate[^\n]*)",
+            false,
+            |captures| CrashCause::DuplicateModule {
+                detail: captures[0].trim().to_string(),
+            },
+        ),
+    ]
+});
+
+/// Converts a class file version such as `61` to its Java major version.
+fn class_file_major(version: Option<regex::Match<'_>>) -> Option<u32> {
+    version?.as_str().parse::<u32>().ok()?.checked_sub(44)
+}
+
+fn capture(captures: &Captures<'_>, name: &str) -> Option<String> {
+    captures.name(name).map(|m| m.as_str().to_string())
+}
+
+fn line_at(text: &str, offset: usize) -> &str {
+    let start = text[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let end = text[offset..]
+        .find('\n')
+        .map_or(text.len(), |index| offset + index);
+    text[start..end].trim()
+}
+
+fn section_title(line: &str) -> Option<&str> {
+    let title = line.strip_prefix("-- ")?.strip_suffix(" --")?;
+    (!title.is_empty()).then_some(title)
+}
+
+fn parse_details(body: &str) -> BTreeMap<String, String> {
+    let mut details: BTreeMap<String, String> = BTreeMap::new();
+    let mut current: Option<String> = None;
+    for line in body.lines() {
+        if line.starts_with("\t\t") {
+            if let Some(value) = current.as_ref().and_then(|key| details.get_mut(key)) {
+                if !value.is_empty() {
+                    value.push('\n');
+                }
+                value.push_str(line.trim());
+            }
+        } else if let Some((key, value)) = line.strip_prefix('\t').and_then(|l| l.split_once(':')) {
+            let key = key.trim().to_string();
+            details.insert(key.clone(), value.trim().to_string());
+            current = Some(key);
+        }
+    }
+    details
+}
+
+fn parse_mods(details: &BTreeMap<String, String>) -> Vec<CrashReportMod> {
+    if let Some(list) = details.get("Mod List") {
+        // Forge: `file.jar |Name |id |version |state |Manifest: ...`
+        return list
+            .lines()
+            .filter_map(|line| {
+                let columns: Vec<&str> = line.split('|').map(str::trim).collect();
+                let id = columns.get(2).filter(|id| !id.is_empty())?;
+                Some(CrashReportMod {
+                    id: id.to_string(),
+                    name: columns.get(1).map(|name| name.to_string()),
+                    version: columns.get(3).map(|version| version.to_string()),
+                })
+            })
+            .collect();
+    }
+    // Fabric and Quilt: `id: Name version`
+    details
+        .get("Fabric Mods")
+        .map(|list| {
+            list.lines()
+                .filter_map(|line| {
+                    let (id, rest) = line.split_once(':')?;
+                    let (name, version) = match rest.trim().rsplit_once(' ') {
+                        Some((name, version)) => {
+                            (Some(name.to_string()), Some(version.to_string()))
+                        }
+                        None => (None, Some(rest.trim().to_string())),
+                    };
+                    Some(CrashReportMod {
+                        id: id.trim().to_string(),
+                        name,
+                        version,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn newest_file(
+    dir: &Path,
+    since: Option<SystemTime>,
+    matches: impl Fn(&str) -> bool,
+) -> Result<Option<PathBuf>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let mut newest: Option<(SystemTime, PathBuf)> = None;
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_name().to_str().is_some_and(&matches) {
+            continue;
+        }
+        let modified = entry.metadata()?.modified()?;
+        if since.is_some_and(|since| modified < since) {
+            continue;
+        }
+        if newest.as_ref().is_none_or(|(time, _)| modified > *time) {
+            newest = Some((modified, entry.path()));
+        }
+    }
+    Ok(newest.map(|(_, path)| path))
+}
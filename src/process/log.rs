@@ -0,0 +1,268 @@
+//! Structured parsing of game log output.
+//!
+//! With the Mojang client logging configuration, the game writes each log
+//! record to standard output as a `<log4j:Event>` XML fragment that usually
+//! spans several lines. Versions launched without that configuration, and many
+//! modded setups, print plain text such as
+//! `[12:34:56] [Render thread/INFO]: Setting user: Steve` instead.
+//! [`LogParser`] accepts both forms line by line and produces [`LogEvent`]s.
+
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+const EVENT_START: &str = "<log4j:Event";
+const EVENT_END: &str = "</log4j:Event>";
+
+static ATTRIBUTE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"([A-Za-z_:]+)\s*=\s*"([^"]*)""#).unwrap());
+static PLAIN_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\[(?P<time>[^\]]+)\] \[(?P<thread>.+?)/(?P<level>[A-Za-z]+)\](?: \[(?P<logger>[^\]]+)\])?:? ?(?P<message>.*)$",
+    )
+    .unwrap()
+});
+
+/// Log4j severity, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    /// `TRACE`.
+    Trace,
+    /// `DEBUG`.
+    Debug,
+    /// `INFO`.
+    Info,
+    /// `WARN`.
+    Warn,
+    /// `ERROR`.
+    Error,
+    /// `FATAL`.
+    Fatal,
+}
+
+impl FromStr for LogLevel {
+    type Err = ();
+
+    fn from_str(level: &str) -> Result<Self, Self::Err> {
+        match level.to_ascii_uppercase().as_str() {
+            "TRACE" => Ok(Self::Trace),
+            "DEBUG" => Ok(Self::Debug),
+            "INFO" => Ok(Self::Info),
+            "WARN" | "WARNING" => Ok(Self::Warn),
+            "ERROR" | "SEVERE" => Ok(Self::Error),
+            "FATAL" => Ok(Self::Fatal),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Trace => "TRACE",
+            Self::Debug => "DEBUG",
+            Self::Info => "INFO",
+            Self::Warn => "WARN",
+            Self::Error => "ERROR",
+            Self::Fatal => "FATAL",
+        })
+    }
+}
+
+/// One log record written by the game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEvent {
+    /// Time the record was logged.
+    ///
+    /// Only XML events carry a full timestamp; plain-text lines only print a
+    /// time of day and leave this empty.
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Record severity. Plain-text lines without a recognizable level are
+    /// reported as [`LogLevel::Info`].
+    pub level: LogLevel,
+    /// Logger name, when reported.
+    pub logger: Option<String>,
+    /// Thread that logged the record, when reported.
+    pub thread: Option<String>,
+    /// Log message.
+    pub message: String,
+    /// Formatted exception and stack trace attached to the record.
+    pub throwable: Option<String>,
+}
+
+impl LogEvent {
+    /// Builds an event from a plain-text output line.
+    ///
+    /// Lines in the vanilla `[time] [thread/LEVEL]: message` layout, including
+    /// Forge's `[time] [thread/LEVEL] [logger]: message` variant, are split
+    /// into their fields. Any other line becomes an `INFO` event whose message
+    /// is the whole line.
+    pub fn from_plain_line(line: &str) -> Self {
+        let line = line.trim_end_matches(['\r', '\n']);
+        if let Some(captures) = PLAIN_LINE.captures(line) {
+            if let Ok(level) = captures["level"].parse() {
+                return Self {
+                    timestamp: None,
+                    level,
+                    logger: captures.name("logger").map(|m| m.as_str().to_string()),
+                    thread: Some(captures["thread"].to_string()),
+                    message: captures["message"].to_string(),
+                    throwable: None,
+                };
+            }
+        }
+        Self {
+            timestamp: None,
+            level: LogLevel::Info,
+            logger: None,
+            thread: None,
+            message: line.to_string(),
+            throwable: None,
+        }
+    }
+}
+
+/// Streaming parser for game output.
+///
+/// Feed output one line at a time with [`LogParser::push_line`]. Lines inside
+/// a `<log4j:Event>` fragment are buffered until the closing tag; every other
+/// non-empty line is returned immediately as a plain-text event. Call
+/// [`LogParser::finish`] once the output ends to flush an incomplete fragment.
+///
+/// ```
+/// use mc_launcher_core::process::log::{LogLevel, LogParser};
+///
+/// let mut parser = LogParser::new();
+/// assert!(parser
+///     .push_line(r#"<log4j:Event logger="dhl" timestamp="1700000000000" level="WARN" thread="main">"#)
+///     .is_empty());
+/// parser.push_line("  <log4j:Message><![CDATA[Slow tick]]></log4j:Message>");
+/// let events = parser.push_line("</log4j:Event>");
+///
+/// assert_eq!(events[0].level, LogLevel::Warn);
+/// assert_eq!(events[0].message, "Slow tick");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LogParser {
+    pending: Option<String>,
+}
+
+impl LogParser {
+    /// Creates a parser with no buffered output.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses one line of output and returns the events it completes.
+    pub fn push_line(&mut self, line: &str) -> Vec<LogEvent> {
+        let mut events = Vec::new();
+        let mut rest = line.trim_end_matches(['\r', '\n']);
+        loop {
+            if let Some(pending) = &mut self.pending {
+                let Some(end) = rest.find(EVENT_END) else {
+                    pending.push_str(rest);
+                    pending.push('\n');
+                    return events;
+                };
+                pending.push_str(&rest[..end]);
+                events.push(parse_xml_event(pending));
+                self.pending = None;
+                rest = &rest[end + EVENT_END.len()..];
+            } else {
+                let start = rest.find(EVENT_START).unwrap_or(rest.len());
+                let (before, after) = rest.split_at(start);
+                if !before.trim().is_empty() {
+                    events.push(LogEvent::from_plain_line(before));
+                }
+                if after.is_empty() {
+                    return events;
+                }
+                self.pending = Some(String::new());
+                rest = after;
+            }
+        }
+    }
+
+    /// Flushes an unterminated `<log4j:Event>` fragment as a plain-text event.
+    pub fn finish(&mut self) -> Option<LogEvent> {
+        let pending = self.pending.take()?;
+        (!pending.trim().is_empty()).then(|| LogEvent::from_plain_line(pending.trim()))
+    }
+}
+
+/// Parses complete game output into events.
+pub fn parse_log_output(output: &str) -> Vec<LogEvent> {
+    let mut parser = LogParser::new();
+    let mut events: Vec<LogEvent> = output
+        .lines()
+        .flat_map(|line| parser.push_line(line))
+        .collect();
+    events.extend(parser.finish());
+    events
+}
+
+/// Parses the text of one `<log4j:Event ...> ... ` fragment without its
+/// closing tag.
+fn parse_xml_event(fragment: &str) -> LogEvent {
+    let header_end = fragment.find('>').unwrap_or(fragment.len());
+    let header = &fragment[..header_end];
+    let body = fragment.get(header_end + 1..).unwrap_or_default();
+
+    let mut event = LogEvent {
+        timestamp: None,
+        level: LogLevel::Info,
+        logger: None,
+        thread: None,
+        message: String::new(),
+        throwable: None,
+    };
+    for captures in ATTRIBUTE.captures_iter(header) {
+        let value = unescape(&captures[2]);
+        match &captures[1] {
+            "logger" => event.logger = Some(value),
+            "thread" => event.thread = Some(value),
+            "level" => event.level = value.parse().unwrap_or(LogLevel::Info),
+            "timestamp" => {
+                event.timestamp = value
+                    .parse()
+                    .ok()
+                    .and_then(DateTime::<Utc>::from_timestamp_millis);
+            }
+            _ => {}
+        }
+    }
+    event.message = element_text(body, "log4j:Message").unwrap_or_default();
+    event.throwable = element_text(body, "log4j:Throwable");
+    event
+}
+
+/// Returns the text content of the first `<name>` element in `body`.
+fn element_text(body: &str, name: &str) -> Option<String> {
+    let open = format!("<{name}>");
+    let close = format!("</{name}>");
+    let start = body.find(&open)? + open.len();
+    let end = start + body[start..].find(&close)?;
+    let content = &body[start..end];
+
+    let mut text = String::new();
+    let mut rest = content;
+    while let Some(cdata) = rest.find("<![CDATA[") {
+        text.push_str(&unescape(&rest[..cdata]));
+        let data = &rest[cdata + "<![CDATA[".len()..];
+        let data_end = data.find("]]>").unwrap_or(data.len());
+        text.push_str(&data[..data_end]);
+        rest = data.get(data_end + "]]>".len()..).unwrap_or_default();
+    }
+    text.push_str(&unescape(rest));
+    Some(text)
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,239 @@
+//! Supervision of a running game process.
+//!
+//! [`GameProcess`] spawns a [`LaunchCommand`] with piped output. Standard output
+//! and standard error are read on background threads and delivered line by
+//! line, in arrival order, through a single channel, so a launcher UI can show a
+//! live log without managing pipes itself. [`log`] turns those lines into
+//! structured log events, and [`crash`] explains why the game exited.
+
+pub mod crash;
+pub mod log;
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{command::builder::LaunchCommand, Result};
+
+/// Interval between exit checks in [`GameProcess::wait_timeout`].
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Output stream a [`GameOutputLine`] was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameOutputStream {
+    /// Standard output.
+    Stdout,
+    /// Standard error.
+    Stderr,
+}
+
+/// One line of game output, without its trailing newline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameOutputLine {
+    /// Stream the line was read from.
+    pub stream: GameOutputStream,
+    /// Line text; invalid UTF-8 is replaced lossily.
+    pub line: String,
+}
+
+/// Handle to a spawned game process.
+///
+/// Dropping the handle does not stop the game; call [`GameProcess::kill`] to
+/// stop it.
+#[derive(Debug)]
+pub struct GameProcess {
+    child: Child,
+    output: Receiver<GameOutputLine>,
+    started_at: SystemTime,
+    started: Instant,
+    exit: Option<(ExitStatus, Instant)>,
+}
+
+impl GameProcess {
+    /// Spawns `command` with piped standard output and standard error.
+    ///
+    /// The working directory is created if it does not exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the working directory cannot be
+    /// created or the executable cannot be started.
+    pub fn spawn(command: &LaunchCommand) -> Result<Self> {
+        fs::create_dir_all(&command.working_dir)?;
+        let mut child = Command::new(&command.executable)
+            .args(&command.args)
+            .current_dir(&command.working_dir)
+            .envs(command.env.iter().map(|(key, value)| (key, value)))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let started = Instant::now();
+        let started_at = SystemTime::now();
+
+        let (sender, output) = mpsc::channel();
+        if let Some(stdout) = child.stdout.take() {
+            forward_lines(stdout, GameOutputStream::Stdout, sender.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            forward_lines(stderr, GameOutputStream::Stderr, sender);
+        }
+
+        Ok(Self {
+            child,
+            output,
+            started_at,
+            started,
+            exit: None,
+        })
+    }
+
+    /// Returns the operating system process id.
+    pub fn pid(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Returns the wall-clock time the process was spawned.
+    pub fn started_at(&self) -> SystemTime {
+        self.started_at
+    }
+
+    /// Returns how long the game has been running.
+    ///
+    /// Once an exit has been observed, this is the time between spawning and
+    /// that observation.
+    pub fn play_duration(&self) -> Duration {
+        match self.exit {
+            Some((_, exited)) => exited - self.started,
+            None => self.started.elapsed(),
+        }
+    }
+
+    /// Returns the exit status if the process has already exited.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the process status cannot be queried.
+    pub fn try_wait(&mut self) -> Result<Option<ExitStatus>> {
+        if self.exit.is_none() {
+            if let Some(status) = self.child.try_wait()? {
+                self.exit = Some((status, Instant::now()));
+            }
+        }
+        Ok(self.exit_status())
+    }
+
+    /// Returns the exit status observed by a previous wait, if any.
+    pub fn exit_status(&self) -> Option<ExitStatus> {
+        self.exit.map(|(status, _)| status)
+    }
+
+    /// Blocks until the process exits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if waiting on the process fails.
+    pub fn wait(&mut self) -> Result<ExitStatus> {
+        if let Some(status) = self.exit_status() {
+            return Ok(status);
+        }
+        let status = self.child.wait()?;
+        self.exit = Some((status, Instant::now()));
+        Ok(status)
+    }
+
+    /// Blocks until the process exits or `timeout` elapses.
+    ///
+    /// Returns `Ok(None)` if the process is still running after `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the process status cannot be queried.
+    pub fn wait_timeout(&mut self, timeout: Duration) -> Result<Option<ExitStatus>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(status) = self.try_wait()? {
+                return Ok(Some(status));
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            thread::sleep(POLL_INTERVAL.min(deadline - now));
+        }
+    }
+
+    /// Kills the process and waits for it to exit.
+    ///
+    /// Killing a process that already exited is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the process cannot be signalled or
+    /// waited on.
+    pub fn kill(&mut self) -> Result<ExitStatus> {
+        if self.try_wait()?.is_none() {
+            self.child.kill()?;
+        }
+        self.wait()
+    }
+
+    /// Returns the next output line, blocking until one is available.
+    ///
+    /// Returns `None` once both streams are closed and every line has been
+    /// received.
+    pub fn next_line(&self) -> Option<GameOutputLine> {
+        self.output.recv().ok()
+    }
+
+    /// Returns the next output line, waiting at most `timeout`.
+    ///
+    /// Returns `None` on timeout or once both streams are closed.
+    pub fn next_line_timeout(&self, timeout: Duration) -> Option<GameOutputLine> {
+        self.output.recv_timeout(timeout).ok()
+    }
+
+    /// Returns output lines that have already arrived without blocking.
+    pub fn pending_lines(&self) -> Vec<GameOutputLine> {
+        self.output.try_iter().collect()
+    }
+
+    /// Returns a blocking iterator over output lines that ends once both
+    /// streams are closed.
+    pub fn lines(&self) -> impl Iterator<Item = GameOutputLine> + '_ {
+        self.output.iter()
+    }
+}
+
+fn forward_lines(
+    stream: impl Read + Send + 'static,
+    source: GameOutputStream,
+    sender: Sender<GameOutputLine>,
+) {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(stream);
+        let mut buffer = Vec::new();
+        loop {
+            buffer.clear();
+            match reader.read_until(b'\n', &mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let line = String::from_utf8_lossy(&buffer);
+                    let line = line.trim_end_matches(['\n', '\r']).to_string();
+                    let line = GameOutputLine {
+                        stream: source,
+                        line,
+                    };
+                    if sender.send(line).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
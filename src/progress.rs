@@ -4,7 +4,7 @@
 //! [`crate::launcher::Launcher::install_with_progress`] because closures that
 //! accept [`ProgressEvent`] automatically implement [`ProgressReporter`].
 
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
 /// Coarse install stage.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -23,6 +23,8 @@ pub enum InstallStage {
     LoaderInstall,
     /// Verify installed files.
     Verify,
+    /// Download modpack files and copy modpack overrides.
+    ModpackFiles,
 }
 
 /// Reason a download task was skipped.
@@ -32,6 +34,8 @@ pub enum SkipReason {
     ChecksumMatched,
     /// Existing file was accepted because no checksum was available.
     FileExistsWithoutChecksum,
+    /// An earlier task in the same plan already writes this destination.
+    DuplicateDestination,
 }
 
 /// Event emitted during install and download operations.
@@ -70,6 +74,19 @@ pub enum ProgressEvent {
         /// Total byte count when the server reported it.
         total: Option<u64>,
     },
+    /// A task attempt failed and will be retried after a delay.
+    TaskRetrying {
+        /// Human-readable task label.
+        label: String,
+        /// Attempt that failed, starting at `1`.
+        attempt: u32,
+        /// Total attempts allowed by the retry policy.
+        max_attempts: u32,
+        /// Delay before the next attempt starts.
+        delay: Duration,
+        /// Display form of the error that caused the retry.
+        reason: String,
+    },
 }
 
 /// Receives installation progress events.
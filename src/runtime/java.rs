@@ -0,0 +1,345 @@
+//! Java installation discovery and selection.
+//!
+//! Installations are described by the `release` file in the Java home when one
+//! exists, so discovery does not need to start a JVM for each candidate. Java
+//! homes without a `release` file are probed with
+//! `java -XshowSettings:properties -version`.
+
+use std::{
+    collections::HashSet,
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::{
+    compatibility::JavaRuntimeHint,
+    core::version::JavaVersion,
+    install::runtime::{find_java_executable, runtime_platform_key},
+    platform::{Arch, Platform},
+    LauncherError, Result,
+};
+
+/// Directories scanned for Java homes on Linux.
+pub const LINUX_JVM_DIRS: &[&str] = &["/usr/lib/jvm", "/usr/lib64/jvm", "/usr/java", "/opt/java"];
+
+/// Where a [`JavaInstallation`] was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JavaSource {
+    /// The `JAVA_HOME` environment variable.
+    JavaHome,
+    /// A directory listed in `PATH`.
+    Path,
+    /// A system JVM directory such as `/usr/lib/jvm`.
+    SystemDirectory,
+    /// A Mojang runtime under `<minecraft_dir>/runtime`.
+    MojangRuntime {
+        /// Runtime component name, such as `java-runtime-gamma`.
+        component: String,
+    },
+}
+
+/// A Java runtime found on this machine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaInstallation {
+    /// `java` executable.
+    pub executable: PathBuf,
+    /// Java home that contains `bin/java`.
+    pub home: PathBuf,
+    /// Version string as reported by the runtime, such as `1.8.0_392` or
+    /// `17.0.8`.
+    pub version: String,
+    /// Java major version, such as `8` or `17`.
+    pub major: u32,
+    /// Java minor version; `0` for most releases.
+    pub minor: u32,
+    /// Runtime vendor, when reported.
+    pub vendor: Option<String>,
+    /// CPU architecture the runtime was built for.
+    pub arch: Arch,
+    /// Where the installation was found.
+    pub source: JavaSource,
+}
+
+impl JavaInstallation {
+    /// Describes the Java home that contains `executable`.
+    ///
+    /// The `release` file is read when present; otherwise the executable is
+    /// run with `-XshowSettings:properties -version`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::LauncherError`] if the executable cannot be run or its
+    /// output contains no parseable version.
+    pub fn probe(executable: impl Into<PathBuf>, source: JavaSource) -> Result<Self> {
+        let executable = executable.into();
+        let home = executable
+            .parent()
+            .and_then(Path::parent)
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+
+        let properties = match fs::read_to_string(home.join("release")) {
+            Ok(release) => parse_release_file(&release),
+            Err(_) => {
+                let output = Command::new(&executable)
+                    .arg("-XshowSettings:properties")
+                    .arg("-version")
+                    .output()?;
+                parse_settings_output(&String::from_utf8_lossy(&output.stderr))
+            }
+        };
+
+        let version = properties
+            .version
+            .ok_or_else(|| LauncherError::MissingField {
+                context: format!("java at {}", executable.display()),
+                field: "java.version".to_string(),
+            })?;
+        let (major, minor) = parse_java_version(&version).ok_or_else(|| LauncherError::Other {
+            message: format!("unrecognized java version {version:?}"),
+        })?;
+        Ok(Self {
+            executable,
+            home,
+            version,
+            major,
+            minor,
+            vendor: properties.vendor,
+            arch: properties.arch.as_deref().map_or(Arch::Other, parse_arch),
+            source,
+        })
+    }
+}
+
+/// Splits a Java version string into major and minor numbers.
+///
+/// Both the legacy `1.8.0_392` scheme and the modern `17.0.8` scheme are
+/// understood. Pre-release suffixes such as `-ea` are ignored.
+///
+/// ```
+/// use mc_launcher_core::runtime::java::parse_java_version;
+///
+/// assert_eq!(parse_java_version("1.8.0_392"), Some((8, 0)));
+/// assert_eq!(parse_java_version("17.0.8"), Some((17, 0)));
+/// assert_eq!(parse_java_version("21-ea"), Some((21, 0)));
+/// ```
+pub fn parse_java_version(version: &str) -> Option<(u32, u32)> {
+    let mut numbers = version
+        .split(|c: char| !c.is_ascii_digit())
+        .take_while(|part| !part.is_empty())
+        .map(|part| part.parse::<u32>().ok());
+    let first = numbers.next()??;
+    let second = numbers.next().flatten();
+    if first == 1 {
+        let major = second?;
+        let minor = numbers.next().flatten().unwrap_or(0);
+        Some((major, minor))
+    } else {
+        Some((first, second.unwrap_or(0)))
+    }
+}
+
+/// Maps a Java `os.arch` value to an [`Arch`].
+pub fn parse_arch(arch: &str) -> Arch {
+    match arch.trim() {
+        "amd64" | "x86_64" | "x64" => Arch::X86_64,
+        "aarch64" | "arm64" => Arch::Aarch64,
+        "x86" | "i386" | "i486" | "i586" | "i686" => Arch::X86,
+        _ => Arch::Other,
+    }
+}
+
+/// Properties read from a `release` file or `-XshowSettings` output.
+#[derive(Debug, Default)]
+struct JavaProperties {
+    version: Option<String>,
+    vendor: Option<String>,
+    arch: Option<String>,
+}
+
+fn parse_release_file(contents: &str) -> JavaProperties {
+    let mut properties = JavaProperties::default();
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = Some(value.trim().trim_matches('"').to_string());
+        match key.trim() {
+            "JAVA_VERSION" => properties.version = value,
+            "IMPLEMENTOR" => properties.vendor = value,
+            "OS_ARCH" => properties.arch = value,
+            _ => {}
+        }
+    }
+    properties
+}
+
+fn parse_settings_output(output: &str) -> JavaProperties {
+    let mut properties = JavaProperties::default();
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(" = ") else {
+            continue;
+        };
+        let value = Some(value.trim().to_string());
+        match key.trim() {
+            "java.version" => properties.version = value,
+            "java.vendor" => properties.vendor = value,
+            "os.arch" => properties.arch = value,
+            _ => {}
+        }
+    }
+    properties
+}
+
+/// Locations searched by [`JavaSearch::discover`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JavaSearch {
+    /// Value of `JAVA_HOME`.
+    pub java_home: Option<PathBuf>,
+    /// Directories from `PATH`.
+    pub path: Vec<PathBuf>,
+    /// Directories whose children are Java homes.
+    pub system_dirs: Vec<PathBuf>,
+    /// Minecraft directory whose `runtime/` holds Mojang runtimes.
+    pub minecraft_dir: Option<PathBuf>,
+}
+
+impl JavaSearch {
+    /// Builds a search from `JAVA_HOME`, `PATH`, [`LINUX_JVM_DIRS`] on Linux,
+    /// and the Mojang runtimes of `minecraft_dir`.
+    pub fn from_env(minecraft_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            java_home: env::var_os("JAVA_HOME").map(PathBuf::from),
+            path: env::var_os("PATH")
+                .map(|path| env::split_paths(&path).collect())
+                .unwrap_or_default(),
+            system_dirs: if cfg!(target_os = "linux") {
+                LINUX_JVM_DIRS.iter().map(PathBuf::from).collect()
+            } else {
+                Vec::new()
+            },
+            minecraft_dir: Some(minecraft_dir.into()),
+        }
+    }
+
+    /// Finds Java installations in search order.
+    ///
+    /// Mojang runtimes come first, followed by `JAVA_HOME`, `PATH`, and system
+    /// directories. An executable reached through several locations, including
+    /// through symlinks, is reported once. Candidates that cannot be probed are
+    /// skipped.
+    pub fn discover(&self) -> Vec<JavaInstallation> {
+        let mut candidates = Vec::new();
+        if let Some(minecraft_dir) = &self.minecraft_dir {
+            candidates.extend(mojang_runtime_candidates(minecraft_dir));
+        }
+        if let Some(home) = &self.java_home {
+            candidates.extend(find_java_executable(home).map(|java| (java, JavaSource::JavaHome)));
+        }
+        for dir in &self.path {
+            for name in ["java", "java.exe"] {
+                let java = dir.join(name);
+                if java.is_file() {
+                    candidates.push((java, JavaSource::Path));
+                }
+            }
+        }
+        for dir in &self.system_dirs {
+            for home in sorted_child_dirs(dir) {
+                candidates.extend(
+                    find_java_executable(&home).map(|java| (java, JavaSource::SystemDirectory)),
+                );
+            }
+        }
+
+        let mut seen = HashSet::new();
+        candidates
+            .into_iter()
+            .filter(|(java, _)| seen.insert(fs::canonicalize(java).unwrap_or(java.clone())))
+            .filter_map(|(java, source)| {
+                // `PATH` usually holds a symlink; describe the real Java home.
+                let java = match source {
+                    JavaSource::Path => fs::canonicalize(&java).unwrap_or(java),
+                    _ => java,
+                };
+                JavaInstallation::probe(java, source).ok()
+            })
+            .collect()
+    }
+}
+
+/// Finds Java installations with [`JavaSearch::from_env`].
+pub fn discover_java_installations(minecraft_dir: impl Into<PathBuf>) -> Vec<JavaInstallation> {
+    JavaSearch::from_env(minecraft_dir).discover()
+}
+
+fn sorted_child_dirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_dir())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    dirs.sort();
+    dirs
+}
+
+fn mojang_runtime_candidates(minecraft_dir: &Path) -> Vec<(PathBuf, JavaSource)> {
+    let Some(key) = runtime_platform_key(Platform::current()) else {
+        return Vec::new();
+    };
+    sorted_child_dirs(&minecraft_dir.join("runtime"))
+        .into_iter()
+        .filter_map(|component_dir| {
+            let component = component_dir.file_name()?.to_str()?.to_string();
+            let java = find_java_executable(component_dir.join(key).join(&component))?;
+            Some((java, JavaSource::MojangRuntime { component }))
+        })
+        .collect()
+}
+
+/// Picks the best installation for a version's Java requirement.
+///
+/// The required major version comes from `hint` when present, otherwise from
+/// `requirement`. An exact major match is preferred. Versions that require
+/// Java 16 or newer also accept the closest newer major; Java 8 versions only
+/// accept Java 8. A `hint` additionally requires its architecture. Among equal
+/// candidates, the current platform's architecture, a Mojang runtime, and a
+/// newer minor version are preferred, in that order.
+///
+/// Without any requirement the newest installation for the current
+/// architecture is returned.
+pub fn select_java_installation<'a>(
+    installations: &'a [JavaInstallation],
+    requirement: Option<&JavaVersion>,
+    hint: Option<&JavaRuntimeHint>,
+) -> Option<&'a JavaInstallation> {
+    let required = hint
+        .map(|hint| hint.major_version)
+        .or_else(|| requirement.map(|java| java.major_version))
+        .and_then(|major| u32::try_from(major).ok());
+    let current_arch = Platform::current().arch;
+
+    installations
+        .iter()
+        .filter(|java| hint.is_none_or(|hint| java.arch == hint.arch))
+        .filter(|java| match required {
+            Some(required) if required >= 16 => java.major >= required,
+            Some(required) => java.major == required,
+            None => true,
+        })
+        .min_by_key(|java| {
+            (
+                match required {
+                    Some(required) => java.major - required,
+                    None => u32::MAX - java.major,
+                },
+                java.arch != current_arch,
+                !matches!(java.source, JavaSource::MojangRuntime { .. }),
+                u32::MAX - java.minor,
+            )
+        })
+}
@@ -1,327 +1,359 @@
-//! Legacy Java runtime discovery and installation helpers.
+//! Mojang Java runtime management.
 //!
-//! The current high-level launch facade expects callers to choose Java and pass
-//! it through [`crate::command::builder::LaunchOptions::java_executable`]. These
-//! helpers remain for callers that still use the older runtime API.
+//! [`crate::install::runtime`] plans and installs runtime files. This module
+//! builds on it to list, locate, verify, and repair runtimes installed under
+//! `<minecraft_dir>/runtime`. [`java`] discovers and selects Java
+//! installations outside of Mojang's runtimes as well. The `get_*` and
+//! `install_jvm_runtime` functions are deprecated wrappers kept for callers of
+//! the older runtime API.
 
 use std::{
-    collections::HashMap,
-    env, fs,
-    io::Write,
+    fs,
+    io::{BufWriter, Write},
     path::{Path, PathBuf},
-    process::Command,
+    time::UNIX_EPOCH,
 };
 
 use chrono::DateTime;
-use reqwest::header;
+
+pub mod java;
 
 use crate::{
-    types::{
-        runtime_types::{PlatformManifestJson, RuntimeListJson},
-        CallbackDict, JvmRuntimeInformation, VersionRuntimeInformation,
-    },
-    utils::helper::{
-        check_path_inside_minecraft_directory, download_file, get_client_json, get_sha1_hash,
-        get_user_agent,
+    install::{
+        client::load_version_json,
+        runtime::{
+            fetch_runtime_index_with_mirrors, find_java_executable, install_runtime_with_options,
+            runtime_home, runtime_platform_dir, runtime_platform_key, select_runtime_release,
+            RuntimeFile, RuntimeManifest, RuntimeRelease,
+        },
     },
+    io::{hash::sha1_file, paths::safe_join},
+    net::{download::DownloadOptions, mirror::MirrorConfig},
+    platform::Platform,
+    progress::{ProgressEvent, ProgressReporter},
+    types::{CallbackDict, JvmRuntimeInformation, VersionRuntimeInformation},
+    LauncherError, Result,
 };
 
-const JVM_MANIFEST_URL: &str = "https://launchermeta.mojang.com/v1/products/java-runtime/2ec0cc96c44e5a76b9c8b7c39df7210883d12871/all.json";
+/// Separator between the path and hash columns of a runtime `.sha1` file.
+const SHA1_SEPARATOR: &str = " /#// ";
 
-fn get_jvm_platform_string() -> String {
-    let os = env::consts::OS;
-    let arch = env::consts::ARCH;
-
-    match (os, arch) {
-        ("windows", "x86") => "windows-x86".to_string(),
-        ("windows", "aarch64") => "windows-arm64".to_string(),
-        ("windows", _) => "windows-x64".to_string(),
-        ("linux", "x86") => "linux-i386".to_string(),
-        ("linux", _) => "linux".to_string(),
-        ("macos", "aarch64") => "mac-os-arm64".to_string(),
-        ("macos", _) => "mac-os".to_string(),
-        _ => "gamecore".to_string(),
-    }
+/// One entry of a runtime `.sha1` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeFileHash {
+    /// Path relative to the Java home.
+    pub path: String,
+    /// Expected SHA-1 digest.
+    pub sha1: String,
 }
 
-pub fn get_jvm_runtimes() -> Result<Vec<String>, Box<dyn std::error::Error>> {
-    let client = reqwest::blocking::Client::new();
-    let response = client
-        .get(JVM_MANIFEST_URL)
-        .header(header::USER_AGENT, get_user_agent())
-        .send()?;
-    let manifest_data: RuntimeListJson = response.json()?;
-    let platform_string = get_jvm_platform_string();
-    if let Some(platform_data) = manifest_data.get(&platform_string) {
-        let jvm_list: Vec<String> = platform_data.keys().cloned().collect();
-        Ok(jvm_list)
-    } else {
-        Err("Platform not found in manifest".into())
-    }
+/// Result of checking an installed runtime against its `.sha1` file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuntimeVerification {
+    /// Number of files listed in the `.sha1` file.
+    pub checked: usize,
+    /// Listed files that do not exist.
+    pub missing: Vec<PathBuf>,
+    /// Listed files whose SHA-1 digest differs.
+    pub corrupt: Vec<PathBuf>,
 }
 
-pub fn get_installed_jvm_runtimes(minecraft_directory: impl AsRef<Path>) -> Vec<String> {
-    let runtime_dir = minecraft_directory.as_ref().join("runtime");
-    match fs::read_dir(runtime_dir) {
-        Ok(entries) => entries
-            .filter_map(|entry| entry.ok().and_then(|e| e.file_name().into_string().ok()))
-            .collect(),
-        Err(_) => Vec::new(),
+impl RuntimeVerification {
+    /// Returns true when every listed file exists and matches its hash.
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.corrupt.is_empty()
     }
 }
 
-pub fn install_jvm_runtime(
-    jvm_version: &str,
-    minecraft_directory: impl AsRef<Path>,
-    callback: &CallbackDict,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let client = reqwest::blocking::Client::new();
-    let manifest_data: RuntimeListJson = client
-        .get(JVM_MANIFEST_URL)
-        .header(header::USER_AGENT, get_user_agent())
-        .send()?
-        .json()?;
-    let platform_string = get_jvm_platform_string();
-
-    // Check if the JVM version exists
-    if !manifest_data
-        .get(&platform_string)
-        .unwrap_or(&HashMap::new())
-        .contains_key(jvm_version)
-    {
-        return Err(format!("jvm version not found: {}", jvm_version).into());
-    }
-
-    // Check if there is a platform manifest
-    if manifest_data
-        .get(&platform_string)
-        .unwrap_or(&HashMap::new())
-        .get(jvm_version)
-        .unwrap_or(&Vec::new())
-        .is_empty()
-    {
-        return Err("platform manifest not exist.".into());
-    }
-    let platform_manifest_url = manifest_data
-        .get(&platform_string)
-        .unwrap()
-        .get(jvm_version)
-        .unwrap()[0]
-        .manifest
-        .url
-        .clone();
-    let platform_manifest: PlatformManifestJson = client
-        .get(platform_manifest_url)
-        .header(header::USER_AGENT, get_user_agent())
-        .send()?
-        .json()?;
-    let base_path = minecraft_directory
-        .as_ref()
-        .join("runtime")
-        .join(jvm_version)
-        .join(&platform_string)
-        .join(jvm_version);
+/// Lists the runtime components Mojang publishes for a platform.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the runtime index cannot be fetched or
+/// Mojang publishes no runtimes for `platform`.
+pub fn list_available_runtimes(platform: Platform, mirrors: &MirrorConfig) -> Result<Vec<String>> {
+    let key = runtime_platform_key(platform).ok_or_else(|| unsupported(platform))?;
+    let index = fetch_runtime_index_with_mirrors(mirrors)?;
+    Ok(index
+        .get(key)
+        .map(|components| {
+            components
+                .iter()
+                .filter(|(_, releases)| !releases.is_empty())
+                .map(|(component, _)| component.clone())
+                .collect()
+        })
+        .unwrap_or_default())
+}
 
-    // Download all files of the runtime
-    if let Some(set_max) = callback.set_max {
-        set_max(platform_manifest.files.len() as i32 - 1);
-    }
-    let mut count = 0;
-    let mut file_list: Vec<&String> = vec![];
-    for (key, value) in platform_manifest.files.iter() {
-        let current_path = base_path.join(key);
-        check_path_inside_minecraft_directory(&minecraft_directory, &current_path)?;
-        if let Some(vtype) = &value.r#type {
-            if vtype == "file" {
-                if let Some(download_info) = &value.downloads {
-                    if download_info.contains_key("lzma") {
-                        download_file(
-                            &download_info.get("lzma").unwrap().url,
-                            &current_path,
-                            Some(download_info.get("raw").unwrap().sha1.as_str()),
-                            true,
-                            None::<&Path>,
-                            Some(&client),
-                            callback,
-                        )?;
-                    } else {
-                        download_file(
-                            &download_info.get("raw").unwrap().url,
-                            &current_path,
-                            Some(download_info.get("raw").unwrap().sha1.as_str()),
-                            false,
-                            None::<&Path>,
-                            Some(&client),
-                            callback,
-                        )?;
-                    }
-                }
-                //Make files executable on unix systems
-                if value.executable == Some(true) {
-                    let _ = Command::new("chmod").arg("+x").arg(current_path).status();
-                }
-                file_list.push(key);
-            } else if vtype == "directory" {
-                let _ = fs::create_dir_all(&current_path);
-            } else if vtype == "link" {
-                check_path_inside_minecraft_directory(
-                    &minecraft_directory,
-                    base_path.join(value.target.as_deref().unwrap_or("")),
-                )?;
-                if !current_path.parent().unwrap().exists() {
-                    let _ = fs::create_dir_all(current_path.parent().unwrap());
-                }
-                // Create a symbolic link at `link_path` pointing to `target`
-                #[cfg(unix)]
-                {
-                    if let Some(target) = value.target.as_ref() {
-                        let _ = std::os::unix::fs::symlink(Path::new(target), &current_path);
-                    }
-                }
-            }
-            if let Some(set_progresss) = callback.set_progress {
-                set_progresss(count);
+/// Lists the runtime components installed under `<minecraft_dir>/runtime`.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the runtime directory exists but cannot
+/// be read.
+pub fn installed_runtimes(minecraft_dir: impl AsRef<Path>) -> Result<Vec<String>> {
+    let entries = match fs::read_dir(minecraft_dir.as_ref().join("runtime")) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let mut components = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            if let Ok(name) = entry.file_name().into_string() {
+                components.push(name);
             }
-            count += 1;
         }
     }
-    // Create the .version file
-    let version_path = minecraft_directory
-        .as_ref()
-        .join("runtime")
-        .join(jvm_version)
-        .join(&platform_string)
-        .join(".version");
-    check_path_inside_minecraft_directory(&minecraft_directory, &version_path)?;
-    let mut version_file = fs::File::create(&version_path)?;
-    version_file.write_all(
-        manifest_data
-            .get(&platform_string)
-            .unwrap()
-            .get(jvm_version)
-            .unwrap()[0]
-            .version
-            .get("name")
-            .unwrap()
-            .as_bytes(),
-    )?;
+    components.sort();
+    Ok(components)
+}
 
-    // Write the .sha1 file
-    let sha1_path = minecraft_directory
-        .as_ref()
-        .join("runtime")
-        .join(jvm_version)
-        .join(platform_string)
-        .join(format!("{}.sha1", jvm_version));
-    check_path_inside_minecraft_directory(&minecraft_directory, &sha1_path)?;
-    let mut sha1_file = fs::File::create(&sha1_path)?;
-    for file in file_list {
-        let current_path = base_path.join(file);
-        let ctime = current_path.metadata()?.modified()?.elapsed()?.as_nanos(); // Use chrono for more precise time handling
-        let sha1 = get_sha1_hash(current_path.to_str().unwrap())?;
-        sha1_file.write_all(format!("{} /#// {} {}\n", file, sha1, ctime).as_bytes())?;
-    }
-    Ok(())
+/// Returns the newest published release of a runtime component.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the runtime index cannot be fetched.
+pub fn runtime_release(
+    component: &str,
+    platform: Platform,
+    mirrors: &MirrorConfig,
+) -> Result<Option<RuntimeRelease>> {
+    let index = fetch_runtime_index_with_mirrors(mirrors)?;
+    Ok(select_runtime_release(&index, component, platform).cloned())
 }
 
-pub fn get_executable_path(
-    jvm_version: &str,
-    minecraft_directory: impl AsRef<Path>,
+/// Returns the `java` executable of an installed runtime component.
+pub fn java_executable(
+    component: &str,
+    minecraft_dir: impl AsRef<Path>,
+    platform: Platform,
 ) -> Option<PathBuf> {
-    let java_path = minecraft_directory
-        .as_ref()
-        .join("runtime")
-        .join(jvm_version)
-        .join(get_jvm_platform_string())
-        .join(jvm_version)
-        .join("bin")
-        .join("java");
+    find_java_executable(runtime_home(minecraft_dir, component, platform).ok()?)
+}
 
-    if java_path.is_file() {
-        return Some(java_path);
+/// Installs a runtime component and returns its `java` executable.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if Mojang does not publish `component` for
+/// `platform`, or for metadata, download, checksum, or filesystem failures.
+pub fn install_runtime(
+    component: &str,
+    minecraft_dir: impl AsRef<Path>,
+    platform: Platform,
+    options: &DownloadOptions,
+    reporter: &mut dyn ProgressReporter,
+) -> Result<PathBuf> {
+    install_runtime_with_options(component, minecraft_dir, platform, options, reporter)?.ok_or_else(
+        || LauncherError::Other {
+            message: format!(
+                "java runtime {component} is not available for {}",
+                runtime_platform_key(platform).unwrap_or("this platform")
+            ),
+        },
+    )
+}
+
+/// Writes the `.sha1` file for a runtime installed from `manifest`.
+///
+/// Each line has the form `<path> /#// <sha1> <modified>`, where `<modified>`
+/// is the file modification time in nanoseconds since the Unix epoch, following
+/// the layout of the file written by the official launcher.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if a listed file is missing or the index
+/// cannot be written.
+pub fn write_sha1_index(
+    manifest: &RuntimeManifest,
+    home: impl AsRef<Path>,
+    index_path: impl AsRef<Path>,
+) -> Result<()> {
+    let home = home.as_ref();
+    let mut writer = BufWriter::new(fs::File::create(index_path)?);
+    for (path, file) in &manifest.files {
+        let RuntimeFile::File { downloads, .. } = file else {
+            continue;
+        };
+        let modified = fs::metadata(safe_join(home, path)?)?
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos())
+            .unwrap_or_default();
+        writeln!(
+            writer,
+            "{path}{SHA1_SEPARATOR}{} {modified}",
+            downloads.raw.sha1
+        )?;
     }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads a runtime `.sha1` file.
+///
+/// Blank and malformed lines are ignored.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the file cannot be read.
+pub fn read_sha1_index(index_path: impl AsRef<Path>) -> Result<Vec<RuntimeFileHash>> {
+    Ok(fs::read_to_string(index_path)?
+        .lines()
+        .filter_map(|line| {
+            let (path, rest) = line.split_once(SHA1_SEPARATOR)?;
+            let sha1 = rest.split_whitespace().next()?;
+            Some(RuntimeFileHash {
+                path: path.to_string(),
+                sha1: sha1.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Checks an installed runtime against the `.sha1` file written at install
+/// time.
+///
+/// Verification reads local files only.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if the runtime has no `.sha1` file, a
+/// listed path escapes the Java home, or a file cannot be hashed.
+pub fn verify_runtime(
+    component: &str,
+    minecraft_dir: impl AsRef<Path>,
+    platform: Platform,
+) -> Result<RuntimeVerification> {
+    let minecraft_dir = minecraft_dir.as_ref();
+    let home = runtime_home(minecraft_dir, component, platform)?;
+    let index_path =
+        runtime_platform_dir(minecraft_dir, component, platform)?.join(format!("{component}.sha1"));
 
-    let java_exe_path = java_path.with_extension("exe");
-    if java_exe_path.is_file() {
-        return Some(java_exe_path);
+    let mut report = RuntimeVerification::default();
+    for entry in read_sha1_index(index_path)? {
+        report.checked += 1;
+        let path = safe_join(&home, &entry.path)?;
+        if !path.is_file() {
+            report.missing.push(path);
+        } else if !sha1_file(&path)?.eq_ignore_ascii_case(&entry.sha1) {
+            report.corrupt.push(path);
+        }
     }
+    Ok(report)
+}
 
-    let java_alternate_path = minecraft_directory
-        .as_ref()
-        .join("runtime")
-        .join(jvm_version)
-        .join(get_jvm_platform_string())
-        .join(jvm_version)
-        .join("jre.bundle")
-        .join("Contents")
-        .join("Home")
-        .join("bin")
-        .join("java");
+/// Verifies a runtime and reinstalls it when files are missing or corrupt.
+///
+/// Reinstalling downloads only files whose checksum does not match and
+/// rewrites the `.sha1` file. A runtime without a `.sha1` file is reinstalled.
+/// The returned report describes the state found before the repair.
+///
+/// # Errors
+///
+/// Returns [`crate::LauncherError`] if verification or reinstallation fails.
+pub fn repair_runtime(
+    component: &str,
+    minecraft_dir: impl AsRef<Path>,
+    platform: Platform,
+    options: &DownloadOptions,
+    reporter: &mut dyn ProgressReporter,
+) -> Result<RuntimeVerification> {
+    let minecraft_dir = minecraft_dir.as_ref();
+    let report = match verify_runtime(component, minecraft_dir, platform) {
+        Ok(report) if report.is_ok() => return Ok(report),
+        Ok(report) => report,
+        Err(LauncherError::Io { source }) if source.kind() == std::io::ErrorKind::NotFound => {
+            RuntimeVerification::default()
+        }
+        Err(err) => return Err(err),
+    };
+    install_runtime(component, minecraft_dir, platform, options, reporter)?;
+    Ok(report)
+}
 
-    if java_alternate_path.is_file() {
-        return Some(java_alternate_path);
+fn unsupported(platform: Platform) -> LauncherError {
+    LauncherError::UnsupportedPlatform {
+        os: platform.minecraft_os_name().to_string(),
+        arch: format!("{:?}", platform.arch).to_lowercase(),
     }
+}
 
-    None
+/// Legacy wrapper for [`list_available_runtimes`] on the current platform.
+#[deprecated(note = "use runtime::list_available_runtimes")]
+pub fn get_jvm_runtimes() -> Result<Vec<String>> {
+    list_available_runtimes(Platform::current(), &MirrorConfig::official())
 }
 
-pub fn get_jvm_runtime_information(
-    jvm_version: &str,
-) -> Result<JvmRuntimeInformation, Box<dyn std::error::Error>> {
-    let client = reqwest::blocking::Client::new();
-    let manifest_data: RuntimeListJson = client
-        .get(JVM_MANIFEST_URL)
-        .header("user-agent", get_user_agent())
-        .send()?
-        .json()?;
+/// Legacy wrapper for [`installed_runtimes`] that ignores read errors.
+#[deprecated(note = "use runtime::installed_runtimes")]
+pub fn get_installed_jvm_runtimes(minecraft_directory: impl AsRef<Path>) -> Vec<String> {
+    installed_runtimes(minecraft_directory).unwrap_or_default()
+}
 
-    let platform_string = get_jvm_platform_string();
+/// Legacy wrapper for [`install_runtime`] on the current platform.
+///
+/// The callback is ignored; use [`install_runtime`] with a
+/// [`ProgressReporter`] to observe progress.
+#[deprecated(note = "use runtime::install_runtime")]
+pub fn install_jvm_runtime(
+    jvm_version: &str,
+    minecraft_directory: impl AsRef<Path>,
+    _callback: &CallbackDict,
+) -> Result<()> {
+    let mut reporter = |_event: ProgressEvent| {};
+    install_runtime(
+        jvm_version,
+        minecraft_directory,
+        Platform::current(),
+        &DownloadOptions::default(),
+        &mut reporter,
+    )?;
+    Ok(())
+}
 
-    // Check if the jvm version exists
-    if !manifest_data
-        .get(&platform_string)
-        .unwrap_or(&HashMap::new())
-        .contains_key(jvm_version)
-    {
-        return Err(format!("jvm version is not found: {}", jvm_version).into());
-    }
+/// Legacy wrapper for [`java_executable`] on the current platform.
+#[deprecated(note = "use runtime::java_executable")]
+pub fn get_executable_path(
+    jvm_version: &str,
+    minecraft_directory: impl AsRef<Path>,
+) -> Option<PathBuf> {
+    java_executable(jvm_version, minecraft_directory, Platform::current())
+}
 
-    if manifest_data
-        .get(&platform_string)
-        .unwrap()
-        .get(jvm_version)
-        .unwrap_or(&Vec::new())
-        .is_empty()
-    {
-        return Err("this platform not supported yet.".into());
-    }
-    let runtime_list_json_entry = manifest_data
-        .get(&platform_string)
-        .unwrap()
-        .get(jvm_version)
-        .unwrap();
+/// Legacy wrapper for [`runtime_release`] on the current platform.
+#[deprecated(note = "use runtime::runtime_release")]
+pub fn get_jvm_runtime_information(jvm_version: &str) -> Result<JvmRuntimeInformation> {
+    let release = runtime_release(jvm_version, Platform::current(), &MirrorConfig::official())?
+        .ok_or_else(|| LauncherError::Other {
+            message: format!("jvm version is not found: {jvm_version}"),
+        })?;
+    let released = DateTime::parse_from_rfc3339(&release.version.released).map_err(|err| {
+        LauncherError::Other {
+            message: format!("invalid runtime release date: {err}"),
+        }
+    })?;
     Ok(JvmRuntimeInformation {
-        name: runtime_list_json_entry[0]
-            .version
-            .get("name")
-            .unwrap()
-            .to_string(),
-        released: DateTime::parse_from_rfc3339(
-            runtime_list_json_entry[0].version.get("released").unwrap(),
-        )?
-        .into(),
+        name: release.version.name,
+        released: released.into(),
     })
 }
 
+/// Legacy helper that reads `javaVersion` from an installed version.
+///
+/// Use [`crate::launcher::Launcher::load_version`] and read
+/// [`crate::core::version::VersionJson::java_version`] instead.
+#[deprecated(note = "use Launcher::load_version and VersionJson::java_version")]
 pub fn get_version_runtime_information(
     version: &str,
     minecraft_directory: impl AsRef<Path>,
 ) -> Option<VersionRuntimeInformation> {
-    let data = match get_client_json(version, &minecraft_directory) {
-        Ok(json_data) => json_data,
-        Err(_) => return None,
-    };
-    let java_version = data.java_version?;
+    let java_version = load_version_json(minecraft_directory, version)
+        .ok()?
+        .java_version?;
     Some(VersionRuntimeInformation {
         name: java_version.component,
         java_major_version: java_version.major_version,
@@ -334,10 +366,11 @@ mod test {
 
     #[test]
     fn debug_get_jvm_platform_string() {
-        println!("{}", get_jvm_platform_string());
+        println!("{:?}", runtime_platform_key(Platform::current()));
     }
 
     #[test]
+    #[allow(deprecated)]
     fn debug_get_jvm_runtimes() {
         match get_jvm_runtimes() {
             Ok(v) => println!("{:?}", v),
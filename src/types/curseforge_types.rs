@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+/// `manifest.json` at the root of a CurseForge modpack zip.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeManifest {
+    /// Game version and loaders.
+    pub minecraft: CurseForgeMinecraft,
+    /// Manifest kind; always `minecraftModpack`.
+    pub manifest_type: String,
+    /// Manifest format version; only `1` exists.
+    pub manifest_version: u32,
+    /// Pack name.
+    pub name: String,
+    /// Pack version, such as `1.2.0`.
+    #[serde(default)]
+    pub version: String,
+    /// Pack author.
+    #[serde(default)]
+    pub author: String,
+    /// Project files to download.
+    #[serde(default)]
+    pub files: Vec<CurseForgeFile>,
+    /// Directory inside the zip copied into the game directory.
+    #[serde(default = "default_overrides")]
+    pub overrides: String,
+}
+
+/// Minecraft version and loaders of a CurseForge modpack.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeMinecraft {
+    /// Minecraft version, such as `1.20.1`.
+    pub version: String,
+    /// Loaders, such as `forge-47.2.0` or `fabric-0.15.0`.
+    #[serde(default)]
+    pub mod_loaders: Vec<CurseForgeModLoader>,
+}
+
+/// One loader entry of a CurseForge manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CurseForgeModLoader {
+    /// Loader and version joined by `-`, such as `forge-47.2.0`.
+    pub id: String,
+    /// Whether this is the loader the pack is installed with.
+    #[serde(default)]
+    pub primary: bool,
+}
+
+/// One project file of a CurseForge manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CurseForgeFile {
+    /// CurseForge project ID.
+    #[serde(rename = "projectID")]
+    pub project_id: u64,
+    /// CurseForge file ID within the project.
+    #[serde(rename = "fileID")]
+    pub file_id: u64,
+    /// Whether the file is enabled in the pack.
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_overrides() -> String {
+    "overrides".to_string()
+}
+
+fn default_required() -> bool {
+    true
+}
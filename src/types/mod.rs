@@ -8,12 +8,14 @@
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
+pub mod curseforge_types;
 pub mod exceptions_types;
 pub mod forge_types;
 pub mod helper_types;
 pub mod install_types;
 pub mod microsoft_types;
 pub mod mrpack_types;
+pub mod multimc_types;
 pub mod runtime_types;
 pub mod shared_types;
 pub mod vanilla_launcher_types;
@@ -184,17 +186,44 @@ pub struct VanillaLauncherProfile {
 }
 
 // mrpack
+/// Summary of an `.mrpack` returned by
+/// [`crate::modpack::mrpack::read_mrpack_information`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct MrpackInformation {
     pub name: String,
     pub summary: String,
     pub version_id: String,
     pub format_version: i32,
     pub minecraft_version: String,
+    /// Paths of files the client may choose to install.
     pub optional_files: Vec<String>,
 }
 
+/// Options for importing an `.mrpack`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct MrpackInstallOptions {
+    /// Optional file paths to install; no optional file is installed when unset.
     pub optional_files: Option<Vec<String>>,
+    /// Skip installing Minecraft and the loader, for example when the profile
+    /// is already installed.
+    pub skip_dependencies_install: Option<bool>,
+}
+
+// curseforge
+/// Options for importing a CurseForge modpack.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CurseForgeInstallOptions {
+    /// Skip installing Minecraft and the loader, for example when the profile
+    /// is already installed.
+    pub skip_dependencies_install: Option<bool>,
+}
+
+// multimc
+/// Options for importing a MultiMC or Prism Launcher instance.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MultiMcInstallOptions {
+    /// Skip installing Minecraft and the loader, for example when the profile
+    /// is already installed.
     pub skip_dependencies_install: Option<bool>,
 }
 
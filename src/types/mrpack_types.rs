@@ -1 +1,72 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
+/// `modrinth.index.json` at the root of an `.mrpack` archive.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrpackIndex {
+    /// Index format version; only `1` exists.
+    pub format_version: u32,
+    /// Game the pack is for; always `minecraft`.
+    pub game: String,
+    /// Pack version, such as `1.2.0`.
+    pub version_id: String,
+    /// Pack name.
+    pub name: String,
+    /// Short description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Files downloaded into the game directory.
+    #[serde(default)]
+    pub files: Vec<MrpackFile>,
+    /// Required versions keyed by `minecraft`, `forge`, `neoforge`,
+    /// `fabric-loader`, or `quilt-loader`.
+    pub dependencies: BTreeMap<String, String>,
+}
+
+/// One downloadable file of a pack.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrpackFile {
+    /// Destination relative to the game directory.
+    pub path: String,
+    /// File digests.
+    pub hashes: MrpackHashes,
+    /// Client and server support; files without it are required on both.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<MrpackEnv>,
+    /// Download URLs, tried in order.
+    pub downloads: Vec<String>,
+    /// Size in bytes.
+    pub file_size: u64,
+}
+
+/// Digests of a pack file as lowercase hexadecimal.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MrpackHashes {
+    /// SHA-1 digest.
+    pub sha1: String,
+    /// SHA-512 digest.
+    pub sha512: String,
+}
+
+/// Per-side support of a pack file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MrpackEnv {
+    /// Client support.
+    pub client: MrpackEnvSupport,
+    /// Server support.
+    pub server: MrpackEnvSupport,
+}
+
+/// Whether a side needs a pack file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MrpackEnvSupport {
+    /// Always installed.
+    Required,
+    /// Installed when the user selects it.
+    Optional,
+    /// Never installed.
+    Unsupported,
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// `mmc-pack.json` of a MultiMC or Prism Launcher instance.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MmcPack {
+    /// Pack format version; only `1` exists.
+    pub format_version: u32,
+    /// Components in load order.
+    #[serde(default)]
+    pub components: Vec<MmcComponent>,
+}
+
+/// One component of an `mmc-pack.json`, such as Minecraft or a loader.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MmcComponent {
+    /// Component id, such as `net.minecraft` or `net.fabricmc.fabric-loader`.
+    pub uid: String,
+    /// Selected version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Display name cached by the launcher.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_name: Option<String>,
+    /// Version cached by the launcher when `version` is absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_version: Option<String>,
+    /// Whether the component was added only to satisfy another one.
+    #[serde(default)]
+    pub dependency_only: bool,
+}
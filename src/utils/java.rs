@@ -6,6 +6,10 @@ use std::{
 
 use crate::types::JavaInformation;
 
+/// Reads version information for the Java home at `path`.
+///
+/// New code should use [`crate::runtime::java::JavaInstallation::probe`],
+/// which returns parsed version numbers and architecture.
 pub fn get_java_information(path: &Path) -> Result<JavaInformation, String> {
     let is_windows = env::consts::OS == "windows";
     let java_exe = if is_windows { "java.exe" } else { "java" };
@@ -23,7 +27,8 @@ pub fn get_java_information(path: &Path) -> Result<JavaInformation, String> {
     let output_str = String::from_utf8_lossy(&output.stderr);
     let lines: Vec<&str> = output_str.lines().collect();
 
-    let version = lines[0]
+    let first_line = lines.first().copied().unwrap_or_default();
+    let version = first_line
         .split("version \"")
         .nth(1)
         .and_then(|s| s.split('"').next())
@@ -46,8 +51,8 @@ pub fn get_java_information(path: &Path) -> Result<JavaInformation, String> {
             .to_str()
             .unwrap_or_default(),
         version,
-        lines[2].contains("64-Bit"),
-        lines[0].starts_with("openjdk"),
+        lines.get(2).is_some_and(|line| line.contains("64-Bit")),
+        first_line.starts_with("openjdk"),
         java_path.to_str().unwrap_or_default(),
         javaw_path,
     ))
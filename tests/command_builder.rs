@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use mc_launcher_core::{
     account::Account,
-    command::builder::{build_launch_command, LaunchCommand, LaunchOptions},
+    command::builder::{build_launch_command, LaunchCommand, LaunchOptions, LogConfigPolicy},
     core::version::VersionJson,
 };
 
@@ -139,3 +139,47 @@ fn modern_custom_resolution_is_replaced_once() {
         .windows(4)
         .any(|window| window == ["--width", "1280", "--height", "720"]));
 }
+
+#[test]
+fn applies_version_log_config_unless_overridden() {
+    let version: VersionJson = serde_json::from_str(
+        r#"{
+            "id":"1.12.2",
+            "mainClass":"net.minecraft.client.main.Main",
+            "minecraftArguments":"--username ${auth_player_name}",
+            "logging":{"client":{
+                "argument":"-Dlog4j.configurationFile=${path}",
+                "file":{"id":"client-1.12.xml","sha1":"abc","size":1,"url":"https://example.invalid/client-1.12.xml"},
+                "type":"log4j2-xml"
+            }}
+        }"#,
+    )
+    .unwrap();
+    let build = |log_config| {
+        build_launch_command(
+            &version,
+            PathBuf::from("/tmp/mc"),
+            LaunchOptions {
+                log_config,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .args
+    };
+    let position = |args: &[String], needle: &str| args.iter().position(|arg| arg == needle);
+
+    let args = build(LogConfigPolicy::Auto);
+    let argument = "-Dlog4j.configurationFile=/tmp/mc/assets/log_configs/client-1.12.xml";
+    assert!(
+        position(&args, argument).unwrap()
+            < position(&args, "net.minecraft.client.main.Main").unwrap()
+    );
+
+    let args = build(LogConfigPolicy::Custom(PathBuf::from("/etc/log4j2.xml")));
+    assert!(args.contains(&"-Dlog4j.configurationFile=/etc/log4j2.xml".to_string()));
+    assert!(position(&args, argument).is_none());
+
+    let args = build(LogConfigPolicy::Disabled);
+    assert!(!args.iter().any(|arg| arg.starts_with("-Dlog4j")));
+}
@@ -0,0 +1,208 @@
+mod support;
+
+use std::fs;
+
+use mc_launcher_core::{
+    instance::content::{ContentKind, ContentMetadata, OPTIONS_FILE},
+    net::download::{DownloadOptions, DownloadTask},
+    prelude::*,
+};
+use serde_json::json;
+use support::{write_zip, StandInServer};
+
+#[test]
+fn lists_and_toggles_instance_content() {
+    let dir = tempfile::tempdir().unwrap();
+    let launcher = Launcher::new(dir.path());
+    launcher
+        .create_instance("Packs", InstanceConfig::vanilla("1.20.1"))
+        .unwrap();
+    let content = launcher.instance_content("Packs").unwrap();
+    let game = content.game_directory().to_path_buf();
+    assert_eq!(content.list(ContentKind::ShaderPack).unwrap(), []);
+
+    let mods = game.join("mods");
+    write_zip(
+        &mods.join("sodium.jar"),
+        &[(
+            "fabric.mod.json",
+            json!({"schemaVersion": 1, "id": "sodium", "version": "0.5.3"})
+                .to_string()
+                .as_bytes(),
+        )],
+    );
+    fs::write(mods.join("broken.jar.disabled"), b"not a zip").unwrap();
+    fs::write(mods.join("readme.txt"), b"hello").unwrap();
+    fs::create_dir(mods.join(".rollback")).unwrap();
+
+    let packs = game.join("resourcepacks");
+    fs::create_dir_all(packs.join("Local/assets")).unwrap();
+    fs::write(
+        packs.join("Local/pack.mcmeta"),
+        json!({"pack": {"pack_format": 15, "description": "Plain"}}).to_string(),
+    )
+    .unwrap();
+    write_zip(
+        &packs.join("Faithful.zip"),
+        &[
+            (
+                "pack.mcmeta",
+                json!({"pack": {"pack_format": 15, "description": [{"text": "Faithful "}, {"text": "32x", "extra": [" edition"]}]}})
+                    .to_string()
+                    .as_bytes(),
+            ),
+            ("pack.png", b"icon"),
+        ],
+    );
+    fs::create_dir_all(game.join("shaderpacks/BSL.zip.disabled")).unwrap();
+
+    let listed = content.list(ContentKind::Mod).unwrap();
+    assert_eq!(
+        listed
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.enabled, entry.error.is_some()))
+            .collect::<Vec<_>>(),
+        [("broken.jar", false, true), ("sodium.jar", true, false)]
+    );
+    let ContentMetadata::Mods(sodium) = &listed[1].metadata else {
+        panic!("expected mod metadata");
+    };
+    assert_eq!(sodium[0].id, "sodium");
+
+    let listed = content.list(ContentKind::ResourcePack).unwrap();
+    assert_eq!(listed.len(), 2);
+    assert_eq!(listed[0].name, "Faithful.zip");
+    assert!(!listed[0].is_directory);
+    let ContentMetadata::Pack(faithful) = &listed[0].metadata else {
+        panic!("expected pack metadata");
+    };
+    assert_eq!(faithful.pack_format, Some(15));
+    assert_eq!(faithful.description, "Faithful 32x edition");
+    assert_eq!(faithful.icon.as_deref(), Some(&b"icon"[..]));
+    assert!(listed[1].is_directory);
+    assert!(
+        matches!(&listed[1].metadata, ContentMetadata::Pack(local) if local.description == "Plain")
+    );
+
+    let shaders = content.list(ContentKind::ShaderPack).unwrap();
+    assert_eq!(shaders.len(), 1);
+    assert_eq!(shaders[0].name, "BSL.zip");
+    assert!(!shaders[0].enabled);
+    assert_eq!(shaders[0].metadata, ContentMetadata::None);
+
+    let enabled = content
+        .set_enabled(ContentKind::ShaderPack, "BSL.zip", true)
+        .unwrap();
+    assert_eq!(enabled, game.join("shaderpacks/BSL.zip"));
+    assert!(enabled.is_dir());
+    let disabled = content
+        .set_enabled(ContentKind::Mod, "sodium.jar", false)
+        .unwrap();
+    assert_eq!(disabled, mods.join("sodium.jar.disabled"));
+    assert_eq!(
+        content
+            .set_enabled(ContentKind::Mod, "sodium.jar", false)
+            .unwrap(),
+        disabled
+    );
+    assert!(!mods.join("sodium.jar").exists());
+    assert!(matches!(
+        content.set_enabled(ContentKind::Mod, "missing.jar", true),
+        Err(LauncherError::ContentNotFound { .. })
+    ));
+    assert!(matches!(
+        content.set_enabled(ContentKind::Mod, "../escape.jar", true),
+        Err(LauncherError::UnsafePath { .. })
+    ));
+}
+
+#[test]
+fn installs_deletes_and_orders_content() {
+    let server = StandInServer::start();
+    server.serve("/files/Complementary.zip", b"shader".to_vec());
+    let dir = tempfile::tempdir().unwrap();
+    let launcher = Launcher::new(dir.path());
+    let instance = launcher
+        .create_instance("Packs", InstanceConfig::vanilla("1.20.1"))
+        .unwrap();
+    let content = instance.content();
+    let game = instance.game_directory();
+
+    let sources = tempfile::tempdir().unwrap();
+    fs::write(sources.path().join("Faithful.zip"), b"pack").unwrap();
+    fs::create_dir_all(sources.path().join("Local/assets")).unwrap();
+    fs::write(sources.path().join("Local/assets/a.json"), b"{}").unwrap();
+    fs::write(sources.path().join("notes.txt"), b"hello").unwrap();
+
+    let installed = content
+        .install_file(
+            ContentKind::ResourcePack,
+            sources.path().join("Faithful.zip"),
+        )
+        .unwrap();
+    assert_eq!(installed, game.join("resourcepacks/Faithful.zip"));
+    assert_eq!(fs::read(&installed).unwrap(), b"pack");
+    let local = content
+        .install_file(ContentKind::ResourcePack, sources.path().join("Local"))
+        .unwrap();
+    assert_eq!(fs::read(local.join("assets/a.json")).unwrap(), b"{}");
+    assert!(matches!(
+        content.install_file(
+            ContentKind::ResourcePack,
+            sources.path().join("Faithful.zip")
+        ),
+        Err(LauncherError::ContentExists { .. })
+    ));
+    assert!(content
+        .install_file(ContentKind::Mod, sources.path().join("notes.txt"))
+        .is_err());
+    assert_eq!(fs::read_dir(game.join("resourcepacks")).unwrap().count(), 2);
+
+    let mut reporter = |_event: ProgressEvent| {};
+    let task = DownloadTask {
+        url: server.url("/files/Complementary.zip"),
+        destination: "downloads/Complementary.zip".into(),
+        checksum: None,
+        size: Some(6),
+        label: "Complementary".to_string(),
+    };
+    let shader = content
+        .install_download(
+            ContentKind::ShaderPack,
+            &task,
+            &DownloadOptions::default(),
+            &mut reporter,
+        )
+        .unwrap();
+    assert_eq!(shader, game.join("shaderpacks/Complementary.zip"));
+    assert_eq!(fs::read(&shader).unwrap(), b"shader");
+
+    assert_eq!(content.resource_pack_order().unwrap(), Vec::<String>::new());
+    fs::write(
+        game.join(OPTIONS_FILE),
+        "version:3465\nresourcePacks:[\"vanilla\"]\nlang:en_us\n",
+    )
+    .unwrap();
+    assert_eq!(content.resource_pack_order().unwrap(), ["vanilla"]);
+    let order = ["vanilla", "file/Local", "file/Faithful.zip"].map(String::from);
+    content.set_resource_pack_order(&order).unwrap();
+    assert_eq!(
+        fs::read_to_string(game.join(OPTIONS_FILE)).unwrap(),
+        "version:3465\nresourcePacks:[\"vanilla\",\"file/Local\",\"file/Faithful.zip\"]\nlang:en_us\n"
+    );
+    assert_eq!(content.resource_pack_order().unwrap(), order);
+
+    content
+        .set_enabled(ContentKind::ResourcePack, "Faithful.zip", false)
+        .unwrap();
+    content
+        .delete(ContentKind::ResourcePack, "Faithful.zip")
+        .unwrap();
+    content.delete(ContentKind::ResourcePack, "Local").unwrap();
+    assert_eq!(content.list(ContentKind::ResourcePack).unwrap(), []);
+    assert_eq!(content.resource_pack_order().unwrap(), ["vanilla"]);
+    assert!(matches!(
+        content.delete(ContentKind::ResourcePack, "Local"),
+        Err(LauncherError::ContentNotFound { .. })
+    ));
+}
@@ -0,0 +1,209 @@
+use std::{
+    fs,
+    time::{Duration, SystemTime},
+};
+
+use mc_launcher_core::process::crash::{
+    analyze_crash, classify_crash, find_latest_crash_report, CrashCause, CrashReport,
+    CrashReportMod, JvmCrashLog,
+};
+
+const FORGE_REPORT: &str = include_str!("fixtures/crash_report_forge.txt");
+
+const HS_ERR: &str = "#
+# A fatal error has been detected by the Java Runtime Environment:
+#
+#  EXCEPTION_ACCESS_VIOLATION (0xc0000005) at pc=0x00007ffb1c2d3e4f, pid=4242, tid=4243
+#
+# JRE version: OpenJDK Runtime Environment Temurin-17.0.8+7 (17.0.8+7) (build 17.0.8+7)
+# Problematic frame:
+# C  [atio6axx.dll+0x1c3e4f]
+#
+---------------  S U M M A R Y ------------
+";
+
+fn causes(text: &str) -> Vec<CrashCause> {
+    classify_crash(text)
+        .into_iter()
+        .map(|diagnosis| diagnosis.cause)
+        .collect()
+}
+
+#[test]
+fn parses_crash_report_sections_and_mod_list() {
+    let report = CrashReport::parse(FORGE_REPORT);
+
+    assert_eq!(report.time.as_deref(), Some("2024-03-01 18:22:05"));
+    assert_eq!(
+        report.description.as_deref(),
+        Some("Mod loading error has occurred")
+    );
+    assert!(report
+        .stack_trace
+        .starts_with("java.lang.Exception: Mod Loading has failed\n\tat "));
+    assert!(!report.stack_trace.contains("A detailed walkthrough"));
+    let titles: Vec<&str> = report
+        .sections
+        .iter()
+        .map(|(title, _)| title.as_str())
+        .collect();
+    assert_eq!(
+        titles,
+        ["Head", "Mod loading issue for: create", "System Details"]
+    );
+    assert_eq!(report.system_details["Minecraft Version"], "1.20.1");
+    assert_eq!(
+        report.system_details["Java Version"],
+        "17.0.8, Eclipse Adoptium"
+    );
+    assert_eq!(
+        report.mods[1],
+        CrashReportMod {
+            id: "create".to_string(),
+            name: Some("Create".to_string()),
+            version: Some("0.5.1.f".to_string()),
+        }
+    );
+}
+
+#[test]
+fn parses_fabric_mod_list() {
+    let report = CrashReport::parse(
+        "Description: Initializing game\n\n-- System Details --\nDetails:\n\tFabric Mods: \n\t\tfabric-api: Fabric API 0.92.0+1.20.1\n\t\tsodium: Sodium 0.5.3\n",
+    );
+
+    assert_eq!(
+        report.mods,
+        vec![
+            CrashReportMod {
+                id: "fabric-api".to_string(),
+                name: Some("Fabric API".to_string()),
+                version: Some("0.92.0+1.20.1".to_string()),
+            },
+            CrashReportMod {
+                id: "sodium".to_string(),
+                name: Some("Sodium".to_string()),
+                version: Some("0.5.3".to_string()),
+            },
+        ]
+    );
+}
+
+#[test]
+fn parses_jvm_crash_log_header() {
+    let log = JvmCrashLog::parse(HS_ERR);
+
+    assert_eq!(
+        log.summary[0],
+        "A fatal error has been detected by the Java Runtime Environment:"
+    );
+    assert_eq!(
+        log.problematic_frame.as_deref(),
+        Some("C  [atio6axx.dll+0x1c3e4f]")
+    );
+}
+
+#[test]
+fn classifies_common_causes() {
+    assert_eq!(
+        causes("Exception in thread \"main\" java.lang.OutOfMemoryError: Java heap space"),
+        vec![CrashCause::OutOfMemory]
+    );
+    assert_eq!(
+        causes("Error: LinkageError occurred while loading main class net.minecraft.client.main.Main\n\tjava.lang.UnsupportedClassVersionError: net/minecraft/client/main/Main has been compiled by a more recent version of the Java Runtime (class file version 65.0), this version of the Java Runtime only recognizes class file versions up to 52.0"),
+        vec![CrashCause::UnsupportedJavaVersion {
+            required: Some(21),
+            current: Some(8),
+        }]
+    );
+    assert_eq!(
+        causes("net.fabricmc.loader.impl.FormattedException: Some of your mods are incompatible with the game or each other!\n\t - Mod 'Sodium Extra' (sodium-extra) 0.5.1 requires version 0.5.0 or later of mod 'Sodium' (sodium), which is missing!"),
+        vec![CrashCause::MissingModDependency {
+            mod_id: Some("sodium-extra".to_string()),
+            dependency: "sodium".to_string(),
+        }]
+    );
+    assert_eq!(
+        causes("Missing or unsupported mandatory dependencies:\n\tMod ID: 'geckolib', Requested by: 'alexsmobs', Expected range: '[4.2,)', Actual version: '[MISSING]'"),
+        vec![CrashCause::MissingModDependency {
+            mod_id: Some("alexsmobs".to_string()),
+            dependency: "geckolib".to_string(),
+        }]
+    );
+    assert_eq!(
+        causes("org.spongepowered.asm.mixin.transformer.throwables.MixinTransformerError: An unexpected critical error was encountered\nCaused by: org.spongepowered.asm.mixin.throwables.MixinApplyError: Mixin [iris.mixins.json:MixinGameRenderer] from phase [DEFAULT] in config [iris.mixins.json] FAILED during APPLY"),
+        vec![CrashCause::MixinFailure {
+            mod_id: None,
+            mixin: Some("iris.mixins.json:MixinGameRenderer".to_string()),
+        }]
+    );
+    assert_eq!(
+        causes("org.lwjgl.LWJGLException: Pixel format not accelerated"),
+        vec![CrashCause::GraphicsDriver {
+            detail: "Pixel format not accelerated".to_string(),
+        }]
+    );
+    assert_eq!(
+        causes("java.lang.module.ResolutionException: Modules minecraft and _1._20._1 export package net.minecraft.world to module forge"),
+        vec![CrashCause::DuplicateModule {
+            detail: "ResolutionException: Modules minecraft and _1._20._1 export package net.minecraft.world to module forge".to_string(),
+        }]
+    );
+    assert!(causes("[12:00:00] [Render thread/INFO]: Stopping!").is_empty());
+}
+
+#[test]
+fn analyzes_newest_files_since_launch() {
+    let dir = tempfile::tempdir().unwrap();
+    let reports = dir.path().join("crash-reports");
+    fs::create_dir_all(&reports).unwrap();
+    let old = reports.join("crash-2024-01-01_00.00.00-client.txt");
+    fs::write(&old, "Description: Old\n\njava.lang.OutOfMemoryError\n").unwrap();
+    let old_time = SystemTime::now() - Duration::from_secs(3600);
+    fs::File::options()
+        .write(true)
+        .open(&old)
+        .unwrap()
+        .set_modified(old_time)
+        .unwrap();
+    let launched = SystemTime::now() - Duration::from_secs(60);
+
+    assert_eq!(
+        find_latest_crash_report(dir.path(), Some(launched)).unwrap(),
+        None
+    );
+    assert_eq!(
+        find_latest_crash_report(dir.path(), None).unwrap(),
+        Some(old.clone())
+    );
+
+    let new = reports.join("crash-2024-03-01_18.22.05-client.txt");
+    fs::write(&new, FORGE_REPORT).unwrap();
+    fs::write(dir.path().join("hs_err_pid4242.log"), HS_ERR).unwrap();
+
+    let analysis = analyze_crash(dir.path(), Some(launched), Some("Stopping!")).unwrap();
+
+    assert_eq!(analysis.crash_report.unwrap().path, Some(new));
+    assert!(analysis.jvm_crash.is_some());
+    let causes: Vec<CrashCause> = analysis
+        .diagnoses
+        .iter()
+        .map(|diagnosis| diagnosis.cause.clone())
+        .collect();
+    assert_eq!(
+        causes,
+        vec![
+            CrashCause::MixinFailure {
+                mod_id: Some("create".to_string()),
+                mixin: Some("create.mixins.json:accessor.LivingEntityAccessor".to_string()),
+            },
+            CrashCause::GraphicsDriver {
+                detail: "atio6axx.dll".to_string(),
+            },
+        ]
+    );
+    assert!(analysis.diagnoses[0]
+        .evidence
+        .starts_with("Failure message: Mixin apply for mod create failed"));
+    assert!(causes[0].suggestion().contains("`create`"));
+}
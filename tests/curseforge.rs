@@ -0,0 +1,185 @@
+mod support;
+
+use std::{fs, path::Path};
+
+use mc_launcher_core::{
+    loader::common::{LoaderSpec, LoaderVersion},
+    modpack::curseforge::{curseforge_install_request, CurseForgeDownload},
+    prelude::*,
+    types::{
+        curseforge_types::{CurseForgeFile, CurseForgeManifest},
+        CurseForgeInstallOptions,
+    },
+};
+use serde_json::json;
+use support::{sha1_of, write_zip, StandInServer};
+
+fn manifest(loaders: serde_json::Value, files: serde_json::Value) -> serde_json::Value {
+    json!({
+        "minecraft": {"version": "1.20.1", "modLoaders": loaders},
+        "manifestType": "minecraftModpack",
+        "manifestVersion": 1,
+        "name": "Example Pack",
+        "version": "1.0.0",
+        "author": "someone",
+        "files": files,
+        "overrides": "overrides",
+    })
+}
+
+fn write_pack(path: &Path, manifest: &serde_json::Value, entries: &[(&str, &[u8])]) {
+    let manifest = manifest.to_string();
+    let mut all = vec![("manifest.json", manifest.as_bytes())];
+    all.extend_from_slice(entries);
+    write_zip(path, &all);
+}
+
+#[test]
+fn imports_resolved_files_and_overrides_into_instance() {
+    let server = StandInServer::start();
+    server.serve("/files/238222/4712866", b"jei".to_vec());
+    server.serve("/files/394468/4593548", b"pack".to_vec());
+    let dir = tempfile::tempdir().unwrap();
+    let pack = dir.path().join("pack.zip");
+    write_pack(
+        &pack,
+        &manifest(
+            json!([{"id": "forge-47.2.0", "primary": true}]),
+            json!([
+                {"projectID": 238222, "fileID": 4712866, "required": true},
+                {"projectID": 394468, "fileID": 4593548},
+                {"projectID": 306612, "fileID": 5010374, "required": false},
+            ]),
+        ),
+        &[
+            ("overrides/config/jei.toml", b"config"),
+            ("overrides/options.txt", b"lang:en_us"),
+            ("other/ignored.txt", b"ignored"),
+        ],
+    );
+
+    let mut requested = Vec::new();
+    let mut resolver = |file: &CurseForgeFile| {
+        requested.push(file.project_id);
+        let (path, bytes) = match file.project_id {
+            238222 => ("mods/jei.jar", &b"jei"[..]),
+            _ => ("resourcepacks/pack.zip", &b"pack"[..]),
+        };
+        Ok(CurseForgeDownload {
+            url: server.url(&format!("/files/{}/{}", file.project_id, file.file_id)),
+            path: path.to_string(),
+            sha1: Some(sha1_of(bytes)),
+            size: Some(bytes.len() as u64),
+        })
+    };
+    let launcher = Launcher::new(dir.path().join("minecraft"));
+    let instance = launcher
+        .import_curseforge(
+            &pack,
+            "Example",
+            &mut resolver,
+            &CurseForgeInstallOptions {
+                skip_dependencies_install: Some(true),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(requested, [238222, 394468]);
+    assert_eq!(instance.config.version_id, "1.20.1-forge-47.2.0");
+    assert_eq!(launcher.instance("Example").unwrap(), instance);
+    let game = instance.game_directory();
+    assert_eq!(fs::read(game.join("mods/jei.jar")).unwrap(), b"jei");
+    assert_eq!(
+        fs::read(game.join("resourcepacks/pack.zip")).unwrap(),
+        b"pack"
+    );
+    assert_eq!(fs::read(game.join("config/jei.toml")).unwrap(), b"config");
+    assert!(game.join("options.txt").is_file());
+    assert!(!game.join("manifest.json").exists());
+    assert!(!game.join("other").exists());
+}
+
+#[test]
+fn maps_mod_loaders_to_loader_spec() {
+    let parse = |loaders: serde_json::Value| -> CurseForgeManifest {
+        serde_json::from_value(manifest(loaders, json!([]))).unwrap()
+    };
+    let loader = |loaders: serde_json::Value| {
+        curseforge_install_request(&parse(loaders))
+            .map(|request| request.loader)
+            .ok()
+    };
+
+    assert_eq!(
+        loader(json!([{"id": "forge-47.2.0", "primary": true}])),
+        Some(Some(LoaderSpec::Forge {
+            version: LoaderVersion::Exact("1.20.1-47.2.0".to_string()),
+        }))
+    );
+    assert_eq!(
+        loader(json!([{"id": "fabric-0.15.0"}])),
+        Some(Some(LoaderSpec::Fabric {
+            version: LoaderVersion::Exact("0.15.0".to_string()),
+        }))
+    );
+    assert_eq!(
+        loader(json!([
+            {"id": "forge-47.2.0", "primary": false},
+            {"id": "neoforge-47.1.79", "primary": true},
+        ])),
+        Some(Some(LoaderSpec::NeoForge {
+            version: LoaderVersion::Exact("47.1.79".to_string()),
+        }))
+    );
+    assert_eq!(loader(json!([])), Some(None));
+    assert_eq!(loader(json!([{"id": "rift-1.0"}])), None);
+    assert_eq!(
+        loader(json!([{"id": "forge-47.2.0"}, {"id": "fabric-0.15.0"}])),
+        None
+    );
+}
+
+#[test]
+fn rejects_unsafe_paths_and_removes_failed_imports() {
+    let server = StandInServer::start();
+    server.serve("/escape.jar", b"x".to_vec());
+    let dir = tempfile::tempdir().unwrap();
+    let pack = dir.path().join("pack.zip");
+    write_pack(
+        &pack,
+        &manifest(
+            json!([{"id": "fabric-0.15.0", "primary": true}]),
+            json!([{"projectID": 1, "fileID": 2}]),
+        ),
+        &[],
+    );
+    let launcher = Launcher::new(dir.path().join("minecraft"));
+    let options = CurseForgeInstallOptions {
+        skip_dependencies_install: Some(true),
+    };
+
+    let mut escaping = |_: &CurseForgeFile| {
+        Ok(CurseForgeDownload {
+            url: server.url("/escape.jar"),
+            path: "../escape.jar".to_string(),
+            sha1: None,
+            size: None,
+        })
+    };
+    let result = launcher.import_curseforge(&pack, "Escape", &mut escaping, &options);
+    assert!(
+        matches!(result, Err(LauncherError::UnsafePath { .. })),
+        "{result:?}"
+    );
+
+    let mut unavailable = |file: &CurseForgeFile| -> mc_launcher_core::Result<CurseForgeDownload> {
+        Err(LauncherError::Other {
+            message: format!("file {} is not available", file.file_id),
+        })
+    };
+    assert!(launcher
+        .import_curseforge(&pack, "Unavailable", &mut unavailable, &options)
+        .is_err());
+    assert!(launcher.instances().unwrap().is_empty());
+    assert!(!dir.path().join("minecraft/escape.jar").exists());
+}
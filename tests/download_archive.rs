@@ -17,12 +17,49 @@ fn skips_existing_file_when_sha1_matches() {
         checksum: Some(Checksum::Sha1(
             "624c22a8c8f8c93f18fe5ecd4713100c8d754507".to_string(),
         )),
+        size: None,
         label: "hello".to_string(),
     };
 
     assert!(should_skip_existing(&task).unwrap());
 }
 
+#[test]
+fn skips_existing_file_when_sha256_matches() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("hello.txt");
+    fs::write(&file, b"minecraft").unwrap();
+
+    let task = DownloadTask {
+        url: "https://example.invalid/hello.txt".to_string(),
+        destination: file,
+        checksum: Some(Checksum::Sha256(
+            "9970626666560a32465d4ce10d28f3233365af833e15eed59884d9477862c379".to_string(),
+        )),
+        size: Some(9),
+        label: "hello".to_string(),
+    };
+
+    assert!(should_skip_existing(&task).unwrap());
+}
+
+#[test]
+fn does_not_skip_existing_file_with_wrong_size() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("hello.txt");
+    fs::write(&file, b"minecraft").unwrap();
+
+    let task = DownloadTask {
+        url: "https://example.invalid/hello.txt".to_string(),
+        destination: file,
+        checksum: None,
+        size: Some(10),
+        label: "hello".to_string(),
+    };
+
+    assert!(!should_skip_existing(&task).unwrap());
+}
+
 #[test]
 fn rejects_zip_entry_that_escapes_destination() {
     let dir = tempfile::tempdir().unwrap();
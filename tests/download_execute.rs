@@ -0,0 +1,359 @@
+mod support;
+
+use std::{fs, time::Duration};
+
+use mc_launcher_core::{
+    net::download::{
+        execute_plan_with_options, Checksum, DownloadOptions, DownloadPlan, DownloadTask,
+        RetryPolicy,
+    },
+    progress::{ProgressEvent, SkipReason},
+    LauncherError,
+};
+use support::{sha1_of, Route, StandInServer};
+
+#[test]
+fn downloads_plan_with_multiple_workers() {
+    let server = StandInServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    let mut plan = DownloadPlan::default();
+    for index in 0..20 {
+        let body = format!("object {index}");
+        server.serve(&format!("/objects/{index}"), body.clone());
+        plan.tasks.push(DownloadTask {
+            url: server.url(&format!("/objects/{index}")),
+            destination: dir.path().join("objects").join(index.to_string()),
+            checksum: Some(Checksum::Sha1(sha1_of(body.as_bytes()))),
+            size: None,
+            label: format!("object {index}"),
+        });
+    }
+
+    let mut events = Vec::new();
+    execute_plan_with_options(
+        &plan,
+        &DownloadOptions {
+            workers: 4,
+            ..Default::default()
+        },
+        &mut |event| events.push(event),
+    )
+    .unwrap();
+
+    for index in 0..20 {
+        assert_eq!(
+            fs::read_to_string(dir.path().join("objects").join(index.to_string())).unwrap(),
+            format!("object {index}")
+        );
+    }
+    let finished = events
+        .iter()
+        .filter(|event| matches!(event, ProgressEvent::TaskFinished { .. }))
+        .count();
+    assert_eq!(finished, 20);
+}
+
+#[test]
+fn reports_duplicate_destinations_once() {
+    let server = StandInServer::start();
+    server.serve("/lib.jar", "library");
+    let dir = tempfile::tempdir().unwrap();
+    let task = DownloadTask {
+        url: server.url("/lib.jar"),
+        destination: dir.path().join("lib.jar"),
+        checksum: None,
+        size: None,
+        label: "lib".to_string(),
+    };
+    let plan = DownloadPlan {
+        tasks: vec![task.clone(), task],
+    };
+
+    let mut events = Vec::new();
+    execute_plan_with_options(&plan, &DownloadOptions::default(), &mut |event| {
+        events.push(event)
+    })
+    .unwrap();
+
+    assert_eq!(server.hits("/lib.jar"), 1);
+    assert!(events.contains(&ProgressEvent::TaskSkipped {
+        label: "lib".to_string(),
+        reason: SkipReason::DuplicateDestination,
+    }));
+}
+
+#[test]
+fn stops_on_first_checksum_failure() {
+    let server = StandInServer::start();
+    server.serve("/bad", "unexpected");
+    let dir = tempfile::tempdir().unwrap();
+    let mut plan = DownloadPlan {
+        tasks: vec![DownloadTask {
+            url: server.url("/bad"),
+            destination: dir.path().join("bad"),
+            checksum: Some(Checksum::Sha1("0".repeat(40))),
+            size: None,
+            label: "bad".to_string(),
+        }],
+    };
+    for index in 0..8 {
+        server.serve(&format!("/later/{index}"), "later");
+        plan.tasks.push(DownloadTask {
+            url: server.url(&format!("/later/{index}")),
+            destination: dir.path().join(format!("later-{index}")),
+            checksum: None,
+            size: None,
+            label: format!("later {index}"),
+        });
+    }
+
+    let err = execute_plan_with_options(
+        &plan,
+        &DownloadOptions {
+            workers: 1,
+            ..Default::default()
+        },
+        &mut |_event| {},
+    )
+    .unwrap_err();
+
+    assert!(matches!(err, LauncherError::ChecksumMismatch { .. }));
+    assert!(!dir.path().join("later-0").exists());
+}
+
+#[test]
+fn verifies_sha256_after_download() {
+    let server = StandInServer::start();
+    server.serve("/good.bin", "sha-256 payload");
+    server.serve("/bad.bin", "sha-256 payload");
+    let dir = tempfile::tempdir().unwrap();
+    let task = |name: &str, sha256: String| DownloadTask {
+        url: server.url(&format!("/{name}")),
+        destination: dir.path().join(name),
+        checksum: Some(Checksum::Sha256(sha256)),
+        size: Some(15),
+        label: name.to_string(),
+    };
+    let good = DownloadPlan {
+        tasks: vec![task(
+            "good.bin",
+            "00deef8081ba5fc245415689cf08125714c689c2e93d900daff98f6315a78ff5".to_string(),
+        )],
+    };
+    let bad = DownloadPlan {
+        tasks: vec![task("bad.bin", "0".repeat(64))],
+    };
+
+    execute_plan_with_options(&good, &DownloadOptions::default(), &mut |_event| {}).unwrap();
+    let err =
+        execute_plan_with_options(&bad, &DownloadOptions::default(), &mut |_event| {}).unwrap_err();
+
+    assert!(dir.path().join("good.bin").is_file());
+    assert!(matches!(err, LauncherError::ChecksumMismatch { .. }));
+    assert!(!dir.path().join("bad.bin").exists());
+}
+
+#[test]
+fn rejects_size_mismatch_before_reading_body() {
+    let server = StandInServer::start();
+    server.serve("/short.jar", "short");
+    let dir = tempfile::tempdir().unwrap();
+    let plan = DownloadPlan {
+        tasks: vec![DownloadTask {
+            url: server.url("/short.jar"),
+            destination: dir.path().join("short.jar"),
+            checksum: Some(Checksum::Sha1("0".repeat(40))),
+            size: Some(1024),
+            label: "short".to_string(),
+        }],
+    };
+
+    let mut events = Vec::new();
+    let err = execute_plan_with_options(&plan, &fast_retry(3), &mut |event| events.push(event))
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        LauncherError::SizeMismatch {
+            expected: 1024,
+            actual: 5,
+            ..
+        }
+    ));
+    assert_eq!(server.hits("/short.jar"), 1);
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event, ProgressEvent::BytesReceived { .. })));
+    assert!(!dir.path().join("short.jar").exists());
+    assert!(!dir.path().join("short.jar.part").exists());
+}
+
+fn fast_retry(max_attempts: u32) -> DownloadOptions {
+    DownloadOptions {
+        workers: 2,
+        retry: RetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn retries_transient_server_errors_with_backoff_events() {
+    let server = StandInServer::start();
+    server.route(
+        "/flaky.jar",
+        Route {
+            fail_first: 2,
+            ..Route::ok("eventually")
+        },
+    );
+    let dir = tempfile::tempdir().unwrap();
+    let plan = DownloadPlan {
+        tasks: vec![DownloadTask {
+            url: server.url("/flaky.jar"),
+            destination: dir.path().join("flaky.jar"),
+            checksum: None,
+            size: None,
+            label: "flaky".to_string(),
+        }],
+    };
+
+    let mut events = Vec::new();
+    execute_plan_with_options(&plan, &fast_retry(3), &mut |event| events.push(event)).unwrap();
+
+    let retries = events
+        .iter()
+        .filter_map(|event| match event {
+            ProgressEvent::TaskRetrying { attempt, .. } => Some(*attempt),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(retries, vec![1, 2]);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("flaky.jar")).unwrap(),
+        "eventually"
+    );
+    assert!(!dir.path().join("flaky.jar.part").exists());
+}
+
+#[test]
+fn gives_up_after_policy_attempts_and_leaves_no_destination() {
+    let server = StandInServer::start();
+    server.route(
+        "/down.jar",
+        Route {
+            fail_first: 10,
+            ..Route::ok("never")
+        },
+    );
+    let dir = tempfile::tempdir().unwrap();
+    let plan = DownloadPlan {
+        tasks: vec![DownloadTask {
+            url: server.url("/down.jar"),
+            destination: dir.path().join("down.jar"),
+            checksum: None,
+            size: None,
+            label: "down".to_string(),
+        }],
+    };
+
+    let err = execute_plan_with_options(&plan, &fast_retry(2), &mut |_event| {}).unwrap_err();
+
+    assert!(matches!(err, LauncherError::Network { .. }));
+    assert_eq!(server.hits("/down.jar"), 2);
+    assert!(!dir.path().join("down.jar").exists());
+}
+
+#[test]
+fn does_not_retry_client_errors() {
+    let server = StandInServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    let plan = DownloadPlan {
+        tasks: vec![DownloadTask {
+            url: server.url("/missing.jar"),
+            destination: dir.path().join("missing.jar"),
+            checksum: None,
+            size: None,
+            label: "missing".to_string(),
+        }],
+    };
+
+    execute_plan_with_options(&plan, &fast_retry(4), &mut |_event| {}).unwrap_err();
+
+    assert_eq!(server.hits("/missing.jar"), 1);
+}
+
+#[test]
+fn resumes_partial_download_with_range_request() {
+    let server = StandInServer::start();
+    let body = b"0123456789abcdefghij".to_vec();
+    server.route(
+        "/big.jar",
+        Route {
+            ranges: true,
+            ..Route::ok(body.clone())
+        },
+    );
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("big.jar.part"), &body[..8]).unwrap();
+    let plan = DownloadPlan {
+        tasks: vec![DownloadTask {
+            url: server.url("/big.jar"),
+            destination: dir.path().join("big.jar"),
+            checksum: Some(Checksum::Sha1(sha1_of(&body))),
+            size: None,
+            label: "big".to_string(),
+        }],
+    };
+
+    let mut events = Vec::new();
+    execute_plan_with_options(&plan, &fast_retry(1), &mut |event| events.push(event)).unwrap();
+
+    assert_eq!(server.range_requests(), vec![("/big.jar".to_string(), 8)]);
+    assert_eq!(fs::read(dir.path().join("big.jar")).unwrap(), body);
+    assert!(events.contains(&ProgressEvent::BytesReceived {
+        label: "big".to_string(),
+        received: 20,
+        total: Some(20),
+    }));
+}
+
+#[test]
+fn restarts_when_server_ignores_range() {
+    let server = StandInServer::start();
+    server.serve("/plain.jar", "fresh contents");
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("plain.jar.part"), "stale").unwrap();
+    let plan = DownloadPlan {
+        tasks: vec![DownloadTask {
+            url: server.url("/plain.jar"),
+            destination: dir.path().join("plain.jar"),
+            checksum: None,
+            size: None,
+            label: "plain".to_string(),
+        }],
+    };
+
+    execute_plan_with_options(&plan, &fast_retry(1), &mut |_event| {}).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(dir.path().join("plain.jar")).unwrap(),
+        "fresh contents"
+    );
+}
+
+#[test]
+fn backoff_doubles_until_capped() {
+    let policy = RetryPolicy {
+        max_attempts: 6,
+        initial_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_millis(350),
+    };
+
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(3), Duration::from_millis(350));
+}
@@ -0,0 +1,126 @@
+#![cfg(unix)]
+
+use std::{
+    fs,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use mc_launcher_core::{
+    core::version::VersionJson,
+    prelude::*,
+    process::{GameOutputLine, GameOutputStream},
+};
+
+fn shell(working_dir: &Path, script: &str) -> LaunchCommand {
+    LaunchCommand {
+        executable: PathBuf::from("/bin/sh"),
+        args: vec!["-c".to_string(), script.to_string()],
+        working_dir: working_dir.to_path_buf(),
+        env: vec![("GREETING".to_string(), "hello".to_string())],
+    }
+}
+
+#[test]
+fn streams_output_lines_and_exit_status() {
+    let dir = tempfile::tempdir().unwrap();
+    let working_dir = dir.path().join("game");
+    let before = SystemTime::now();
+
+    let mut game = GameProcess::spawn(&shell(
+        &working_dir,
+        "echo \"$GREETING\"; echo warn >&2; printf 'no newline'; exit 3",
+    ))
+    .unwrap();
+
+    assert!(game.pid() > 0);
+    assert!(game.started_at() >= before);
+    let mut lines: Vec<GameOutputLine> = game.lines().collect();
+    lines.sort_by_key(|line| line.stream == GameOutputStream::Stderr);
+    assert_eq!(
+        lines,
+        vec![
+            GameOutputLine {
+                stream: GameOutputStream::Stdout,
+                line: "hello".to_string(),
+            },
+            GameOutputLine {
+                stream: GameOutputStream::Stdout,
+                line: "no newline".to_string(),
+            },
+            GameOutputLine {
+                stream: GameOutputStream::Stderr,
+                line: "warn".to_string(),
+            },
+        ]
+    );
+    let status = game.wait().unwrap();
+    assert_eq!(status.code(), Some(3));
+    assert_eq!(game.exit_status(), Some(status));
+    assert!(working_dir.is_dir());
+    let played = game.play_duration();
+    std::thread::sleep(Duration::from_millis(20));
+    assert_eq!(game.play_duration(), played);
+}
+
+#[test]
+fn wait_timeout_and_kill_stop_a_running_game() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut game = GameProcess::spawn(&shell(dir.path(), "echo started; exec sleep 30")).unwrap();
+
+    assert_eq!(
+        game.next_line_timeout(Duration::from_secs(5)).unwrap().line,
+        "started"
+    );
+    assert_eq!(game.wait_timeout(Duration::from_millis(50)).unwrap(), None);
+    assert!(game.play_duration() >= Duration::from_millis(50));
+
+    let status = game.kill().unwrap();
+
+    assert!(!status.success());
+    assert_eq!(game.try_wait().unwrap(), Some(status));
+    assert_eq!(game.kill().unwrap(), status);
+    assert!(game.play_duration() < Duration::from_secs(30));
+}
+
+#[test]
+fn launcher_launches_version_with_configured_java() {
+    let dir = tempfile::tempdir().unwrap();
+    let java = dir.path().join("java");
+    fs::write(
+        &java,
+        "#!/bin/sh\nfor arg in \"$@\"; do echo \"$arg\"; done\n",
+    )
+    .unwrap();
+    fs::set_permissions(&java, fs::Permissions::from_mode(0o755)).unwrap();
+    let version: VersionJson = serde_json::from_str(
+        r#"{
+            "id":"1.20.4",
+            "type":"release",
+            "mainClass":"net.minecraft.client.main.Main",
+            "arguments":{"jvm":[],"game":["--username","${auth_player_name}"]},
+            "libraries":[]
+        }"#,
+    )
+    .unwrap();
+    let launcher = Launcher::new(dir.path().join("minecraft"));
+
+    let mut game = launcher
+        .launch(
+            &version,
+            LaunchOptions {
+                account: Account::offline("Steve"),
+                java_executable: Some(java),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let args: Vec<String> = game.lines().map(|line| line.line).collect();
+    assert!(game.wait().unwrap().success());
+    assert!(args
+        .windows(2)
+        .any(|window| window == ["--username", "Steve"]));
+    assert!(args.contains(&"net.minecraft.client.main.Main".to_string()));
+}
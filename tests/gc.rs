@@ -0,0 +1,217 @@
+mod support;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use mc_launcher_core::{
+    install::gc::{plan_garbage_collection_for_platform, GcItemKind, GcReport},
+    platform::{Arch, Os, Platform},
+    prelude::*,
+};
+use support::write_zip;
+
+fn write(path: &Path, bytes: &[u8]) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, bytes).unwrap();
+}
+
+fn write_installer(path: &Path, profile: &str) {
+    write_zip(path, &[("install_profile.json", profile.as_bytes())]);
+}
+
+fn paths(report: &GcReport, kind: GcItemKind) -> Vec<PathBuf> {
+    report
+        .items_of(kind)
+        .map(|item| item.path.clone())
+        .collect()
+}
+
+#[test]
+fn reclaims_only_unreferenced_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    let libraries = root.join("libraries");
+    let objects = root.join("assets/objects");
+
+    write(
+        &root.join("versions/1.20.4/1.20.4.json"),
+        br#"{
+            "id":"1.20.4",
+            "assetIndex":{"id":"12","sha1":"x","size":1,"totalSize":1,"url":"https://example.invalid/12.json"},
+            "libraries":[{"name":"com.example:demo:1.0","downloads":{"artifact":{"path":"com/example/demo/1.0/demo-1.0.jar","sha1":"x","size":4,"url":"https://example.invalid/demo.jar"}}}]
+        }"#,
+    );
+    write(
+        &root.join("versions/fabric/fabric.json"),
+        br#"{"id":"fabric","inheritsFrom":"1.20.4","libraries":[{"name":"net.fabricmc:fabric-loader:0.15.0","url":"https://maven.fabricmc.net/"}]}"#,
+    );
+    write(
+        &root.join("versions/neoforge/neoforge.json"),
+        br#"{"id":"neoforge","inheritsFrom":"1.20.4","libraries":[]}"#,
+    );
+    write_installer(
+        &root.join("versions/.installers/neoforge-20.4.1-installer.jar"),
+        r#"{"version":"neoforge","minecraft":"1.20.4","data":{"MC_SLIM":{"client":"[net.minecraft:client:1.20.4:slim]","server":"[net.minecraft:server:1.20.4:slim]"}},"libraries":[{"name":"net.neoforged:installertools:2.0"}]}"#,
+    );
+    write_installer(
+        &root.join("versions/.installers/forge-47.2.0-installer.jar"),
+        r#"{"version":"1.20.1-forge-47.2.0","minecraft":"1.20.1"}"#,
+    );
+    write(
+        &root.join("versions/.installers/forge-47.3.0-installer.jar.part"),
+        b"partial",
+    );
+
+    write(
+        &libraries.join("com/example/demo/1.0/demo-1.0.jar"),
+        b"demo",
+    );
+    write(
+        &libraries.join("net/fabricmc/fabric-loader/0.15.0/fabric-loader-0.15.0.jar"),
+        b"loader",
+    );
+    write(
+        &libraries.join("net/minecraft/client/1.20.4/client-1.20.4-slim.jar"),
+        b"slim",
+    );
+    write(
+        &libraries.join("net/neoforged/installertools/2.0/installertools-2.0.jar"),
+        b"tools",
+    );
+    let stale_library = libraries.join("com/example/old/0.9/old-0.9.jar");
+    write(&stale_library, b"old library");
+
+    let used_hash = "aa00000000000000000000000000000000000000";
+    let unused_hash = "bb00000000000000000000000000000000000000";
+    write(
+        &root.join("assets/indexes/12.json"),
+        format!(r#"{{"objects":{{"a":{{"hash":"{used_hash}","size":4}}}}}}"#).as_bytes(),
+    );
+    let stale_index = root.join("assets/indexes/5.json");
+    write(&stale_index, b"{\"objects\":{}}");
+    write(&objects.join("aa").join(used_hash), b"used");
+    let stale_object = objects.join("bb").join(unused_hash);
+    write(&stale_object, b"unused");
+
+    write(&root.join("versions/1.20.4/natives/lwjgl.so"), b"native");
+    let stale_natives = root.join("versions/1.20.4/1.20.4-natives-1700000000");
+    write(&stale_natives.join("lwjgl.so"), b"native");
+    let orphaned_natives = root.join("versions/1.19/natives");
+    write(&orphaned_natives.join("lwjgl.so"), b"native");
+
+    let launcher = Launcher::new(root);
+    let report = launcher.plan_garbage_collection().unwrap();
+
+    assert_eq!(report.versions, ["1.20.4", "fabric", "neoforge"]);
+    assert_eq!(
+        paths(&report, GcItemKind::Library),
+        vec![stale_library.clone()]
+    );
+    assert_eq!(
+        paths(&report, GcItemKind::AssetObject),
+        vec![stale_object.clone()]
+    );
+    assert_eq!(
+        paths(&report, GcItemKind::AssetIndex),
+        vec![stale_index.clone()]
+    );
+    assert_eq!(
+        paths(&report, GcItemKind::Installer),
+        vec![
+            root.join("versions/.installers/forge-47.2.0-installer.jar"),
+            root.join("versions/.installers/forge-47.3.0-installer.jar.part"),
+        ]
+    );
+    assert_eq!(
+        paths(&report, GcItemKind::Natives),
+        vec![orphaned_natives.clone(), stale_natives.clone()]
+    );
+    let installer_size = fs::metadata(root.join("versions/.installers/forge-47.2.0-installer.jar"))
+        .unwrap()
+        .len();
+    assert_eq!(
+        report.reclaimable_bytes(),
+        11 + 6 + 14 + installer_size + 7 + 6 + 6
+    );
+    assert!(stale_library.is_file());
+
+    let collected = launcher.collect_garbage().unwrap();
+    assert_eq!(collected, report);
+    assert!(!stale_library.exists());
+    assert!(!libraries.join("com/example/old").exists());
+    assert!(!objects.join("bb").exists());
+    assert!(!stale_natives.exists());
+    assert!(!orphaned_natives.exists());
+    assert!(libraries
+        .join("com/example/demo/1.0/demo-1.0.jar")
+        .is_file());
+    assert!(objects.join("aa").join(used_hash).is_file());
+    assert!(root.join("versions/1.20.4/natives/lwjgl.so").is_file());
+    assert!(root
+        .join("versions/.installers/neoforge-20.4.1-installer.jar")
+        .is_file());
+
+    assert!(launcher.plan_garbage_collection().unwrap().items.is_empty());
+}
+
+#[test]
+fn refuses_to_collect_when_a_version_cannot_load() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        &dir.path().join("versions/broken/broken.json"),
+        br#"{"id":"broken","inheritsFrom":"missing"}"#,
+    );
+    let library = dir
+        .path()
+        .join("libraries/com/example/demo/1.0/demo-1.0.jar");
+    write(&library, b"demo");
+
+    assert!(Launcher::new(dir.path()).collect_garbage().is_err());
+    assert!(library.is_file());
+}
+
+#[test]
+fn keeps_libraries_patched_in_for_macos_arm64() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    let libraries = root.join("libraries");
+    write(
+        &root.join("versions/1.12.2/1.12.2.json"),
+        br#"{
+            "id":"1.12.2",
+            "libraries":[
+                {"name":"org.lwjgl.lwjgl:lwjgl:2.9.2-nightly-20140822"},
+                {"name":"ca.weblite:java-objc-bridge:1.0.0"}
+            ]
+        }"#,
+    );
+    let raw = libraries
+        .join("org/lwjgl/lwjgl/lwjgl/2.9.2-nightly-20140822/lwjgl-2.9.2-nightly-20140822.jar");
+    let lwjgl = libraries
+        .join("org/lwjgl/lwjgl/lwjgl/2.9.4-nightly-20150209/lwjgl-2.9.4-nightly-20150209.jar");
+    let bridge = libraries
+        .join("ca/weblite/java-objc-bridge/1.1.0-mmachina.1/java-objc-bridge-1.1.0-mmachina.1.jar");
+    let stale = libraries.join("com/example/old/0.9/old-0.9.jar");
+    for path in [&raw, &lwjgl, &bridge, &stale] {
+        write(path, b"jar");
+    }
+
+    let mac_arm64 = Platform {
+        os: Os::MacOs,
+        arch: Arch::Aarch64,
+    };
+    let report = plan_garbage_collection_for_platform(root, mac_arm64).unwrap();
+    assert_eq!(paths(&report, GcItemKind::Library), vec![stale.clone()]);
+
+    let linux = Platform {
+        os: Os::Linux,
+        arch: Arch::X86_64,
+    };
+    let report = plan_garbage_collection_for_platform(root, linux).unwrap();
+    assert_eq!(
+        paths(&report, GcItemKind::Library),
+        vec![bridge, stale, lwjgl]
+    );
+}
@@ -0,0 +1,259 @@
+mod support;
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use mc_launcher_core::{
+    install::forge::{read_install_profile, substitute_arg, Processor},
+    LauncherError,
+};
+use support::{sha1_of, write_zip, zip_bytes};
+
+#[test]
+fn substitutes_data_and_artifact_placeholders() {
+    let libraries = Path::new("/mc/libraries");
+    let data = HashMap::from([
+        ("SIDE".to_string(), "client".to_string()),
+        ("MAPPINGS".to_string(), "/tmp/mappings.txt".to_string()),
+    ]);
+
+    assert_eq!(
+        substitute_arg("--side={SIDE}", &data, libraries).unwrap(),
+        "--side=client"
+    );
+    assert_eq!(
+        substitute_arg("{MAPPINGS}", &data, libraries).unwrap(),
+        "/tmp/mappings.txt"
+    );
+    assert_eq!(
+        substitute_arg(r"\{SIDE\}", &data, libraries).unwrap(),
+        "{SIDE}"
+    );
+    assert_eq!(
+        PathBuf::from(
+            substitute_arg(
+                "[net.minecraftforge:forge:1.20.1-47.2.0:client]",
+                &data,
+                libraries
+            )
+            .unwrap()
+        ),
+        libraries.join("net/minecraftforge/forge/1.20.1-47.2.0/forge-1.20.1-47.2.0-client.jar")
+    );
+    assert!(matches!(
+        substitute_arg("{MISSING}", &data, libraries),
+        Err(LauncherError::MissingField { .. })
+    ));
+    assert!(substitute_arg("{SIDE", &data, libraries).is_err());
+}
+
+#[test]
+fn reads_modern_profile_and_detects_legacy_installers() {
+    let dir = tempfile::tempdir().unwrap();
+    let modern = dir.path().join("modern.jar");
+    write_zip(
+        &modern,
+        &[(
+            "install_profile.json",
+            br#"{
+                "spec":1,
+                "version":"1.20.1-forge-47.2.0",
+                "minecraft":"1.20.1",
+                "data":{"SIDE_DATA":{"client":"'c'","server":"'s'"}},
+                "processors":[{"sides":["server"],"jar":"a:b:1"},{"jar":"a:c:1"}]
+            }"#,
+        )],
+    );
+    let legacy = dir.path().join("legacy.jar");
+    write_zip(
+        &legacy,
+        &[(
+            "install_profile.json",
+            br#"{"install":{"target":"1.12.2-forge-14.23.5.2860"},"versionInfo":{"id":"1.12.2-forge-14.23.5.2860"}}"#,
+        )],
+    );
+
+    let profile = read_install_profile(&modern).unwrap().unwrap();
+
+    assert_eq!(profile.json, "/version.json");
+    assert_eq!(profile.data["SIDE_DATA"].client, "'c'");
+    let client: Vec<&Processor> = profile
+        .processors
+        .iter()
+        .filter(|processor| processor.runs_on_client())
+        .collect();
+    assert_eq!(client.len(), 1);
+    assert_eq!(client[0].jar, "a:c:1");
+    assert_eq!(read_install_profile(&legacy).unwrap(), None);
+}
+
+#[cfg(unix)]
+mod processors {
+    use std::os::unix::fs::PermissionsExt;
+
+    use mc_launcher_core::{
+        install::{forge::install_from_installer, loader::InstallerInvocation},
+        loader::LoaderKind,
+        net::download::DownloadOptions,
+        progress::{ProgressEvent, SkipReason},
+    };
+
+    use super::*;
+
+    const PATCHED: &[u8] = b"patched client";
+
+    /// Writes a stand-in `java` that records its arguments and writes the
+    /// `--output` file, standing in for a real processor jar.
+    fn fake_java(dir: &Path) -> PathBuf {
+        let java = dir.join("java");
+        fs::write(
+            &java,
+            format!(
+                "#!/bin/sh\necho \"$@\" > \"{log}\"\nwhile [ $# -gt 0 ]; do\n  if [ \"$1\" = --output ]; then shift; mkdir -p \"$(dirname \"$1\")\"; printf 'patched client' > \"$1\"; fi\n  shift\ndone\n",
+                log = dir.join("java-args.log").display()
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&java, fs::Permissions::from_mode(0o755)).unwrap();
+        java
+    }
+
+    fn installer(dir: &Path, output_sha1: &str) -> PathBuf {
+        let processor_jar = zip_bytes(&[(
+            "META-INF/MANIFEST.MF",
+            b"Manifest-Version: 1.0\r\nMain-Class: net.minecraftforge.installertools.Co\r\n nsoleTool\r\n",
+        )]);
+        let profile = format!(
+            r#"{{
+                "spec":1,
+                "version":"1.20.1-forge-47.2.0",
+                "json":"/version.json",
+                "minecraft":"1.20.1",
+                "data":{{
+                    "PATCHED":{{"client":"[net.minecraftforge:forge:1.20.1-47.2.0:client]","server":"[net.minecraftforge:forge:1.20.1-47.2.0:server]"}},
+                    "PATCHED_SHA":{{"client":"'{output_sha1}'","server":"''"}},
+                    "BINPATCH":{{"client":"/data/client.lzma","server":"/data/server.lzma"}}
+                }},
+                "processors":[
+                    {{"sides":["server"],"jar":"net.minecraftforge:missing:1.0","args":[]}},
+                    {{
+                        "jar":"net.minecraftforge:installertools:1.3.0",
+                        "classpath":["net.minecraftforge:srgutils:0.4.3"],
+                        "args":["--task","PATCH","--input","{{MINECRAFT_JAR}}","--patch","{{BINPATCH}}","--side","{{SIDE}}","--output","{{PATCHED}}"],
+                        "outputs":{{"{{PATCHED}}":"{{PATCHED_SHA}}"}}
+                    }}
+                ],
+                "libraries":[
+                    {{"name":"net.minecraftforge:installertools:1.3.0","downloads":{{"artifact":{{"path":"net/minecraftforge/installertools/1.3.0/installertools-1.3.0.jar","url":"","sha1":"{jar_sha1}","size":{jar_size}}}}}}}
+                ]
+            }}"#,
+            jar_sha1 = sha1_of(&processor_jar),
+            jar_size = processor_jar.len(),
+        );
+        let version = br#"{
+            "id":"1.20.1-forge-47.2.0",
+            "inheritsFrom":"1.20.1",
+            "type":"release",
+            "mainClass":"cpw.mods.bootstraplauncher.BootstrapLauncher",
+            "libraries":[]
+        }"#;
+        let path = dir.join("forge-installer.jar");
+        write_zip(
+            &path,
+            &[
+                ("install_profile.json", profile.as_bytes()),
+                ("version.json", version),
+                ("data/client.lzma", b"binpatch"),
+                (
+                    "maven/net/minecraftforge/installertools/1.3.0/installertools-1.3.0.jar",
+                    &processor_jar,
+                ),
+            ],
+        );
+        path
+    }
+
+    #[test]
+    fn runs_client_processors_and_checks_outputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let minecraft_dir = dir.path().join("minecraft");
+        let invocation = InstallerInvocation {
+            loader: LoaderKind::Forge,
+            java_executable: fake_java(dir.path()),
+            installer_path: installer(dir.path(), &sha1_of(PATCHED)),
+            minecraft_dir: minecraft_dir.clone(),
+        };
+
+        let version_id =
+            install_from_installer(&invocation, &DownloadOptions::default(), &mut |_event| {})
+                .unwrap();
+
+        assert_eq!(version_id, "1.20.1-forge-47.2.0");
+        assert!(minecraft_dir
+            .join("versions/1.20.1-forge-47.2.0/1.20.1-forge-47.2.0.json")
+            .is_file());
+        let patched = minecraft_dir.join(
+            "libraries/net/minecraftforge/forge/1.20.1-47.2.0/forge-1.20.1-47.2.0-client.jar",
+        );
+        assert_eq!(fs::read(&patched).unwrap(), PATCHED);
+        let args = fs::read_to_string(dir.path().join("java-args.log")).unwrap();
+        let libraries = minecraft_dir.join("libraries");
+        let classpath = format!(
+            "{}:{}",
+            libraries
+                .join("net/minecraftforge/installertools/1.3.0/installertools-1.3.0.jar")
+                .display(),
+            libraries
+                .join("net/minecraftforge/srgutils/0.4.3/srgutils-0.4.3.jar")
+                .display()
+        );
+        assert!(args.starts_with(&format!(
+            "-cp {classpath} net.minecraftforge.installertools.ConsoleTool --task PATCH --input {} --patch ",
+            minecraft_dir.join("versions/1.20.1/1.20.1.jar").display()
+        )));
+        assert!(args.ends_with(&format!(
+            "client.lzma --side client --output {}\n",
+            patched.display()
+        )));
+
+        let mut events = Vec::new();
+        install_from_installer(&invocation, &DownloadOptions::default(), &mut |event| {
+            events.push(event)
+        })
+        .unwrap();
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ProgressEvent::TaskSkipped {
+                reason: SkipReason::ChecksumMatched,
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn rejects_processor_output_with_wrong_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let minecraft_dir = dir.path().join("minecraft");
+        let invocation = InstallerInvocation {
+            loader: LoaderKind::Forge,
+            java_executable: fake_java(dir.path()),
+            installer_path: installer(dir.path(), &sha1_of(b"something else")),
+            minecraft_dir: minecraft_dir.clone(),
+        };
+
+        let err =
+            install_from_installer(&invocation, &DownloadOptions::default(), &mut |_event| {})
+                .unwrap_err();
+
+        assert!(matches!(err, LauncherError::ChecksumMismatch { .. }));
+        assert!(!minecraft_dir
+            .join("libraries/net/minecraftforge/forge/1.20.1-47.2.0/forge-1.20.1-47.2.0-client.jar")
+            .exists());
+        assert!(!minecraft_dir
+            .join("versions/1.20.1-forge-47.2.0/1.20.1-forge-47.2.0.json")
+            .exists());
+    }
+}
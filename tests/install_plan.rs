@@ -9,7 +9,7 @@ use mc_launcher_core::{
 };
 
 #[test]
-fn plans_client_library_and_asset_index_downloads() {
+fn plans_client_library_asset_index_and_log_config_downloads() {
     let version: VersionJson =
         serde_json::from_str(include_str!("fixtures/version_1_20_4_min.json")).unwrap();
     let dir = tempfile::tempdir().unwrap();
@@ -31,6 +31,9 @@ fn plans_client_library_and_asset_index_downloads() {
     assert!(destinations
         .iter()
         .any(|path| path.ends_with("assets/indexes/12.json")));
+    assert!(destinations
+        .iter()
+        .any(|path| path.ends_with("assets/log_configs/client-1.12.xml")));
 }
 
 #[test]
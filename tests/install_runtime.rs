@@ -0,0 +1,219 @@
+mod support;
+
+use std::fs;
+
+use mc_launcher_core::{
+    install::runtime::{
+        install_runtime_manifest, runtime_home, runtime_platform_key, RuntimeManifest,
+    },
+    net::{download::DownloadOptions, mirror::MirrorConfig},
+    platform::{Arch, Os, Platform},
+    prelude::*,
+    runtime::{installed_runtimes, java_executable, repair_runtime, verify_runtime},
+    LauncherError,
+};
+use support::{sha1_of, stand_in_mirror, StandInServer};
+
+fn file_entry(url: &str, bytes: &[u8], executable: bool) -> String {
+    format!(
+        r#"{{"type":"file","executable":{executable},"downloads":{{"raw":{{"sha1":"{}","size":{},"url":"{url}"}}}}}}"#,
+        sha1_of(bytes),
+        bytes.len()
+    )
+}
+
+/// Serves a vanilla version that requires `component` and a runtime for the
+/// current platform.
+fn serve_version_with_runtime(server: &StandInServer, component: &str) {
+    let client = b"client jar".to_vec();
+    let version = format!(
+        r#"{{
+            "id":"1.20.4",
+            "type":"release",
+            "mainClass":"net.minecraft.client.main.Main",
+            "javaVersion":{{"component":"{component}","majorVersion":17}},
+            "downloads":{{"client":{{"sha1":"{}","size":{},"url":"https://piston-data.mojang.com/v1/objects/client.jar"}}}},
+            "libraries":[]
+        }}"#,
+        sha1_of(&client),
+        client.len()
+    );
+    server.serve(
+        "/mc/game/version_manifest_v2.json",
+        r#"{"versions":[{"id":"1.20.4","url":"https://piston-data.mojang.com/v1/packages/1.20.4.json"}]}"#,
+    );
+    server.serve("/v1/packages/1.20.4.json", version);
+    server.serve("/v1/objects/client.jar", client);
+
+    let java = b"#!/bin/sh\necho java\n".to_vec();
+    let release = b"JAVA_VERSION=\"17.0.8\"\n".to_vec();
+    let manifest = format!(
+        r#"{{"files":{{
+            "bin":{{"type":"directory"}},
+            "bin/java":{java},
+            "release":{release},
+            "lib/release":{{"type":"link","target":"../release"}}
+        }}}}"#,
+        java = file_entry(
+            "https://piston-data.mojang.com/v1/objects/java",
+            &java,
+            true
+        ),
+        release = file_entry(
+            "https://piston-data.mojang.com/v1/objects/release",
+            &release,
+            false
+        ),
+    );
+    let platform = runtime_platform_key(Platform::current()).unwrap();
+    let index = format!(
+        r#"{{"{platform}":{{"{component}":[{{
+            "availability":{{"group":1,"progress":100}},
+            "manifest":{{"sha1":"{}","size":{},"url":"https://piston-data.mojang.com/v1/objects/manifest.json"}},
+            "version":{{"name":"17.0.8","released":"2023-08-08T00:00:00+00:00"}}
+        }}]}}}}"#,
+        sha1_of(manifest.as_bytes()),
+        manifest.len()
+    );
+    server.serve("/runtime/all.json", index);
+    server.serve("/v1/objects/manifest.json", manifest);
+    server.serve("/v1/objects/java", java);
+    server.serve("/v1/objects/release", release);
+}
+
+#[test]
+fn maps_platforms_to_mojang_runtime_keys() {
+    let key = |os, arch| runtime_platform_key(Platform { os, arch });
+
+    assert_eq!(key(Os::Linux, Arch::X86_64), Some("linux"));
+    assert_eq!(key(Os::MacOs, Arch::Aarch64), Some("mac-os-arm64"));
+    assert_eq!(key(Os::Windows, Arch::X86_64), Some("windows-x64"));
+    assert_eq!(key(Os::Linux, Arch::Aarch64), None);
+}
+
+#[test]
+fn auto_policy_installs_runtime_named_by_version() {
+    if runtime_platform_key(Platform::current()).is_none() {
+        return;
+    }
+    let server = StandInServer::start();
+    serve_version_with_runtime(&server, "java-runtime-gamma");
+    let dir = tempfile::tempdir().unwrap();
+    let launcher = Launcher::new(dir.path()).with_mirrors(MirrorConfig {
+        mirrors: vec![stand_in_mirror("local", &server)],
+        fallback_to_official: false,
+    });
+
+    let install = launcher.install(InstallRequest::vanilla("1.20.4")).unwrap();
+
+    let home = runtime_home(dir.path(), "java-runtime-gamma", Platform::current()).unwrap();
+    let java = install.java_executable.unwrap();
+    assert_eq!(java, home.join("bin").join("java"));
+    assert_eq!(
+        fs::read_to_string(home.parent().unwrap().join(".version")).unwrap(),
+        "17.0.8"
+    );
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        assert_ne!(fs::metadata(&java).unwrap().permissions().mode() & 0o111, 0);
+        assert_eq!(
+            fs::read_to_string(home.join("lib/release")).unwrap(),
+            "JAVA_VERSION=\"17.0.8\"\n"
+        );
+    }
+}
+
+#[test]
+fn never_policy_skips_runtime_install() {
+    let server = StandInServer::start();
+    serve_version_with_runtime(&server, "java-runtime-gamma");
+    let dir = tempfile::tempdir().unwrap();
+    let launcher = Launcher::new(dir.path()).with_mirrors(MirrorConfig {
+        mirrors: vec![stand_in_mirror("local", &server)],
+        fallback_to_official: false,
+    });
+
+    let install = launcher
+        .install(InstallRequest {
+            java: JavaInstallPolicy::Never,
+            ..InstallRequest::vanilla("1.20.4")
+        })
+        .unwrap();
+
+    assert_eq!(install.java_executable, None);
+    assert_eq!(server.hits("/runtime/all.json"), 0);
+    assert!(!dir.path().join("runtime").exists());
+}
+
+#[test]
+fn rejects_runtime_link_that_escapes_home() {
+    let dir = tempfile::tempdir().unwrap();
+    let manifest: RuntimeManifest =
+        serde_json::from_str(r#"{"files":{"lib/evil":{"type":"link","target":"../../outside"}}}"#)
+            .unwrap();
+
+    let err = install_runtime_manifest(
+        &manifest,
+        dir.path().join("home"),
+        &DownloadOptions::default(),
+        &mut |_event| {},
+    )
+    .unwrap_err();
+
+    assert!(matches!(err, LauncherError::UnsafePath { .. }));
+    assert!(!dir.path().join("home/lib/evil").exists());
+}
+
+#[test]
+fn verifies_and_repairs_installed_runtime() {
+    let platform = Platform::current();
+    if runtime_platform_key(platform).is_none() {
+        return;
+    }
+    let server = StandInServer::start();
+    serve_version_with_runtime(&server, "java-runtime-gamma");
+    let dir = tempfile::tempdir().unwrap();
+    let options = DownloadOptions {
+        mirrors: MirrorConfig {
+            mirrors: vec![stand_in_mirror("local", &server)],
+            fallback_to_official: false,
+        },
+        ..Default::default()
+    };
+    let launcher = Launcher::new(dir.path()).with_download_options(options.clone());
+    launcher.install(InstallRequest::vanilla("1.20.4")).unwrap();
+
+    let report = verify_runtime("java-runtime-gamma", dir.path(), platform).unwrap();
+    assert_eq!(report.checked, 2);
+    assert!(report.is_ok());
+    assert_eq!(
+        installed_runtimes(dir.path()).unwrap(),
+        vec!["java-runtime-gamma".to_string()]
+    );
+
+    let home = runtime_home(dir.path(), "java-runtime-gamma", platform).unwrap();
+    fs::write(home.join("release"), "tampered").unwrap();
+    fs::remove_file(home.join("bin/java")).unwrap();
+    let report = verify_runtime("java-runtime-gamma", dir.path(), platform).unwrap();
+    assert_eq!(report.corrupt, vec![home.join("release")]);
+    assert_eq!(report.missing, vec![home.join("bin/java")]);
+
+    let repaired = repair_runtime(
+        "java-runtime-gamma",
+        dir.path(),
+        platform,
+        &options,
+        &mut |_event| {},
+    )
+    .unwrap();
+    assert_eq!(repaired, report);
+    assert!(verify_runtime("java-runtime-gamma", dir.path(), platform)
+        .unwrap()
+        .is_ok());
+    assert_eq!(
+        java_executable("java-runtime-gamma", dir.path(), platform),
+        Some(home.join("bin/java"))
+    );
+}
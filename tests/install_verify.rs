@@ -0,0 +1,100 @@
+mod support;
+
+use std::fs;
+
+use mc_launcher_core::{net::mirror::MirrorConfig, prelude::*};
+use support::{sha1_of, stand_in_mirror, StandInServer};
+
+#[test]
+fn verifies_and_repairs_only_broken_files() {
+    let server = StandInServer::start();
+    let client = b"client jar".to_vec();
+    let library = b"library jar".to_vec();
+    let object = b"sound".to_vec();
+    let object_hash = sha1_of(&object);
+    let asset_index = format!(
+        r#"{{"objects":{{"minecraft/sounds/a.ogg":{{"hash":"{object_hash}","size":5}}}}}}"#
+    );
+    let version = format!(
+        r#"{{
+            "id":"1.20.4",
+            "type":"release",
+            "mainClass":"net.minecraft.client.main.Main",
+            "assets":"12",
+            "assetIndex":{{"id":"12","sha1":"{index_sha1}","size":{index_size},"totalSize":5,"url":"https://piston-data.mojang.com/v1/objects/index/12.json"}},
+            "downloads":{{"client":{{"sha1":"{client_sha1}","size":10,"url":"https://piston-data.mojang.com/v1/objects/client/client.jar"}}}},
+            "libraries":[
+                {{"name":"com.example:demo:1.0","downloads":{{"artifact":{{"path":"com/example/demo/1.0/demo-1.0.jar","sha1":"{library_sha1}","size":11,"url":"https://libraries.minecraft.net/com/example/demo/1.0/demo-1.0.jar"}}}}}},
+                {{"name":"com.example:plain:1.0","url":"https://libraries.minecraft.net/"}}
+            ]
+        }}"#,
+        index_sha1 = sha1_of(asset_index.as_bytes()),
+        index_size = asset_index.len(),
+        client_sha1 = sha1_of(&client),
+        library_sha1 = sha1_of(&library),
+    );
+    let object_route = format!("/assets/{}/{object_hash}", &object_hash[..2]);
+    server.serve(
+        "/mc/game/version_manifest_v2.json",
+        r#"{"versions":[{"id":"1.20.4","url":"https://piston-data.mojang.com/v1/packages/1.20.4.json"}]}"#,
+    );
+    server.serve("/v1/packages/1.20.4.json", version);
+    server.serve("/v1/objects/client/client.jar", client.clone());
+    server.serve("/v1/objects/index/12.json", asset_index);
+    server.serve("/maven/com/example/demo/1.0/demo-1.0.jar", library);
+    server.serve("/maven/com/example/plain/1.0/plain-1.0.jar", "plain jar");
+    server.serve(&object_route, object.clone());
+
+    let dir = tempfile::tempdir().unwrap();
+    let launcher = Launcher::new(dir.path()).with_mirrors(MirrorConfig {
+        mirrors: vec![stand_in_mirror("local", &server)],
+        fallback_to_official: false,
+    });
+    launcher.install(InstallRequest::vanilla("1.20.4")).unwrap();
+
+    let client_path = dir.path().join("versions/1.20.4/1.20.4.jar");
+    let plain_path = dir
+        .path()
+        .join("libraries/com/example/plain/1.0/plain-1.0.jar");
+    let object_path = dir
+        .path()
+        .join("assets/objects")
+        .join(&object_hash[..2])
+        .join(&object_hash);
+
+    let report = launcher.verify("1.20.4").unwrap();
+    assert!(report.is_ok());
+    assert_eq!(report.checked, 5);
+    assert_eq!(report.unverifiable, vec![plain_path.clone()]);
+
+    fs::remove_file(&object_path).unwrap();
+    fs::write(&client_path, b"tampered!!").unwrap();
+    let report = launcher.verify("1.20.4").unwrap();
+    assert!(!report.is_ok());
+    assert_eq!(report.missing, vec![object_path.clone()]);
+    assert_eq!(report.corrupt, vec![client_path.clone()]);
+
+    let library_hits = server.hits("/maven/com/example/demo/1.0/demo-1.0.jar");
+    let plain_hits = server.hits("/maven/com/example/plain/1.0/plain-1.0.jar");
+    let client_hits = server.hits("/v1/objects/client/client.jar");
+    let object_hits = server.hits(&object_route);
+
+    let repaired = launcher.repair("1.20.4").unwrap();
+    assert_eq!(repaired, report);
+    assert_eq!(fs::read(&client_path).unwrap(), client);
+    assert_eq!(fs::read(&object_path).unwrap(), object);
+    assert_eq!(
+        server.hits("/v1/objects/client/client.jar"),
+        client_hits + 1
+    );
+    assert_eq!(server.hits(&object_route), object_hits + 1);
+    assert_eq!(
+        server.hits("/maven/com/example/demo/1.0/demo-1.0.jar"),
+        library_hits
+    );
+    assert_eq!(
+        server.hits("/maven/com/example/plain/1.0/plain-1.0.jar"),
+        plain_hits
+    );
+    assert!(launcher.verify("1.20.4").unwrap().is_ok());
+}
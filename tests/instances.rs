@@ -0,0 +1,183 @@
+use std::fs;
+
+use mc_launcher_core::{
+    instance::{InstanceLoader, Resolution, INSTANCE_FILE},
+    loader::common::{LoaderSpec, LoaderVersion},
+    prelude::*,
+};
+
+fn fabric_config() -> InstanceConfig {
+    InstanceConfig {
+        minecraft_version: "1.20.4".to_string(),
+        version_id: "fabric-loader-0.15.0-1.20.4".to_string(),
+        loader: Some(InstanceLoader {
+            kind: LoaderKind::Fabric,
+            version: "0.15.0".to_string(),
+        }),
+        max_memory_mb: Some(4096),
+        jvm_args: vec!["-XX:+UseG1GC".to_string()],
+        resolution: Some(Resolution {
+            width: 1280,
+            height: 720,
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn manages_instance_lifecycle() {
+    let dir = tempfile::tempdir().unwrap();
+    let launcher = Launcher::new(dir.path());
+
+    let created = launcher
+        .create_instance("Survival", fabric_config())
+        .unwrap();
+    assert_eq!(created.path, dir.path().join("instances/Survival"));
+    assert!(created.game_directory().join("mods").is_dir());
+    let saved: serde_json::Value =
+        serde_json::from_slice(&fs::read(created.path.join(INSTANCE_FILE)).unwrap()).unwrap();
+    assert_eq!(saved["versionId"], "fabric-loader-0.15.0-1.20.4");
+    assert_eq!(saved["loader"]["kind"], "fabric");
+    assert!(matches!(
+        launcher.create_instance("Survival", InstanceConfig::vanilla("1.20.4")),
+        Err(LauncherError::InstanceExists { .. })
+    ));
+    for name in ["", ".hidden", "a/b", "..", "con:"] {
+        assert!(
+            matches!(
+                launcher.create_instance(name, InstanceConfig::vanilla("1.20.4")),
+                Err(LauncherError::InvalidInstanceName { .. })
+            ),
+            "{name:?}"
+        );
+    }
+
+    fs::write(created.game_directory().join("mods/sodium.jar"), b"mod").unwrap();
+    launcher
+        .create_instance("Creative", InstanceConfig::vanilla("1.20.4"))
+        .unwrap();
+    fs::create_dir_all(dir.path().join("instances/not-an-instance")).unwrap();
+    let names: Vec<String> = launcher
+        .instances()
+        .unwrap()
+        .into_iter()
+        .map(|instance| instance.name)
+        .collect();
+    assert_eq!(names, ["Creative", "Survival"]);
+
+    let renamed = launcher.rename_instance("Survival", "Hardcore").unwrap();
+    assert_eq!(renamed.config, fabric_config());
+    assert!(!dir.path().join("instances/Survival").exists());
+
+    let copy = launcher
+        .clone_instance("Hardcore", "Hardcore Copy")
+        .unwrap();
+    assert_eq!(
+        fs::read(copy.game_directory().join("mods/sodium.jar")).unwrap(),
+        b"mod"
+    );
+    assert_eq!(launcher.instance("Hardcore Copy").unwrap(), copy);
+
+    launcher.delete_instance("Hardcore").unwrap();
+    assert!(matches!(
+        launcher.instance("Hardcore"),
+        Err(LauncherError::InstanceNotFound { .. })
+    ));
+    assert!(copy.game_directory().join("mods/sodium.jar").is_file());
+}
+
+#[test]
+fn builds_launch_options_and_install_request_from_settings() {
+    let dir = tempfile::tempdir().unwrap();
+    let instance = Launcher::new(dir.path())
+        .create_instance("Modded", fabric_config())
+        .unwrap();
+
+    let options = instance.launch_options(Account::offline("Alex"));
+    assert_eq!(options.account.username(), "Alex");
+    assert_eq!(options.game_directory, Some(instance.game_directory()));
+    assert_eq!(options.jvm_args, ["-Xmx4096M", "-XX:+UseG1GC"]);
+    assert_eq!(options.custom_resolution, Some((1280, 720)));
+    assert_eq!(options.java_executable, None);
+
+    let request = instance.config.install_request();
+    assert_eq!(request.minecraft_version, "1.20.4");
+    assert_eq!(
+        request.loader,
+        Some(LoaderSpec::Fabric {
+            version: LoaderVersion::Exact("0.15.0".to_string()),
+        })
+    );
+}
+
+#[test]
+fn stores_forge_versions_with_the_minecraft_prefix() {
+    let short = InstanceLoader::forge("1.20.1", "47.2.0");
+    assert_eq!(short, InstanceLoader::forge("1.20.1", "1.20.1-47.2.0"));
+    assert_eq!(short.version, "1.20.1-47.2.0");
+    assert_eq!(short.pack_version("1.20.1"), "47.2.0");
+    assert_eq!(
+        InstanceLoader::new(LoaderKind::Forge, "1.20.1", "47.2.0"),
+        short
+    );
+
+    let config = InstanceConfig::with_loader("1.20.1", Some(short));
+    assert_eq!(config.version_id, "1.20.1-forge-47.2.0");
+    let neoforge = InstanceLoader::new(LoaderKind::NeoForge, "1.20.4", "20.4.80");
+    assert_eq!(neoforge.pack_version("1.20.4"), "20.4.80");
+    assert_eq!(
+        InstanceConfig::with_loader("1.20.4", Some(neoforge)).version_id,
+        "neoforge-20.4.80"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn launches_instance_in_its_game_directory() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let java = dir.path().join("java");
+    fs::write(
+        &java,
+        "#!/bin/sh\npwd\nfor arg in \"$@\"; do echo \"$arg\"; done\n",
+    )
+    .unwrap();
+    fs::set_permissions(&java, fs::Permissions::from_mode(0o755)).unwrap();
+    let launcher = Launcher::new(dir.path().join("minecraft"));
+    let version_dir = launcher.minecraft_dir().join("versions/1.20.4");
+    fs::create_dir_all(&version_dir).unwrap();
+    fs::write(
+        version_dir.join("1.20.4.json"),
+        r#"{"id":"1.20.4","mainClass":"net.minecraft.client.main.Main","arguments":{"jvm":[],"game":["--gameDir","${game_directory}"]},"libraries":[]}"#,
+    )
+    .unwrap();
+    let instance = launcher
+        .create_instance(
+            "Vanilla",
+            InstanceConfig {
+                java_executable: Some(java),
+                min_memory_mb: Some(512),
+                ..InstanceConfig::vanilla("1.20.4")
+            },
+        )
+        .unwrap();
+
+    let mut game = launcher
+        .launch_instance("Vanilla", Account::offline("Steve"))
+        .unwrap();
+    let lines: Vec<String> = game.lines().map(|line| line.line).collect();
+
+    assert!(game.wait().unwrap().success());
+    let game_dir = fs::canonicalize(instance.game_directory()).unwrap();
+    assert_eq!(lines[0], game_dir.to_string_lossy());
+    assert_eq!(
+        lines[lines.len() - 4..],
+        [
+            "-Xms512M".to_string(),
+            "net.minecraft.client.main.Main".to_string(),
+            "--gameDir".to_string(),
+            instance.game_directory().to_string_lossy().into_owned(),
+        ]
+    );
+}
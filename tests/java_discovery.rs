@@ -0,0 +1,202 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use mc_launcher_core::{
+    compatibility::JavaRuntimeHint,
+    core::version::JavaVersion,
+    install::runtime::runtime_platform_key,
+    platform::{Arch, Platform},
+    runtime::java::{
+        parse_arch, parse_java_version, select_java_installation, JavaInstallation, JavaSearch,
+        JavaSource,
+    },
+};
+
+fn fake_home(home: &Path, version: &str, vendor: &str, arch: &str) -> PathBuf {
+    fs::create_dir_all(home.join("bin")).unwrap();
+    fs::write(home.join("bin/java"), "").unwrap();
+    fs::write(
+        home.join("release"),
+        format!("IMPLEMENTOR=\"{vendor}\"\nJAVA_VERSION=\"{version}\"\nOS_ARCH=\"{arch}\"\n"),
+    )
+    .unwrap();
+    home.join("bin/java")
+}
+
+fn installation(major: u32, arch: Arch, source: JavaSource) -> JavaInstallation {
+    JavaInstallation {
+        executable: PathBuf::from(format!("/jvm/{major}-{arch:?}/bin/java")),
+        home: PathBuf::from(format!("/jvm/{major}-{arch:?}")),
+        version: format!("{major}.0.1"),
+        major,
+        minor: 0,
+        vendor: None,
+        arch,
+        source,
+    }
+}
+
+#[test]
+fn parses_legacy_and_modern_version_strings() {
+    assert_eq!(parse_java_version("1.8.0_392"), Some((8, 0)));
+    assert_eq!(parse_java_version("11.0.21"), Some((11, 0)));
+    assert_eq!(parse_java_version("17"), Some((17, 0)));
+    assert_eq!(parse_java_version("22.1+9"), Some((22, 1)));
+    assert_eq!(parse_java_version("openjdk"), None);
+    assert_eq!(parse_arch("amd64"), Arch::X86_64);
+    assert_eq!(parse_arch("aarch64"), Arch::Aarch64);
+    assert_eq!(parse_arch("ppc64le"), Arch::Other);
+}
+
+#[test]
+fn discovers_installations_from_every_source_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let java_home = fake_home(
+        &dir.path().join("home-17"),
+        "17.0.8",
+        "Eclipse Adoptium",
+        "x86_64",
+    );
+    fake_home(
+        &dir.path().join("jvm/java-8"),
+        "1.8.0_392",
+        "Azul Systems, Inc.",
+        "aarch64",
+    );
+    fake_home(
+        &dir.path().join("jvm/java-21"),
+        "21.0.1",
+        "Oracle Corporation",
+        "amd64",
+    );
+    let mut search = JavaSearch {
+        java_home: Some(dir.path().join("home-17")),
+        path: vec![java_home.parent().unwrap().to_path_buf()],
+        system_dirs: vec![dir.path().join("jvm"), dir.path().join("missing")],
+        minecraft_dir: Some(dir.path().join("minecraft")),
+    };
+    if let Some(key) = runtime_platform_key(Platform::current()) {
+        fake_home(
+            &dir.path()
+                .join("minecraft/runtime/java-runtime-gamma")
+                .join(key)
+                .join("java-runtime-gamma"),
+            "17.0.3",
+            "Microsoft",
+            "x86_64",
+        );
+    } else {
+        search.minecraft_dir = None;
+    }
+
+    let found = search.discover();
+
+    let mut summary = found
+        .iter()
+        .map(|java| {
+            (
+                java.major,
+                java.vendor.clone().unwrap(),
+                java.source.clone(),
+            )
+        })
+        .collect::<Vec<_>>();
+    if search.minecraft_dir.is_some() {
+        assert_eq!(
+            summary.remove(0),
+            (
+                17,
+                "Microsoft".to_string(),
+                JavaSource::MojangRuntime {
+                    component: "java-runtime-gamma".to_string()
+                }
+            )
+        );
+    }
+    assert_eq!(
+        summary,
+        vec![
+            (17, "Eclipse Adoptium".to_string(), JavaSource::JavaHome),
+            (
+                21,
+                "Oracle Corporation".to_string(),
+                JavaSource::SystemDirectory
+            ),
+            (
+                8,
+                "Azul Systems, Inc.".to_string(),
+                JavaSource::SystemDirectory
+            ),
+        ]
+    );
+    let java_8 = found.iter().find(|java| java.major == 8).unwrap();
+    assert_eq!(java_8.arch, Arch::Aarch64);
+    assert_eq!(java_8.version, "1.8.0_392");
+    assert_eq!(java_8.home, dir.path().join("jvm/java-8"));
+}
+
+#[test]
+fn selects_exact_major_then_closest_newer() {
+    let current = Platform::current().arch;
+    let installations = vec![
+        installation(8, current, JavaSource::SystemDirectory),
+        installation(21, current, JavaSource::SystemDirectory),
+        installation(17, current, JavaSource::SystemDirectory),
+        installation(
+            17,
+            current,
+            JavaSource::MojangRuntime {
+                component: "java-runtime-gamma".to_string(),
+            },
+        ),
+    ];
+    let requires = |major| JavaVersion {
+        component: "java-runtime".to_string(),
+        major_version: major,
+    };
+
+    let picked = select_java_installation(&installations, Some(&requires(17)), None).unwrap();
+    assert_eq!(picked, &installations[3]);
+
+    let picked = select_java_installation(&installations, Some(&requires(18)), None).unwrap();
+    assert_eq!(picked.major, 21);
+
+    let picked = select_java_installation(&installations, Some(&requires(8)), None).unwrap();
+    assert_eq!(picked.major, 8);
+
+    assert_eq!(
+        select_java_installation(&installations[1..], Some(&requires(8)), None),
+        None
+    );
+    assert_eq!(
+        select_java_installation(&installations, None, None)
+            .unwrap()
+            .major,
+        21
+    );
+}
+
+#[test]
+fn runtime_hint_overrides_version_and_requires_architecture() {
+    let installations = vec![
+        installation(8, Arch::X86_64, JavaSource::SystemDirectory),
+        installation(17, Arch::Aarch64, JavaSource::SystemDirectory),
+        installation(8, Arch::Aarch64, JavaSource::SystemDirectory),
+    ];
+    let hint = JavaRuntimeHint {
+        major_version: 8,
+        arch: Arch::Aarch64,
+        distribution_hint: "Azul Zulu Java 8 arm64",
+        reason: "test",
+    };
+    let requirement = JavaVersion {
+        component: "jre-legacy".to_string(),
+        major_version: 17,
+    };
+
+    let picked = select_java_installation(&installations, Some(&requirement), Some(&hint)).unwrap();
+
+    assert_eq!(picked, &installations[2]);
+}
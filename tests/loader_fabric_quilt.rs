@@ -1,8 +1,13 @@
 use mc_launcher_core::{
     core::version::VersionJson,
     loader::{
-        fabric::{latest_stable_loader as latest_fabric_stable, FabricLoaderVersion},
-        quilt::{latest_loader as latest_quilt_loader, QuiltLoaderVersion},
+        fabric::{
+            fabric_installed_version_id, latest_stable_loader as latest_fabric_stable,
+            FabricLoaderVersion,
+        },
+        quilt::{
+            latest_loader as latest_quilt_loader, quilt_installed_version_id, QuiltLoaderVersion,
+        },
     },
 };
 
@@ -28,6 +33,10 @@ fn fabric_profile_parses_as_version_json() {
     let profile: VersionJson =
         serde_json::from_str(include_str!("fixtures/fabric_profile_1_20_4.json")).unwrap();
     assert_eq!(profile.id.as_deref(), Some("fabric-loader-0.15.7-1.20.4"));
+    assert_eq!(
+        profile.id.as_deref(),
+        Some(fabric_installed_version_id("1.20.4", "0.15.7").as_str())
+    );
     assert_eq!(profile.inherits_from.as_deref(), Some("1.20.4"));
 }
 
@@ -36,5 +45,9 @@ fn quilt_profile_parses_as_version_json() {
     let profile: VersionJson =
         serde_json::from_str(include_str!("fixtures/quilt_profile_1_20_4.json")).unwrap();
     assert_eq!(profile.id.as_deref(), Some("quilt-loader-0.23.1-1.20.4"));
+    assert_eq!(
+        profile.id.as_deref(),
+        Some(quilt_installed_version_id("1.20.4", "0.23.1").as_str())
+    );
     assert_eq!(profile.inherits_from.as_deref(), Some("1.20.4"));
 }
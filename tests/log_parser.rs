@@ -0,0 +1,99 @@
+use mc_launcher_core::process::log::{parse_log_output, LogEvent, LogLevel, LogParser};
+
+const XML_OUTPUT: &str = r#"<log4j:Event logger="net.minecraft.client.Minecraft" timestamp="1700000000123" level="INFO" thread="Render thread">
+  <log4j:Message><![CDATA[Setting user: Steve]]></log4j:Message>
+</log4j:Event>
+<log4j:Event logger="net.minecraft.server.MinecraftServer" timestamp="1700000001000" level="ERROR" thread="Server &quot;main&quot;">
+  <log4j:Message><![CDATA[Encountered an unexpected exception]]></log4j:Message>
+  <log4j:Throwable><![CDATA[java.lang.IllegalStateException: boom
+	at net.minecraft.server.MinecraftServer.run(MinecraftServer.java:1)
+]]></log4j:Throwable>
+</log4j:Event>
+"#;
+
+#[test]
+fn parses_multiline_xml_events() {
+    let events = parse_log_output(XML_OUTPUT);
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].level, LogLevel::Info);
+    assert_eq!(
+        events[0].logger.as_deref(),
+        Some("net.minecraft.client.Minecraft")
+    );
+    assert_eq!(events[0].thread.as_deref(), Some("Render thread"));
+    assert_eq!(events[0].message, "Setting user: Steve");
+    assert_eq!(
+        events[0].timestamp.unwrap().timestamp_millis(),
+        1_700_000_000_123
+    );
+    assert_eq!(events[0].throwable, None);
+
+    assert_eq!(events[1].level, LogLevel::Error);
+    assert_eq!(events[1].thread.as_deref(), Some("Server \"main\""));
+    let throwable = events[1].throwable.as_deref().unwrap();
+    assert!(throwable.starts_with("java.lang.IllegalStateException: boom\n\tat "));
+}
+
+#[test]
+fn parses_plain_text_lines() {
+    let vanilla = LogEvent::from_plain_line("[12:34:56] [Render thread/WARN]: Missing sound");
+    assert_eq!(vanilla.level, LogLevel::Warn);
+    assert_eq!(vanilla.thread.as_deref(), Some("Render thread"));
+    assert_eq!(vanilla.logger, None);
+    assert_eq!(vanilla.message, "Missing sound");
+    assert_eq!(vanilla.timestamp, None);
+
+    let forge = LogEvent::from_plain_line(
+        "[12:34:56] [main/INFO] [cp.mo.mo.Launcher/MODLAUNCHER]: ModLauncher running",
+    );
+    assert_eq!(forge.level, LogLevel::Info);
+    assert_eq!(forge.thread.as_deref(), Some("main"));
+    assert_eq!(
+        forge.logger.as_deref(),
+        Some("cp.mo.mo.Launcher/MODLAUNCHER")
+    );
+    assert_eq!(forge.message, "ModLauncher running");
+
+    let raw = LogEvent::from_plain_line("Exception in thread \"main\" java.lang.Error");
+    assert_eq!(raw.level, LogLevel::Info);
+    assert_eq!(raw.message, "Exception in thread \"main\" java.lang.Error");
+}
+
+#[test]
+fn streams_mixed_output_line_by_line() {
+    let mut parser = LogParser::new();
+    let mut events = Vec::new();
+    for line in [
+        "Picked up _JAVA_OPTIONS: -Xmx2G",
+        "",
+        r#"<log4j:Event logger="a" timestamp="1" level="DEBUG" thread="t"><log4j:Message>x &lt; y</log4j:Message></log4j:Event>"#,
+        r#"<log4j:Event logger="b" timestamp="2" level="FATAL" thread="t">"#,
+    ] {
+        events.extend(parser.push_line(line));
+    }
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].message, "Picked up _JAVA_OPTIONS: -Xmx2G");
+    assert_eq!(events[1].level, LogLevel::Debug);
+    assert_eq!(events[1].message, "x < y");
+
+    let events = parser.push_line("<log4j:Message><![CDATA[crash]]></log4j:Message>");
+    assert!(events.is_empty());
+    let events = parser.push_line("</log4j:Event>[12:00:00] [main/ERROR]: after");
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].level, LogLevel::Fatal);
+    assert_eq!(events[0].message, "crash");
+    assert_eq!(events[1].level, LogLevel::Error);
+    assert_eq!(parser.finish(), None);
+
+    parser.push_line(r#"<log4j:Event logger="c" level="INFO">"#);
+    assert!(parser.finish().unwrap().message.starts_with("<log4j:Event"));
+}
+
+#[test]
+fn levels_order_by_severity() {
+    assert!(LogLevel::Warn > LogLevel::Info);
+    assert!(LogLevel::Fatal > LogLevel::Error);
+    assert_eq!("warning".parse::<LogLevel>(), Ok(LogLevel::Warn));
+    assert_eq!(LogLevel::Error.to_string(), "ERROR");
+}
@@ -0,0 +1,161 @@
+mod support;
+
+use std::fs;
+
+use mc_launcher_core::{
+    net::{
+        download::{execute_plan_with_options, DownloadOptions, DownloadPlan, DownloadTask},
+        mirror::{Endpoints, Mirror, MirrorConfig},
+    },
+    prelude::*,
+};
+use support::{sha1_of, stand_in_mirror, StandInServer};
+
+#[test]
+fn official_config_returns_url_unchanged() {
+    let url = "https://libraries.minecraft.net/com/example/demo/1.0/demo-1.0.jar";
+    assert_eq!(
+        MirrorConfig::official().candidates(url),
+        vec![url.to_string()]
+    );
+}
+
+#[test]
+fn mirrors_are_tried_in_order_before_official() {
+    let first = Mirror::new(
+        "first",
+        Endpoints {
+            libraries: "https://first.example/maven".to_string(),
+            ..Endpoints::official()
+        },
+    );
+    let config = MirrorConfig::with_fallback(vec![first, Mirror::bmclapi()]);
+
+    assert_eq!(
+        config.candidates("https://libraries.minecraft.net/a/b.jar"),
+        vec![
+            "https://first.example/maven/a/b.jar".to_string(),
+            "https://bmclapi2.bangbang93.com/maven/a/b.jar".to_string(),
+            "https://libraries.minecraft.net/a/b.jar".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn rewrites_require_a_path_boundary() {
+    let mirror = Mirror::bmclapi();
+    assert_eq!(
+        mirror.rewrite("https://piston-data.mojang.com.evil.example/client.jar"),
+        None
+    );
+}
+
+#[test]
+fn disabling_official_fallback_keeps_only_mirror_urls() {
+    let config = MirrorConfig {
+        mirrors: vec![Mirror::bmclapi()],
+        fallback_to_official: false,
+    };
+
+    assert_eq!(
+        config.candidates("https://meta.fabricmc.net/v2/versions/loader"),
+        vec!["https://bmclapi2.bangbang93.com/fabric-meta/v2/versions/loader".to_string()]
+    );
+}
+
+#[test]
+fn download_falls_back_to_next_mirror() {
+    let broken = StandInServer::start();
+    let healthy = StandInServer::start();
+    healthy.serve("/maven/com/example/demo.jar", "demo");
+    let dir = tempfile::tempdir().unwrap();
+    let plan = DownloadPlan {
+        tasks: vec![DownloadTask {
+            url: "https://libraries.minecraft.net/com/example/demo.jar".to_string(),
+            destination: dir.path().join("demo.jar"),
+            checksum: None,
+            size: None,
+            label: "demo".to_string(),
+        }],
+    };
+    let options = DownloadOptions {
+        mirrors: MirrorConfig {
+            mirrors: vec![
+                stand_in_mirror("broken", &broken),
+                stand_in_mirror("healthy", &healthy),
+            ],
+            fallback_to_official: false,
+        },
+        ..Default::default()
+    };
+
+    execute_plan_with_options(&plan, &options, &mut |_event| {}).unwrap();
+
+    assert_eq!(broken.hits("/maven/com/example/demo.jar"), 1);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("demo.jar")).unwrap(),
+        "demo"
+    );
+}
+
+#[test]
+fn launcher_installs_vanilla_from_local_stand_in() {
+    let server = StandInServer::start();
+    let client = b"client jar".to_vec();
+    let library = b"library jar".to_vec();
+    let object = b"sound".to_vec();
+    let object_hash = sha1_of(&object);
+    let asset_index = format!(
+        r#"{{"objects":{{"minecraft/sounds/a.ogg":{{"hash":"{object_hash}","size":5}}}}}}"#
+    );
+    let version = format!(
+        r#"{{
+            "id":"1.20.4",
+            "type":"release",
+            "mainClass":"net.minecraft.client.main.Main",
+            "assets":"12",
+            "assetIndex":{{"id":"12","sha1":"{index_sha1}","size":{index_size},"totalSize":5,"url":"https://piston-data.mojang.com/v1/objects/index/12.json"}},
+            "downloads":{{"client":{{"sha1":"{client_sha1}","size":10,"url":"https://piston-data.mojang.com/v1/objects/client/client.jar"}}}},
+            "libraries":[{{"name":"com.example:demo:1.0","downloads":{{"artifact":{{"path":"com/example/demo/1.0/demo-1.0.jar","sha1":"{library_sha1}","size":11,"url":"https://libraries.minecraft.net/com/example/demo/1.0/demo-1.0.jar"}}}}}}]
+        }}"#,
+        index_sha1 = sha1_of(asset_index.as_bytes()),
+        index_size = asset_index.len(),
+        client_sha1 = sha1_of(&client),
+        library_sha1 = sha1_of(&library),
+    );
+    server.serve(
+        "/mc/game/version_manifest_v2.json",
+        r#"{"versions":[{"id":"1.20.4","url":"https://piston-data.mojang.com/v1/packages/1.20.4.json"}]}"#,
+    );
+    server.serve("/v1/packages/1.20.4.json", version);
+    server.serve("/v1/objects/client/client.jar", client.clone());
+    server.serve("/v1/objects/index/12.json", asset_index);
+    server.serve("/maven/com/example/demo/1.0/demo-1.0.jar", library);
+    server.serve(
+        &format!("/assets/{}/{object_hash}", &object_hash[..2]),
+        object,
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let launcher = Launcher::new(dir.path()).with_mirrors(MirrorConfig {
+        mirrors: vec![stand_in_mirror("local", &server)],
+        fallback_to_official: false,
+    });
+    let install = launcher.install(InstallRequest::vanilla("1.20.4")).unwrap();
+
+    assert_eq!(install.version_id, "1.20.4");
+    assert_eq!(
+        fs::read(dir.path().join("versions/1.20.4/1.20.4.jar")).unwrap(),
+        client
+    );
+    assert!(dir
+        .path()
+        .join("libraries/com/example/demo/1.0/demo-1.0.jar")
+        .is_file());
+    assert!(dir
+        .path()
+        .join("assets/objects")
+        .join(&object_hash[..2])
+        .join(&object_hash)
+        .is_file());
+}
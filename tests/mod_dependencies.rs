@@ -0,0 +1,358 @@
+mod support;
+
+use std::{fs, path::Path};
+
+use mc_launcher_core::{
+    core::version::VersionJson,
+    instance::InstanceLoader,
+    mods::{
+        dependencies::{check_mods, DiagnosticSeverity, ModEnvironment, ModProblem},
+        metadata::scan_mods_directory,
+        versions::{fabric_predicate_matches, maven_range_matches},
+    },
+    prelude::*,
+};
+use serde_json::json;
+use support::{dependency, write_profile, write_zip};
+
+fn fabric_jar(path: &Path, metadata: serde_json::Value) {
+    write_zip(
+        path,
+        &[("fabric.mod.json", metadata.to_string().as_bytes())],
+    );
+}
+
+fn fabric_environment() -> ModEnvironment {
+    ModEnvironment {
+        minecraft_version: "1.20.1".to_string(),
+        loader: Some(LoaderKind::Fabric),
+        loader_version: Some("0.15.11".to_string()),
+    }
+}
+
+#[test]
+fn matches_fabric_predicates_and_maven_ranges() {
+    for (version, predicate, expected) in [
+        ("0.15.11", ">=0.15.0", Some(true)),
+        ("0.14.22", ">=0.15.0", Some(false)),
+        ("1.20.1", "~1.20", Some(true)),
+        ("1.21", "~1.20", Some(false)),
+        ("1.20.4", ">=1.20 <1.21", Some(true)),
+        ("1.20.1", "1.20.x", Some(true)),
+        ("1.19.4", "1.20.x", Some(false)),
+        ("2.1.0", "^1.4", Some(false)),
+        ("1.0.0-beta.2", "<1.0.0", Some(true)),
+        ("0.5.3+mc1.20.1", "0.5.3", Some(true)),
+        ("23w40a", "23w40a", Some(true)),
+        ("23w40a", ">=1.20", None),
+        ("anything", "*", Some(true)),
+    ] {
+        assert_eq!(
+            fabric_predicate_matches(version, predicate),
+            expected,
+            "{version} {predicate}"
+        );
+    }
+
+    for (version, range, expected) in [
+        ("47.2.0", "[47,)", Some(true)),
+        ("46.0.14", "[47,)", Some(false)),
+        ("1.20.1", "[1.20.1,1.21)", Some(true)),
+        ("1.21", "[1.20.1,1.21)", Some(false)),
+        ("1.21-pre1", "[1.20.1,1.21)", Some(false)),
+        ("1.21-rc1", "[1.20.1,1.21)", Some(true)),
+        ("2.0", "(,1.0],[1.5,)", Some(true)),
+        ("1.2", "(,1.0],[1.5,)", Some(false)),
+        ("1.0.0", "[1.0]", Some(true)),
+        ("20.4.80-beta", "[20.4,)", Some(true)),
+        ("3.0", "1.0", Some(true)),
+        ("1.0", "[1.0", None),
+    ] {
+        assert_eq!(
+            maven_range_matches(version, range),
+            expected,
+            "{version} {range}"
+        );
+    }
+}
+
+#[test]
+fn reports_dependency_loader_and_duplicate_problems() {
+    let dir = tempfile::tempdir().unwrap();
+    fabric_jar(
+        &dir.path().join("a-api.jar"),
+        json!({
+            "schemaVersion": 1, "id": "fabric-api", "version": "0.92.0",
+            "provides": ["fabric"],
+            "depends": {"fabricloader": ">=0.15.0", "minecraft": "~1.20.1"},
+        }),
+    );
+    fabric_jar(
+        &dir.path().join("b-sodium.jar"),
+        json!({
+            "schemaVersion": 1, "id": "sodium", "version": "0.5.3",
+            "depends": {"fabric": "*", "minecraft": "1.20.x", "java": ">=17"},
+            "breaks": {"optifabric": "*"},
+            "conflicts": {"iris": "<1.6"},
+        }),
+    );
+    fabric_jar(
+        &dir.path().join("c-iris.jar"),
+        json!({
+            "schemaVersion": 1, "id": "iris", "version": "1.5.2",
+            "depends": {"sodium": ">=0.6", "minecraft": ">=1.21", "fabricloader": ">=0.16"},
+        }),
+    );
+    fabric_jar(
+        &dir.path().join("d-optifabric.jar"),
+        json!({"schemaVersion": 1, "id": "optifabric", "version": "1.13.0", "depends": {"missing": "*"}}),
+    );
+    fabric_jar(
+        &dir.path().join("e-sodium-copy.jar"),
+        json!({"schemaVersion": 1, "id": "sodium", "version": "0.5.3"}),
+    );
+    write_zip(
+        &dir.path().join("f-forge.jar"),
+        &[(
+            "META-INF/mods.toml",
+            b"modLoader=\"javafml\"\nloaderVersion=\"[47,)\"\n[[mods]]\nmodId=\"jei\"\nversion=\"15.2.0\"\n",
+        )],
+    );
+    fabric_jar(
+        &dir.path().join("g-disabled.jar.disabled"),
+        json!({"schemaVersion": 1, "id": "disabled", "version": "1.0", "depends": {"missing": "*"}}),
+    );
+    write_zip(&dir.path().join("h-library.jar"), &[("a/B.class", b"")]);
+    fs::write(dir.path().join("i-broken.jar"), b"not a zip").unwrap();
+
+    let files = scan_mods_directory(dir.path()).unwrap();
+    let diagnostics = check_mods(&files, &fabric_environment());
+    let summary = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            (
+                diagnostic.severity,
+                diagnostic.mod_id.as_deref(),
+                diagnostic.file.file_name().unwrap().to_str().unwrap(),
+                match &diagnostic.problem {
+                    ModProblem::Unreadable { .. } => ModProblem::Unreadable {
+                        error: "zip error".to_string(),
+                    },
+                    problem => problem.clone(),
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+    let path = |name: &str| dir.path().join(name);
+    assert_eq!(
+        summary,
+        [
+            (
+                DiagnosticSeverity::Error,
+                Some("jei"),
+                "f-forge.jar",
+                ModProblem::WrongLoader {
+                    expected: Some(LoaderKind::Fabric),
+                    found: vec![LoaderKind::Forge],
+                },
+            ),
+            (
+                DiagnosticSeverity::Warning,
+                None,
+                "i-broken.jar",
+                ModProblem::Unreadable {
+                    error: "zip error".to_string(),
+                },
+            ),
+            (
+                DiagnosticSeverity::Error,
+                Some("sodium"),
+                "e-sodium-copy.jar",
+                ModProblem::Duplicate {
+                    other: path("b-sodium.jar"),
+                },
+            ),
+            (
+                DiagnosticSeverity::Error,
+                Some("sodium"),
+                "b-sodium.jar",
+                ModProblem::Breaks {
+                    dependency: dependency("optifabric", &[]),
+                    found: Some("1.13.0".to_string()),
+                },
+            ),
+            (
+                DiagnosticSeverity::Warning,
+                Some("sodium"),
+                "b-sodium.jar",
+                ModProblem::Conflicts {
+                    dependency: dependency("iris", &["<1.6"]),
+                    found: Some("1.5.2".to_string()),
+                },
+            ),
+            (
+                DiagnosticSeverity::Error,
+                Some("iris"),
+                "c-iris.jar",
+                ModProblem::DependencyVersionMismatch {
+                    dependency: dependency("fabricloader", &[">=0.16"]),
+                    found: vec!["0.15.11".to_string()],
+                },
+            ),
+            (
+                DiagnosticSeverity::Error,
+                Some("iris"),
+                "c-iris.jar",
+                ModProblem::DependencyVersionMismatch {
+                    dependency: dependency("minecraft", &[">=1.21"]),
+                    found: vec!["1.20.1".to_string()],
+                },
+            ),
+            (
+                DiagnosticSeverity::Error,
+                Some("iris"),
+                "c-iris.jar",
+                ModProblem::DependencyVersionMismatch {
+                    dependency: dependency("sodium", &[">=0.6"]),
+                    found: vec!["0.5.3".to_string(), "0.5.3".to_string()],
+                },
+            ),
+            (
+                DiagnosticSeverity::Error,
+                Some("optifabric"),
+                "d-optifabric.jar",
+                ModProblem::MissingDependency {
+                    dependency: dependency("missing", &[]),
+                },
+            ),
+        ]
+    );
+    assert!(diagnostics[0].is_error());
+    assert_eq!(
+        diagnostics[0].to_string(),
+        format!(
+            "error: jei ({}) is built for Forge, not Fabric",
+            path("f-forge.jar").display()
+        )
+    );
+    assert_eq!(
+        diagnostics[5].to_string(),
+        format!(
+            "error: iris ({}) requires fabricloader >=0.16, but 0.15.11 is installed",
+            path("c-iris.jar").display()
+        )
+    );
+
+    let vanilla = ModEnvironment {
+        loader: None,
+        loader_version: None,
+        ..fabric_environment()
+    };
+    let diagnostics = check_mods(&files, &vanilla);
+    assert_eq!(
+        diagnostics
+            .iter()
+            .filter(|diagnostic| matches!(diagnostic.problem, ModProblem::WrongLoader { .. }))
+            .count(),
+        6
+    );
+}
+
+#[test]
+fn reads_environment_from_profiles_and_checks_instance_mods() {
+    let fabric: VersionJson = serde_json::from_value(json!({
+        "id": "fabric-loader-0.15.11-1.20.1",
+        "inheritsFrom": "1.20.1",
+        "libraries": [{"name": "net.fabricmc:fabric-loader:0.15.11"}],
+    }))
+    .unwrap();
+    assert_eq!(
+        ModEnvironment::from_version(&fabric).unwrap(),
+        fabric_environment()
+    );
+
+    for (profile, loader, loader_version) in [
+        (
+            json!({
+                "id": "1.20.1-forge-47.2.0",
+                "inheritsFrom": "1.20.1",
+                "arguments": {"game": ["--fml.forgeVersion", "47.2.0", "--fml.mcVersion", "1.20.1"]},
+                "libraries": [{"name": "net.minecraftforge:forge:1.20.1-47.2.0:universal"}],
+            }),
+            Some(LoaderKind::Forge),
+            Some("47.2.0"),
+        ),
+        (
+            json!({
+                "id": "neoforge-20.4.80-beta",
+                "inheritsFrom": "1.20.4",
+                "arguments": {"game": ["--fml.neoForgeVersion", "20.4.80-beta"]},
+                "libraries": [{"name": "net.neoforged.fancymodloader:loader:2.0.17"}],
+            }),
+            Some(LoaderKind::NeoForge),
+            Some("20.4.80-beta"),
+        ),
+        (
+            json!({
+                "id": "1.7.10-Forge10.13.4.1614-1.7.10",
+                "inheritsFrom": "1.7.10",
+                "libraries": [{"name": "net.minecraftforge:forge:1.7.10-10.13.4.1614-1.7.10"}],
+            }),
+            Some(LoaderKind::Forge),
+            Some("10.13.4.1614"),
+        ),
+        (json!({"id": "1.20.1"}), None, None),
+    ] {
+        let version: VersionJson = serde_json::from_value(profile).unwrap();
+        let environment = ModEnvironment::from_version(&version).unwrap();
+        assert_eq!(environment.loader, loader);
+        assert_eq!(environment.loader_version.as_deref(), loader_version);
+    }
+    assert!(matches!(
+        ModEnvironment::from_version(&VersionJson::default()),
+        Err(LauncherError::MissingField { .. })
+    ));
+
+    let dir = tempfile::tempdir().unwrap();
+    let launcher = Launcher::new(dir.path());
+    for (id, profile) in [
+        ("1.20.1", json!({"id": "1.20.1"})),
+        (
+            "fabric-loader-0.15.11-1.20.1",
+            serde_json::to_value(&fabric).unwrap(),
+        ),
+    ] {
+        write_profile(dir.path(), id, profile);
+    }
+    let instance = launcher
+        .create_instance(
+            "Modded",
+            InstanceConfig::with_loader(
+                "1.20.1",
+                Some(InstanceLoader {
+                    kind: LoaderKind::Fabric,
+                    version: "0.15.11".to_string(),
+                }),
+            ),
+        )
+        .unwrap();
+    let mods = instance.game_directory().join("mods");
+    fs::create_dir_all(&mods).unwrap();
+    fabric_jar(
+        &mods.join("sodium.jar"),
+        json!({"schemaVersion": 1, "id": "sodium", "version": "0.5.3", "depends": {"fabricloader": ">=0.15", "minecraft": "1.20.1"}}),
+    );
+    assert!(launcher.check_instance_mods("Modded").unwrap().is_empty());
+
+    fabric_jar(
+        &mods.join("iris.jar"),
+        json!({"schemaVersion": 1, "id": "iris", "version": "1.6.4", "depends": {"sodium": ">=0.5.4"}}),
+    );
+    let diagnostics = launcher.check_instance_mods("Modded").unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].mod_id.as_deref(), Some("iris"));
+    assert!(matches!(
+        diagnostics[0].problem,
+        ModProblem::DependencyVersionMismatch { .. }
+    ));
+}
@@ -0,0 +1,295 @@
+mod support;
+
+use std::fs;
+
+use mc_launcher_core::{mods::metadata::read_mod_metadata, prelude::*};
+use serde_json::json;
+use support::{dependency, write_zip, zip_bytes};
+
+#[test]
+fn reads_fabric_and_quilt_metadata_with_nested_jars() {
+    let dir = tempfile::tempdir().unwrap();
+    let nested = zip_bytes(&[(
+        "fabric.mod.json",
+        json!({"schemaVersion": 1, "id": "fabric-api-base", "version": "0.4.31"})
+            .to_string()
+            .as_bytes(),
+    )]);
+    let library = zip_bytes(&[("com/example/Library.class", b"")]);
+    let jar = dir.path().join("fabric-api.jar");
+    write_zip(
+        &jar,
+        &[
+            (
+                "fabric.mod.json",
+                json!({
+                    "schemaVersion": 1,
+                    "id": "fabric-api",
+                    "version": "0.92.0",
+                    "name": "Fabric API",
+                    "description": "Core API",
+                    "authors": ["FabricMC", {"name": "modmuss50", "contact": {}}],
+                    "icon": {"16": "assets/icon16.png", "128": "assets/icon.png"},
+                    "depends": {"fabricloader": ">=0.15.0", "minecraft": ["1.20", "1.20.1"], "java": "*"},
+                    "breaks": {"optifabric": "<1.13.0"},
+                    "conflicts": {"old-api": "*"},
+                    "provides": ["fabric"],
+                    "jars": [
+                        {"file": "META-INF/jars/fabric-api-base.jar"},
+                        {"file": "META-INF/jars/library.jar"},
+                    ],
+                })
+                .to_string()
+                .as_bytes(),
+            ),
+            ("assets/icon.png", b"large icon"),
+            ("assets/icon16.png", b"small icon"),
+            ("META-INF/jars/fabric-api-base.jar", &nested),
+            ("META-INF/jars/library.jar", &library),
+        ],
+    );
+
+    let mods = read_mod_metadata(&jar).unwrap();
+    assert_eq!(mods.len(), 1);
+    let fabric = &mods[0];
+    assert_eq!(fabric.id, "fabric-api");
+    assert_eq!(fabric.name, "Fabric API");
+    assert_eq!(fabric.loader, LoaderKind::Fabric);
+    assert_eq!(fabric.authors, ["FabricMC", "modmuss50"]);
+    assert_eq!(fabric.icon.as_deref(), Some(&b"large icon"[..]));
+    assert_eq!(
+        fabric.depends,
+        [
+            dependency("fabricloader", &[">=0.15.0"]),
+            dependency("java", &[]),
+            dependency("minecraft", &["1.20", "1.20.1"]),
+        ]
+    );
+    assert_eq!(fabric.breaks, [dependency("optifabric", &["<1.13.0"])]);
+    assert_eq!(fabric.conflicts, [dependency("old-api", &[])]);
+    assert_eq!(fabric.provides, ["fabric"]);
+    assert_eq!(fabric.nested.len(), 1);
+    assert_eq!(fabric.nested[0].id, "fabric-api-base");
+    assert_eq!(fabric.nested[0].name, "fabric-api-base");
+
+    let quilt = dir.path().join("quilted.jar");
+    write_zip(
+        &quilt,
+        &[(
+            "quilt.mod.json",
+            json!({
+                "schema_version": 1,
+                "quilt_loader": {
+                    "group": "org.example",
+                    "id": "quilted",
+                    "version": "1.0.0",
+                    "metadata": {
+                        "name": "Quilted",
+                        "contributors": {"Alice": "Owner", "Bob": ["Developer"]},
+                        "icon": "icon.png",
+                    },
+                    "depends": [
+                        "quilt_loader",
+                        {"id": "org.quiltmc:qsl", "versions": ">=6.0.0"},
+                        {"id": "modmenu", "optional": true},
+                        [{"id": "a"}, {"id": "b"}],
+                    ],
+                    "breaks": [{"id": "sodium", "versions": ["<0.5.0"]}],
+                },
+            })
+            .to_string()
+            .as_bytes(),
+        )],
+    );
+    let quilt = read_mod_metadata(&quilt).unwrap().remove(0);
+    assert_eq!(quilt.loader, LoaderKind::Quilt);
+    assert_eq!(quilt.authors, ["Alice", "Bob"]);
+    assert_eq!(quilt.icon, None);
+    assert_eq!(
+        quilt.depends,
+        [
+            dependency("quilt_loader", &[]),
+            dependency("qsl", &[">=6.0.0"])
+        ]
+    );
+    assert_eq!(quilt.breaks, [dependency("sodium", &["<0.5.0"])]);
+}
+
+#[test]
+fn reads_forge_neoforge_and_legacy_metadata() {
+    let dir = tempfile::tempdir().unwrap();
+    let embedded = zip_bytes(&[(
+        "META-INF/mods.toml",
+        br#"
+modLoader = "javafml"
+loaderVersion = "[47,)"
+[[mods]]
+modId = "embedded"
+version = "2.0"
+"#,
+    )]);
+    let forge = dir.path().join("forge.jar");
+    write_zip(
+        &forge,
+        &[
+            ("META-INF/MANIFEST.MF", b"Manifest-Version: 1.0\r\nImplementation-Version: 15.2.0\r\n"),
+            (
+                "META-INF/mods.toml",
+                br#"
+modLoader = "javafml"
+loaderVersion = "[47,)"
+license = "MIT"
+logoFile = "logo.png"
+authors = "mezz, someone"
+
+[[mods]]
+modId = "jei"
+version = "${file.jarVersion}"
+displayName = "Just Enough Items"
+description = '''
+Item list
+'''
+
+[[mods]]
+modId = "jei_addon"
+version = "1.0"
+authors = ["helper"]
+
+[[dependencies.jei]]
+modId = "forge"
+mandatory = true
+versionRange = "[47,)"
+ordering = "NONE"
+side = "BOTH"
+
+[[dependencies.jei]]
+modId = "optional_mod"
+mandatory = false
+versionRange = "*"
+
+[[dependencies.jei_addon]]
+modId = "jei"
+mandatory = true
+versionRange = ""
+"#,
+            ),
+            ("logo.png", b"logo"),
+            (
+                "META-INF/jarjar/metadata.json",
+                json!({"jars": [{"identifier": {}, "version": {}, "path": "META-INF/jarjar/embedded.jar"}]})
+                    .to_string()
+                    .as_bytes(),
+            ),
+            ("META-INF/jarjar/embedded.jar", &embedded),
+        ],
+    );
+
+    let mods = read_mod_metadata(&forge).unwrap();
+    assert_eq!(mods.len(), 2);
+    assert_eq!(mods[0].id, "jei");
+    assert_eq!(mods[0].version, "15.2.0");
+    assert_eq!(mods[0].name, "Just Enough Items");
+    assert_eq!(mods[0].description, "Item list");
+    assert_eq!(mods[0].loader, LoaderKind::Forge);
+    assert_eq!(mods[0].authors, ["mezz", "someone"]);
+    assert_eq!(mods[0].icon.as_deref(), Some(&b"logo"[..]));
+    assert_eq!(mods[0].depends, [dependency("forge", &["[47,)"])]);
+    assert_eq!(mods[0].nested[0].id, "embedded");
+    assert_eq!(mods[1].authors, ["helper"]);
+    assert_eq!(mods[1].depends, [dependency("jei", &[])]);
+    assert!(mods[1].nested.is_empty());
+
+    let neoforge = dir.path().join("neoforge.jar");
+    write_zip(
+        &neoforge,
+        &[(
+            "META-INF/neoforge.mods.toml",
+            br#"
+modLoader = "javafml"
+loaderVersion = "[1,)"
+[[mods]]
+modId = "neomod"
+version = "3.1"
+[[dependencies.neomod]]
+modId = "neoforge"
+type = "required"
+versionRange = "[20.4,)"
+[[dependencies.neomod]]
+modId = "optifine"
+type = "incompatible"
+[[dependencies.neomod]]
+modId = "jei"
+type = "optional"
+"#,
+        )],
+    );
+    let neomod = read_mod_metadata(&neoforge).unwrap().remove(0);
+    assert_eq!(neomod.loader, LoaderKind::NeoForge);
+    assert_eq!(neomod.depends, [dependency("neoforge", &["[20.4,)"])]);
+    assert_eq!(neomod.breaks, [dependency("optifine", &[])]);
+
+    for (name, info) in [
+        (
+            "legacy.jar",
+            json!([{"modid": "legacy", "name": "Legacy", "version": "1.7.10-1.0", "authorList": ["Old"], "requiredMods": ["Forge@[10.13,)", "CodeChickenCore"]}]),
+        ),
+        (
+            "legacy-v2.jar",
+            json!({"modListVersion": 2, "modList": [{"modid": "legacy", "version": "1.0", "authors": ["Old"]}]}),
+        ),
+    ] {
+        let jar = dir.path().join(name);
+        write_zip(&jar, &[("mcmod.info", info.to_string().as_bytes())]);
+        let legacy = read_mod_metadata(&jar).unwrap().remove(0);
+        assert_eq!(legacy.id, "legacy");
+        assert_eq!(legacy.loader, LoaderKind::Forge);
+        assert_eq!(legacy.authors, ["Old"]);
+        if name == "legacy.jar" {
+            assert_eq!(
+                legacy.depends,
+                [
+                    dependency("Forge", &["[10.13,)"]),
+                    dependency("CodeChickenCore", &[])
+                ]
+            );
+        }
+    }
+}
+
+#[test]
+fn scans_instance_mods_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let launcher = Launcher::new(dir.path());
+    let instance = launcher
+        .create_instance("Modded", InstanceConfig::vanilla("1.20.1"))
+        .unwrap();
+    let mods = instance.game_directory().join("mods");
+    let fabric = json!({"schemaVersion": 1, "id": "sodium", "version": "0.5.3"}).to_string();
+    write_zip(
+        &mods.join("sodium.jar.disabled"),
+        &[("fabric.mod.json", fabric.as_bytes())],
+    );
+    write_zip(&mods.join("library.jar"), &[("a/B.class", b"")]);
+    fs::write(mods.join("broken.jar"), b"not a zip").unwrap();
+    fs::write(mods.join("notes.txt"), b"hello").unwrap();
+    fs::create_dir(mods.join("folder.jar")).unwrap();
+
+    let files = launcher.instance_mods("Modded").unwrap();
+
+    let names: Vec<String> = files
+        .iter()
+        .map(|file| {
+            file.path
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect();
+    assert_eq!(names, ["broken.jar", "library.jar", "sodium.jar.disabled"]);
+    assert!(files[0].error.is_some());
+    assert!(files[1].mods.is_empty() && files[1].error.is_none());
+    assert!(!files[2].enabled);
+    assert_eq!(files[2].mods[0].id, "sodium");
+    assert!(files[1].enabled);
+}
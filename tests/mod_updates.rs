@@ -0,0 +1,410 @@
+mod support;
+
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use mc_launcher_core::{
+    instance::InstanceLoader,
+    io::hash::{sha1_file, sha512_file},
+    modrinth::ModrinthClient,
+    mods::{
+        dependencies::ModEnvironment,
+        metadata::scan_mods_directory,
+        updates::{
+            apply_mod_update, check_mod_updates, rollback_mod_update, HashedModFile, ModRelease,
+            ModReleaseLookup, ModUpdateProvider, ModrinthUpdateProvider, ROLLBACK_DIRECTORY,
+            STAGING_DIRECTORY,
+        },
+    },
+    net::download::{Checksum, DownloadOptions},
+    prelude::*,
+};
+use serde_json::json;
+use support::{write_profile, StandInServer};
+
+fn digests(bytes: &[u8]) -> (String, String) {
+    let dir = tempfile::tempdir().unwrap();
+    let probe = dir.path().join("probe");
+    fs::write(&probe, bytes).unwrap();
+    (sha1_file(&probe).unwrap(), sha512_file(&probe).unwrap())
+}
+
+fn modrinth_version(
+    server: &StandInServer,
+    project: &str,
+    id: &str,
+    number: &str,
+    published: &str,
+    bytes: &[u8],
+) -> serde_json::Value {
+    let (sha1, sha512) = digests(bytes);
+    let file_name = format!("{project}-{number}.jar");
+    let route = format!("/cdn/{file_name}");
+    server.serve(&route, bytes.to_vec());
+    json!({
+        "id": id,
+        "project_id": project,
+        "name": number,
+        "version_number": number,
+        "version_type": "release",
+        "date_published": published,
+        "loaders": ["fabric"],
+        "game_versions": ["1.20.1"],
+        "files": [{
+            "hashes": {"sha1": sha1, "sha512": sha512},
+            "url": server.url(&route),
+            "filename": file_name,
+            "primary": true,
+            "size": bytes.len(),
+        }],
+    })
+}
+
+fn release(
+    version_id: &str,
+    published: &str,
+    file_name: &str,
+    url: String,
+    bytes: &[u8],
+) -> ModRelease {
+    ModRelease {
+        project_id: "project".to_string(),
+        version_id: version_id.to_string(),
+        version_number: version_id.to_string(),
+        file_name: file_name.to_string(),
+        url,
+        checksum: Some(Checksum::Sha1(digests(bytes).0)),
+        size: Some(bytes.len() as u64),
+        date_published: published.to_string(),
+    }
+}
+
+#[test]
+fn checks_modrinth_updates_and_swaps_with_rollback() {
+    let server = StandInServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    let mods = dir.path();
+    fs::write(mods.join("sodium-0.5.3.jar"), b"sodium 0.5.3").unwrap();
+    fs::write(mods.join("lithium-0.11.2.jar.disabled"), b"lithium 0.11.2").unwrap();
+    fs::write(mods.join("private.jar"), b"private").unwrap();
+
+    fs::write(mods.join("iris-1.6.5.jar"), b"iris 1.6.5").unwrap();
+
+    let sodium_old = modrinth_version(
+        &server,
+        "sodium",
+        "s1",
+        "0.5.3",
+        "2023-09-01T00:00:00Z",
+        b"sodium 0.5.3",
+    );
+    let sodium_new = modrinth_version(
+        &server,
+        "sodium",
+        "s2",
+        "0.5.4",
+        "2023-11-01T00:00:00Z",
+        b"sodium 0.5.4",
+    );
+    let lithium = modrinth_version(
+        &server,
+        "lithium",
+        "l1",
+        "0.11.2",
+        "2023-06-01T00:00:00Z",
+        b"lithium 0.11.2",
+    );
+    let iris_installed = modrinth_version(
+        &server,
+        "iris",
+        "i2",
+        "1.6.5",
+        "2023-07-01T00:00:00Z",
+        b"iris 1.6.5",
+    );
+    let iris_older = modrinth_version(
+        &server,
+        "iris",
+        "i1",
+        "1.6.4",
+        "2023-05-01T00:00:00Z",
+        b"iris 1.6.4",
+    );
+    let sodium_sha1 = digests(b"sodium 0.5.3").0;
+    let lithium_sha1 = digests(b"lithium 0.11.2").0;
+    let iris_sha1 = digests(b"iris 1.6.5").0;
+    server.serve(
+        "/v2/version_files",
+        json!({
+            sodium_sha1.clone(): sodium_old,
+            lithium_sha1.clone(): lithium,
+            iris_sha1.clone(): iris_installed,
+        })
+        .to_string(),
+    );
+    server.serve(
+        "/v2/version_files/update",
+        json!({
+            sodium_sha1.clone(): sodium_new,
+            lithium_sha1.clone(): lithium,
+            iris_sha1.clone(): iris_older,
+        })
+        .to_string(),
+    );
+
+    let environment = ModEnvironment {
+        minecraft_version: "1.20.1".to_string(),
+        loader: Some(LoaderKind::Quilt),
+        loader_version: Some("0.26.0".to_string()),
+    };
+    let mut provider =
+        ModrinthUpdateProvider::new(ModrinthClient::with_base_url(server.url("/v2")).unwrap());
+    let updates = check_mod_updates(mods, &environment, &mut provider).unwrap();
+    assert_eq!(updates.len(), 1);
+    let update = &updates[0];
+    assert_eq!(update.path, mods.join("sodium-0.5.3.jar"));
+    assert_eq!(update.current.version_number, "0.5.3");
+    assert_eq!(update.latest.version_number, "0.5.4");
+    assert_eq!(update.latest.file_name, "sodium-0.5.4.jar");
+    assert_eq!(
+        update.latest.checksum,
+        Some(Checksum::Sha512(digests(b"sodium 0.5.4").1))
+    );
+    let (request, body) = server.requests().pop().unwrap();
+    assert_eq!(request, "POST /v2/version_files/update");
+    let body = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+    assert_eq!(body["algorithm"], "sha1");
+    assert_eq!(body["loaders"], json!(["quilt", "fabric"]));
+    assert_eq!(body["game_versions"], json!(["1.20.1"]));
+    assert_eq!(body["hashes"].as_array().unwrap().len(), 4);
+
+    let mut broken = update.clone();
+    broken.latest.checksum = Some(Checksum::Sha1("0".repeat(40)));
+    let mut reporter = |_event: ProgressEvent| {};
+    assert!(apply_mod_update(&broken, &DownloadOptions::default(), &mut reporter).is_err());
+    assert_eq!(
+        fs::read(mods.join("sodium-0.5.3.jar")).unwrap(),
+        b"sodium 0.5.3"
+    );
+    assert!(!mods.join(ROLLBACK_DIRECTORY).exists());
+
+    let applied = apply_mod_update(update, &DownloadOptions::default(), &mut reporter).unwrap();
+    assert_eq!(applied.installed, mods.join("sodium-0.5.4.jar"));
+    assert_eq!(fs::read(&applied.installed).unwrap(), b"sodium 0.5.4");
+    assert!(!update.path.exists());
+    assert_eq!(
+        applied.rollback,
+        mods.join(ROLLBACK_DIRECTORY).join("sodium-0.5.3.jar")
+    );
+    assert_eq!(fs::read(&applied.rollback).unwrap(), b"sodium 0.5.3");
+    assert!(!mods.join(STAGING_DIRECTORY).exists());
+    let names = scan_mods_directory(mods)
+        .unwrap()
+        .into_iter()
+        .map(|file| {
+            file.path
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "iris-1.6.5.jar",
+            "lithium-0.11.2.jar.disabled",
+            "private.jar",
+            "sodium-0.5.4.jar"
+        ]
+    );
+
+    rollback_mod_update(&applied).unwrap();
+    assert_eq!(fs::read(&update.path).unwrap(), b"sodium 0.5.3");
+    assert!(!applied.installed.exists());
+    assert!(!applied.rollback.exists());
+    assert!(rollback_mod_update(&applied).is_err());
+}
+
+#[test]
+fn updates_instance_mods_through_a_pluggable_provider() {
+    let server = StandInServer::start();
+    server.serve("/files/jei-15.3.0.jar", b"jei 15.3.0".to_vec());
+    let dir = tempfile::tempdir().unwrap();
+    let launcher = Launcher::new(dir.path());
+    write_profile(dir.path(), "1.20.1", json!({"id": "1.20.1"}));
+    write_profile(
+        dir.path(),
+        "1.20.1-forge-47.2.0",
+        json!({
+            "id": "1.20.1-forge-47.2.0",
+            "inheritsFrom": "1.20.1",
+            "libraries": [{"name": "net.minecraftforge:forge:1.20.1-47.2.0:universal"}],
+        }),
+    );
+    let instance = launcher
+        .create_instance(
+            "Forge",
+            InstanceConfig::with_loader(
+                "1.20.1",
+                Some(InstanceLoader {
+                    kind: LoaderKind::Forge,
+                    version: "1.20.1-47.2.0".to_string(),
+                }),
+            ),
+        )
+        .unwrap();
+    let mods = instance.game_directory().join("mods");
+    fs::create_dir_all(&mods).unwrap();
+    fs::write(mods.join("jei-15.2.0.jar.disabled"), b"jei 15.2.0").unwrap();
+
+    let url = server.url("/files/jei-15.3.0.jar");
+    let mut seen = Vec::new();
+    let mut provider = |files: &[HashedModFile], environment: &ModEnvironment| {
+        seen.push((files.to_vec(), environment.clone()));
+        Ok(files
+            .iter()
+            .map(|file| {
+                (
+                    file.path.clone(),
+                    ModReleaseLookup {
+                        current: release(
+                            "15.2.0",
+                            "2023-08-01T00:00:00Z",
+                            "jei-15.2.0.jar",
+                            String::new(),
+                            b"",
+                        ),
+                        latest: release(
+                            "15.3.0",
+                            "2023-10-01T00:00:00Z",
+                            "jei-15.3.0.jar",
+                            url.clone(),
+                            b"jei 15.3.0",
+                        ),
+                    },
+                )
+            })
+            .collect::<BTreeMap<_, _>>())
+    };
+    let updates = launcher
+        .check_instance_mod_updates("Forge", &mut provider)
+        .unwrap();
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].0[0].sha1, digests(b"jei 15.2.0").0);
+    assert_eq!(seen[0].0[0].sha512, None);
+    assert_eq!(seen[0].1.loader, Some(LoaderKind::Forge));
+    assert_eq!(seen[0].1.loader_version.as_deref(), Some("47.2.0"));
+    assert_eq!(updates.len(), 1);
+
+    let other = tempfile::tempdir().unwrap();
+    let mut stray = updates[0].clone();
+    stray.path = other.path().join("jei-15.2.0.jar.disabled");
+    assert!(matches!(
+        launcher.update_instance_mod("Forge", &stray),
+        Err(LauncherError::UnsafePath { .. })
+    ));
+
+    let mut escaping = updates[0].clone();
+    escaping.latest.file_name = "../jei.jar".to_string();
+    assert!(matches!(
+        launcher.update_instance_mod("Forge", &escaping),
+        Err(LauncherError::UnsafePath { .. })
+    ));
+
+    let applied = launcher.update_instance_mod("Forge", &updates[0]).unwrap();
+    assert_eq!(applied.installed, mods.join("jei-15.3.0.jar.disabled"));
+    assert_eq!(fs::read(&applied.installed).unwrap(), b"jei 15.3.0");
+    assert!(!mods.join("jei-15.2.0.jar.disabled").exists());
+}
+
+#[test]
+fn never_offers_a_downgrade() {
+    let dir = tempfile::tempdir().unwrap();
+    let mods = dir.path();
+    fs::write(mods.join("jei-15.3.0.jar"), b"jei 15.3.0").unwrap();
+    fs::write(mods.join("emi-1.0.0.jar"), b"emi 1.0.0").unwrap();
+
+    let mut provider = |files: &[HashedModFile], _environment: &ModEnvironment| {
+        Ok(files
+            .iter()
+            .map(|file| {
+                let current = release(
+                    "15.3.0",
+                    "2023-10-01T00:00:00Z",
+                    "jei-15.3.0.jar",
+                    String::new(),
+                    b"",
+                );
+                let mut latest = release(
+                    "15.2.0",
+                    "2023-08-01T00:00:00Z",
+                    "jei-15.2.0.jar",
+                    String::new(),
+                    b"",
+                );
+                if file.path.ends_with("emi-1.0.0.jar") {
+                    latest.version_id = "1.1.0".to_string();
+                    latest.date_published = String::new();
+                }
+                (file.path.clone(), ModReleaseLookup { current, latest })
+            })
+            .collect::<BTreeMap<_, _>>())
+    };
+    let environment = ModEnvironment {
+        minecraft_version: "1.20.1".to_string(),
+        loader: Some(LoaderKind::Forge),
+        loader_version: None,
+    };
+    assert_eq!(
+        check_mod_updates(mods, &environment, &mut provider).unwrap(),
+        []
+    );
+
+    let at = |version_id: &str, published: &str| {
+        release(version_id, published, "a.jar", String::new(), b"")
+    };
+    assert!(at("2", "2023-10-01T12:34:56.1Z").is_newer_than(&at("1", "2023-10-01T12:34:56Z")));
+    assert!(at("2", "2023-10-01T12:00:00Z").is_newer_than(&at("1", "2023-10-01T13:00:00+02:00")));
+    assert!(!at("2", "2023-10-01T12:00:00+00:00").is_newer_than(&at("1", "2023-10-01T12:00:00Z")));
+    assert!(!at("2", "yesterday").is_newer_than(&at("1", "2023-10-01T12:00:00Z")));
+    assert!(!at("2", "2023-10-01T12:00:00Z").is_newer_than(&at("1", "")));
+}
+
+struct Sha512Provider {
+    seen: Vec<HashedModFile>,
+}
+
+impl ModUpdateProvider for Sha512Provider {
+    fn needs_sha512(&self) -> bool {
+        true
+    }
+
+    fn lookup(
+        &mut self,
+        files: &[HashedModFile],
+        _environment: &ModEnvironment,
+    ) -> mc_launcher_core::Result<BTreeMap<PathBuf, ModReleaseLookup>> {
+        self.seen.extend_from_slice(files);
+        Ok(BTreeMap::new())
+    }
+}
+
+#[test]
+fn hashes_with_sha512_only_on_request() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("jei-15.3.0.jar"), b"jei 15.3.0").unwrap();
+    let environment = ModEnvironment {
+        minecraft_version: "1.20.1".to_string(),
+        loader: None,
+        loader_version: None,
+    };
+    let mut provider = Sha512Provider { seen: Vec::new() };
+    assert_eq!(
+        check_mod_updates(dir.path(), &environment, &mut provider).unwrap(),
+        []
+    );
+    let (sha1, sha512) = digests(b"jei 15.3.0");
+    assert_eq!(provider.seen.len(), 1);
+    assert_eq!(provider.seen[0].sha1, sha1);
+    assert_eq!(provider.seen[0].sha512, Some(sha512));
+}
@@ -0,0 +1,224 @@
+mod support;
+
+use mc_launcher_core::{
+    io::hash::{sha1_file, sha512_file},
+    modrinth::{
+        ModrinthClient, ModrinthDependencyType, ModrinthHashAlgorithm, ModrinthProjectType,
+        ModrinthSearchQuery, ModrinthVersionFilter, ModrinthVersionType,
+    },
+    net::download::{execute_plan, Checksum, DownloadPlan},
+    progress::ProgressEvent,
+};
+use serde_json::json;
+use support::StandInServer;
+
+fn version(server: &StandInServer, id: &str, number: &str, bytes: &[u8]) -> serde_json::Value {
+    let dir = tempfile::tempdir().unwrap();
+    let probe = dir.path().join("probe");
+    std::fs::write(&probe, bytes).unwrap();
+    let route = format!("/data/{id}.jar");
+    server.serve(&route, bytes.to_vec());
+    json!({
+        "id": id,
+        "project_id": "AANobbMI",
+        "name": format!("Sodium {number}"),
+        "version_number": number,
+        "version_type": "release",
+        "loaders": ["fabric"],
+        "game_versions": ["1.20.1"],
+        "date_published": "2024-01-01T00:00:00Z",
+        "dependencies": [
+            {"project_id": "P7dR8mSH", "dependency_type": "required"},
+            {"version_id": null, "project_id": "YL57xq9U", "dependency_type": "incompatible"},
+        ],
+        "files": [
+            {
+                "hashes": {"sha1": "0000", "sha512": "0000"},
+                "url": server.url("/data/sources.jar"),
+                "filename": "sodium-sources.jar",
+                "primary": false,
+                "size": 1,
+            },
+            {
+                "hashes": {
+                    "sha1": sha1_file(&probe).unwrap(),
+                    "sha512": sha512_file(&probe).unwrap(),
+                },
+                "url": server.url(&route),
+                "filename": format!("sodium-{number}.jar"),
+                "primary": true,
+                "size": bytes.len(),
+            },
+        ],
+    })
+}
+
+#[test]
+fn searches_projects_with_facets() {
+    let server = StandInServer::start();
+    server.serve(
+        "/v2/search",
+        json!({
+            "hits": [{
+                "project_id": "AANobbMI",
+                "project_type": "mod",
+                "slug": "sodium",
+                "title": "Sodium",
+                "description": "Rendering engine",
+                "author": "jellysquid3",
+                "categories": ["fabric", "optimization"],
+                "versions": ["1.20.1"],
+                "downloads": 1000,
+                "icon_url": null,
+                "latest_version": "abc",
+                "client_side": "required",
+            }],
+            "offset": 20,
+            "limit": 5,
+            "total_hits": 21,
+        })
+        .to_string(),
+    );
+    let client = ModrinthClient::with_base_url(server.url("/v2/")).unwrap();
+    assert_eq!(client.base_url(), server.url("/v2"));
+
+    let results = client
+        .search(&ModrinthSearchQuery {
+            query: "sodium mod".to_string(),
+            loaders: vec!["fabric".to_string(), "quilt".to_string()],
+            game_versions: vec!["1.20.1".to_string()],
+            project_type: Some(ModrinthProjectType::Mod),
+            index: Some("downloads".to_string()),
+            offset: 20,
+            limit: Some(5),
+        })
+        .unwrap();
+
+    assert_eq!(results.total_hits, 21);
+    assert_eq!(results.hits[0].slug, "sodium");
+    let (request, _) = &server.requests()[0];
+    let url = url::Url::parse(&format!("http://host{}", &request["GET ".len()..])).unwrap();
+    let query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    assert_eq!(url.path(), "/v2/search");
+    assert_eq!(
+        query,
+        [
+            ("query", "sodium mod"),
+            (
+                "facets",
+                r#"[["categories:fabric","categories:quilt"],["versions:1.20.1"],["project_type:mod"]]"#,
+            ),
+            ("index", "downloads"),
+            ("offset", "20"),
+            ("limit", "5"),
+        ]
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+    );
+}
+
+#[test]
+fn lists_versions_and_downloads_primary_file() {
+    let server = StandInServer::start();
+    let newest = version(&server, "v2", "0.5.3", b"sodium 0.5.3");
+    server.serve(
+        "/v2/project/sodium/version",
+        json!([newest, version(&server, "v1", "0.5.2", b"sodium 0.5.2")]).to_string(),
+    );
+    let client = ModrinthClient::with_base_url(server.url("/v2")).unwrap();
+
+    let versions = client
+        .project_versions(
+            "sodium",
+            &ModrinthVersionFilter {
+                loaders: vec!["fabric".to_string()],
+                game_versions: vec!["1.20.1".to_string()],
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        server.requests()[0].0,
+        "GET /v2/project/sodium/version?loaders=%5B%22fabric%22%5D&game_versions=%5B%221.20.1%22%5D"
+    );
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[0].version_type, ModrinthVersionType::Release);
+    assert_eq!(
+        versions[0].dependencies[1].dependency_type,
+        ModrinthDependencyType::Incompatible
+    );
+    let file = versions[0].primary_file().unwrap();
+    assert_eq!(file.filename, "sodium-0.5.3.jar");
+
+    let dir = tempfile::tempdir().unwrap();
+    let task = file.download_task(dir.path().join("mods")).unwrap();
+    assert_eq!(task.size, Some(12));
+    assert_eq!(
+        task.checksum,
+        Some(Checksum::Sha512(file.hashes.sha512.clone()))
+    );
+    let mut reporter = |_event: ProgressEvent| {};
+    execute_plan(&DownloadPlan { tasks: vec![task] }, &mut reporter).unwrap();
+    assert_eq!(
+        std::fs::read(dir.path().join("mods/sodium-0.5.3.jar")).unwrap(),
+        b"sodium 0.5.3"
+    );
+
+    let mut unsafe_file = file.clone();
+    unsafe_file.filename = "../escape.jar".to_string();
+    assert!(unsafe_file.download_task(dir.path()).is_err());
+}
+
+#[test]
+fn looks_up_versions_by_file_hash() {
+    let server = StandInServer::start();
+    let known = version(&server, "v2", "0.5.3", b"sodium 0.5.3");
+    let sha1 = known["files"][1]["hashes"]["sha1"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    server.serve(&format!("/v2/version_file/{sha1}"), known.to_string());
+    server.serve(
+        "/v2/version_files",
+        json!({ sha1.clone(): known }).to_string(),
+    );
+    let client = ModrinthClient::with_base_url(server.url("/v2")).unwrap();
+
+    let found = client
+        .version_from_hash(&sha1, ModrinthHashAlgorithm::Sha1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.id, "v2");
+    assert_eq!(
+        server.requests()[0].0,
+        format!("GET /v2/version_file/{sha1}?algorithm=sha1")
+    );
+    assert_eq!(
+        client
+            .version_from_hash("ffff", ModrinthHashAlgorithm::Sha512)
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        client
+            .version_from_hash("ff/ff?", ModrinthHashAlgorithm::Sha512)
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        server.requests()[2].0,
+        "GET /v2/version_file/ff%2Fff%3F?algorithm=sha512"
+    );
+
+    let hashes = vec![sha1.clone(), "ffff".to_string()];
+    let versions = client
+        .versions_from_hashes(&hashes, ModrinthHashAlgorithm::Sha1)
+        .unwrap();
+    assert_eq!(versions.len(), 1);
+    assert_eq!(versions[&sha1].version_number, "0.5.3");
+    let (request, body) = server.requests().pop().unwrap();
+    assert_eq!(request, "POST /v2/version_files");
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+        json!({"hashes": hashes, "algorithm": "sha1"})
+    );
+}
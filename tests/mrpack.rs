@@ -0,0 +1,384 @@
+mod support;
+
+use std::{
+    fs::{self, File},
+    io::Read,
+    path::Path,
+};
+
+use mc_launcher_core::{
+    instance::InstanceLoader,
+    io::hash::{sha1_file, sha512_file},
+    loader::common::{LoaderSpec, LoaderVersion},
+    modpack::mrpack::{
+        mrpack_install_request, plan_mrpack_downloads, read_mrpack_index, read_mrpack_information,
+        MrpackExportOptions,
+    },
+    prelude::*,
+    types::{
+        mrpack_types::{MrpackHashes, MrpackIndex},
+        MrpackInstallOptions,
+    },
+};
+use serde_json::json;
+use support::{write_zip, StandInServer};
+
+fn hashes(bytes: &[u8]) -> serde_json::Value {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("probe");
+    fs::write(&path, bytes).unwrap();
+    json!({"sha1": sha1_file(&path).unwrap(), "sha512": sha512_file(&path).unwrap()})
+}
+
+fn file_entry(
+    server: &StandInServer,
+    path: &str,
+    bytes: &[u8],
+    env: Option<(&str, &str)>,
+) -> serde_json::Value {
+    let route = format!("/data/{path}");
+    server.serve(&route, bytes.to_vec());
+    let mut entry = json!({
+        "path": path,
+        "hashes": hashes(bytes),
+        "downloads": [server.url(&route)],
+        "fileSize": bytes.len(),
+    });
+    if let Some((client, server)) = env {
+        entry["env"] = json!({"client": client, "server": server});
+    }
+    entry
+}
+
+fn write_mrpack(path: &Path, index: &serde_json::Value, entries: &[(&str, &[u8])]) {
+    let index = index.to_string();
+    let mut all = vec![("modrinth.index.json", index.as_bytes())];
+    all.extend_from_slice(entries);
+    write_zip(path, &all);
+}
+
+fn index(dependencies: serde_json::Value, files: Vec<serde_json::Value>) -> serde_json::Value {
+    json!({
+        "formatVersion": 1,
+        "game": "minecraft",
+        "versionId": "1.2.0",
+        "name": "Example Pack",
+        "summary": "A test pack",
+        "files": files,
+        "dependencies": dependencies,
+    })
+}
+
+#[test]
+fn imports_files_and_overrides_into_instance() {
+    let server = StandInServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    let pack = dir.path().join("pack.mrpack");
+    write_mrpack(
+        &pack,
+        &index(
+            json!({"minecraft": "1.20.1", "fabric-loader": "0.15.0"}),
+            vec![
+                json!({
+                    "path": "mods/lithium.jar",
+                    "hashes": hashes(b"lithium"),
+                    "downloads": [server.url("/offline/lithium.jar"), server.url("/data/mods/lithium.jar")],
+                    "fileSize": 7,
+                }),
+                file_entry(&server, "mods/sodium.jar", b"sodium", None),
+                file_entry(
+                    &server,
+                    "mods/modmenu.jar",
+                    b"modmenu",
+                    Some(("required", "unsupported")),
+                ),
+                file_entry(
+                    &server,
+                    "shaderpacks/complementary.zip",
+                    b"shader",
+                    Some(("optional", "unsupported")),
+                ),
+                file_entry(
+                    &server,
+                    "resourcepacks/extra.zip",
+                    b"extra",
+                    Some(("optional", "optional")),
+                ),
+                file_entry(
+                    &server,
+                    "mods/server-only.jar",
+                    b"server",
+                    Some(("unsupported", "required")),
+                ),
+            ],
+        ),
+        &[
+            ("overrides/config/sodium.json", b"shared"),
+            ("overrides/options.txt", b"lang:en_us"),
+            ("client-overrides/config/sodium.json", b"client"),
+            ("server-overrides/server.properties", b"motd=hi"),
+        ],
+    );
+
+    let information = read_mrpack_information(&pack).unwrap();
+    assert_eq!(information.name, "Example Pack");
+    assert_eq!(information.minecraft_version, "1.20.1");
+    assert_eq!(
+        information.optional_files,
+        ["shaderpacks/complementary.zip", "resourcepacks/extra.zip"]
+    );
+
+    server.serve("/data/mods/lithium.jar", b"lithium".to_vec());
+    let launcher = Launcher::new(dir.path().join("minecraft"));
+    let instance = launcher
+        .import_mrpack(
+            &pack,
+            "Example",
+            &MrpackInstallOptions {
+                optional_files: Some(vec!["shaderpacks/complementary.zip".to_string()]),
+                skip_dependencies_install: Some(true),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(instance.config.version_id, "fabric-loader-0.15.0-1.20.1");
+    assert_eq!(instance.config.minecraft_version, "1.20.1");
+    assert_eq!(launcher.instance("Example").unwrap(), instance);
+    let game = instance.game_directory();
+    assert_eq!(fs::read(game.join("mods/sodium.jar")).unwrap(), b"sodium");
+    assert_eq!(fs::read(game.join("mods/lithium.jar")).unwrap(), b"lithium");
+    assert!(server
+        .requests()
+        .iter()
+        .any(|(request, _)| request == "GET /offline/lithium.jar"));
+    assert!(game.join("mods/modmenu.jar").is_file());
+    assert!(game.join("shaderpacks/complementary.zip").is_file());
+    assert!(!game.join("resourcepacks/extra.zip").exists());
+    assert!(!game.join("mods/server-only.jar").exists());
+    assert_eq!(
+        fs::read(game.join("config/sodium.json")).unwrap(),
+        b"client"
+    );
+    assert_eq!(fs::read(game.join("options.txt")).unwrap(), b"lang:en_us");
+    assert!(!game.join("server.properties").exists());
+}
+
+#[test]
+fn maps_dependencies_to_install_request() {
+    let parse = |dependencies: serde_json::Value| -> MrpackIndex {
+        serde_json::from_value(index(dependencies, Vec::new())).unwrap()
+    };
+
+    let request =
+        mrpack_install_request(&parse(json!({"minecraft": "1.20.1", "forge": "47.2.0"}))).unwrap();
+    assert_eq!(request.minecraft_version, "1.20.1");
+    assert_eq!(
+        request.loader,
+        Some(LoaderSpec::Forge {
+            version: LoaderVersion::Exact("1.20.1-47.2.0".to_string()),
+        })
+    );
+    let request = mrpack_install_request(&parse(
+        json!({"minecraft": "1.20.4", "neoforge": "20.4.80"}),
+    ))
+    .unwrap();
+    assert_eq!(
+        request.loader,
+        Some(LoaderSpec::NeoForge {
+            version: LoaderVersion::Exact("20.4.80".to_string()),
+        })
+    );
+    assert_eq!(
+        mrpack_install_request(&parse(json!({"minecraft": "1.20.1"})))
+            .unwrap()
+            .loader,
+        None
+    );
+
+    assert!(mrpack_install_request(&parse(json!({"fabric-loader": "0.15.0"}))).is_err());
+    assert!(mrpack_install_request(&parse(
+        json!({"minecraft": "1.20.1", "fabric-loader": "0.15.0", "quilt-loader": "0.23.0"})
+    ))
+    .is_err());
+    assert!(mrpack_install_request(&parse(json!({"minecraft": "1.20.1", "rift": "1.0"}))).is_err());
+}
+
+#[test]
+fn rejects_unsafe_paths_and_removes_failed_imports() {
+    let server = StandInServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    let escaping: MrpackIndex = serde_json::from_value(index(
+        json!({"minecraft": "1.20.1"}),
+        vec![file_entry(&server, "../escape.jar", b"x", None)],
+    ))
+    .unwrap();
+    assert!(matches!(
+        plan_mrpack_downloads(&escaping, dir.path(), &MrpackInstallOptions::default()),
+        Err(LauncherError::UnsafePath { .. })
+    ));
+
+    let mut tampered = file_entry(&server, "mods/sodium.jar", b"sodium", None);
+    server.serve("/data/mods/sodium.jar", b"tamper".to_vec());
+    tampered["fileSize"] = json!(6);
+    let pack = dir.path().join("broken.mrpack");
+    write_mrpack(
+        &pack,
+        &index(json!({"minecraft": "1.20.1"}), vec![tampered]),
+        &[],
+    );
+    assert_eq!(read_mrpack_index(&pack).unwrap().files.len(), 1);
+
+    let launcher = Launcher::new(dir.path().join("minecraft"));
+    let result = launcher.import_mrpack(
+        &pack,
+        "Broken",
+        &MrpackInstallOptions {
+            skip_dependencies_install: Some(true),
+            ..Default::default()
+        },
+    );
+
+    assert!(
+        matches!(result, Err(LauncherError::ChecksumMismatch { .. })),
+        "{result:?}"
+    );
+    assert!(launcher.instances().unwrap().is_empty());
+}
+
+#[test]
+fn exports_instance_and_reimports_it() {
+    let server = StandInServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    let launcher = Launcher::new(dir.path().join("minecraft"));
+    let instance = launcher
+        .create_instance(
+            "Forge",
+            InstanceConfig {
+                minecraft_version: "1.20.1".to_string(),
+                version_id: "1.20.1-forge-47.2.0".to_string(),
+                loader: Some(InstanceLoader {
+                    kind: LoaderKind::Forge,
+                    version: "1.20.1-47.2.0".to_string(),
+                }),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let game = instance.game_directory();
+    for (path, bytes) in [
+        ("mods/jei.jar", &b"jei"[..]),
+        ("mods/private.jar", b"private"),
+        ("resourcepacks/faithful.zip", b"faithful"),
+        ("config/jei.toml", b"config"),
+        ("options.txt", b"lang:en_us"),
+        ("logs/latest.log", b"log"),
+        ("saves/World/level.dat", b"level"),
+        ("config/cache/index.bin", b"cache"),
+    ] {
+        fs::create_dir_all(game.join(path).parent().unwrap()).unwrap();
+        fs::write(game.join(path), bytes).unwrap();
+    }
+    server.serve("/jei.jar", b"jei".to_vec());
+    server.serve("/faithful.zip", b"faithful".to_vec());
+
+    let mut offered = Vec::new();
+    let mut resolver = |path: &str, hashes: &MrpackHashes| {
+        offered.push(path.to_string());
+        assert_eq!(hashes.sha1.len(), 40);
+        Ok((path != "mods/private.jar")
+            .then(|| vec![server.url(&format!("/{}", path.rsplit('/').next().unwrap()))]))
+    };
+    let mut options = MrpackExportOptions::new("Forge Pack", "2.0.0");
+    options
+        .exclude
+        .extend(["saves".to_string(), "config/**/*.bin".to_string()]);
+    let pack = dir.path().join("forge.mrpack");
+    let exported = launcher
+        .export_mrpack("Forge", &pack, &options, &mut resolver)
+        .unwrap();
+
+    assert_eq!(
+        offered,
+        [
+            "mods/jei.jar",
+            "mods/private.jar",
+            "resourcepacks/faithful.zip"
+        ]
+    );
+    assert!(!dir.path().join("forge.mrpack.part").exists());
+    let index = read_mrpack_index(&pack).unwrap();
+    assert_eq!(index, exported);
+    assert_eq!(
+        index.dependencies,
+        [("forge", "47.2.0"), ("minecraft", "1.20.1")]
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .into()
+    );
+    let files: Vec<&str> = index.files.iter().map(|file| file.path.as_str()).collect();
+    assert_eq!(files, ["mods/jei.jar", "resourcepacks/faithful.zip"]);
+    assert_eq!(index.files[0].file_size, 3);
+    assert_eq!(index.files[0].hashes.sha512, hashes(b"jei")["sha512"]);
+    let mut archive = zip::ZipArchive::new(File::open(&pack).unwrap()).unwrap();
+    let mut names: Vec<&str> = archive.file_names().collect();
+    names.sort_unstable();
+    assert_eq!(
+        names,
+        [
+            "modrinth.index.json",
+            "overrides/config/jei.toml",
+            "overrides/mods/private.jar",
+            "overrides/options.txt",
+        ]
+    );
+    let mut override_bytes = Vec::new();
+    archive
+        .by_name("overrides/mods/private.jar")
+        .unwrap()
+        .read_to_end(&mut override_bytes)
+        .unwrap();
+    assert_eq!(override_bytes, b"private");
+
+    let imported = launcher
+        .import_mrpack(
+            &pack,
+            "Forge Copy",
+            &MrpackInstallOptions {
+                skip_dependencies_install: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(imported.config.loader, instance.config.loader);
+    assert_eq!(imported.config.version_id, "1.20.1-forge-47.2.0");
+    let copy = imported.game_directory();
+    assert_eq!(fs::read(copy.join("mods/jei.jar")).unwrap(), b"jei");
+    assert_eq!(fs::read(copy.join("mods/private.jar")).unwrap(), b"private");
+    assert!(copy.join("resourcepacks/faithful.zip").is_file());
+    assert!(!copy.join("logs").exists());
+    assert!(!copy.join("saves/World").exists());
+
+    let mut only_mods = MrpackExportOptions::new("Mods", "1.0.0");
+    only_mods.include.push("mods/*.jar".to_string());
+    let mut nothing = |_: &str, _: &MrpackHashes| Ok(None);
+    let index = launcher
+        .export_mrpack(
+            "Forge",
+            dir.path().join("mods.mrpack"),
+            &only_mods,
+            &mut nothing,
+        )
+        .unwrap();
+    assert!(index.files.is_empty());
+    let archive =
+        zip::ZipArchive::new(File::open(dir.path().join("mods.mrpack")).unwrap()).unwrap();
+    let mut names: Vec<&str> = archive.file_names().collect();
+    names.sort_unstable();
+    assert_eq!(
+        names,
+        [
+            "modrinth.index.json",
+            "overrides/mods/jei.jar",
+            "overrides/mods/private.jar",
+        ]
+    );
+}
@@ -0,0 +1,182 @@
+use std::{fs, path::Path};
+
+use mc_launcher_core::{
+    instance::{InstanceLoader, Resolution},
+    loader::common::{LoaderSpec, LoaderVersion},
+    modpack::multimc::read_multimc_instance,
+    prelude::*,
+    types::MultiMcInstallOptions,
+};
+use serde_json::json;
+
+fn write_instance(dir: &Path, cfg: &str, components: serde_json::Value) {
+    fs::create_dir_all(dir).unwrap();
+    fs::write(dir.join("instance.cfg"), cfg).unwrap();
+    fs::write(
+        dir.join("mmc-pack.json"),
+        json!({"formatVersion": 1, "components": components}).to_string(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn imports_prism_instance_with_settings_and_game_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("prism/Fabric Pack");
+    write_instance(
+        &source,
+        r#"[General]
+ConfigVersion=1.2
+InstanceType=OneSix
+name=Fabric Pack
+iconKey=flame
+OverrideJavaLocation=true
+JavaPath=/opt/java/17/bin/java
+OverrideJavaArgs=true
+JvmArgs="-XX:+UseG1GC -Dfoo=\"a b\""
+OverrideMemory=true
+MinMemAlloc=1024
+MaxMemAlloc=6144
+OverrideWindow=true
+MinecraftWinWidth=1280
+MinecraftWinHeight=720
+"#,
+        json!([
+            {"uid": "org.lwjgl3", "version": "3.3.1", "dependencyOnly": true},
+            {"uid": "net.minecraft", "version": "1.20.1", "important": true},
+            {"uid": "net.fabricmc.intermediary", "version": "1.20.1", "dependencyOnly": true},
+            {"uid": "net.fabricmc.fabric-loader", "version": "0.15.0"},
+            {"uid": "org.multimc.jarmod.1234", "cachedName": "OptiFine.jar"},
+            {"uid": "custom.agent", "version": "1.0"},
+        ]),
+    );
+    fs::create_dir_all(source.join("patches")).unwrap();
+    fs::write(source.join("patches/custom.agent.json"), "{}").unwrap();
+    fs::create_dir_all(source.join(".minecraft/mods")).unwrap();
+    fs::write(source.join(".minecraft/mods/sodium.jar"), b"sodium").unwrap();
+    fs::write(source.join(".minecraft/options.txt"), b"lang:en_us").unwrap();
+
+    let read = read_multimc_instance(&source).unwrap();
+    assert_eq!(read.name, "Fabric Pack");
+    assert_eq!(
+        read.install_request().loader,
+        Some(LoaderSpec::Fabric {
+            version: LoaderVersion::Exact("0.15.0".to_string()),
+        })
+    );
+
+    let launcher = Launcher::new(dir.path().join("minecraft"));
+    let import = launcher
+        .import_multimc(
+            &source,
+            "Imported",
+            &MultiMcInstallOptions {
+                skip_dependencies_install: Some(true),
+            },
+        )
+        .unwrap();
+
+    let config = &import.instance.config;
+    assert_eq!(config.minecraft_version, "1.20.1");
+    assert_eq!(config.version_id, "fabric-loader-0.15.0-1.20.1");
+    assert_eq!(
+        config.java_executable.as_deref(),
+        Some(Path::new("/opt/java/17/bin/java"))
+    );
+    assert_eq!(config.jvm_args, ["-XX:+UseG1GC", "-Dfoo=a b"]);
+    assert_eq!(
+        (config.min_memory_mb, config.max_memory_mb),
+        (Some(1024), Some(6144))
+    );
+    assert_eq!(
+        config.resolution,
+        Some(Resolution {
+            width: 1280,
+            height: 720
+        })
+    );
+    assert_eq!(config.icon.as_deref(), Some("flame"));
+    let uids: Vec<&str> = import
+        .unmapped_components
+        .iter()
+        .map(|unmapped| unmapped.component.uid.as_str())
+        .collect();
+    assert_eq!(uids, ["org.multimc.jarmod.1234", "custom.agent"]);
+    assert_eq!(
+        import.unmapped_components[1].patch,
+        Some(source.join("patches/custom.agent.json"))
+    );
+    let game = import.instance.game_directory();
+    assert_eq!(fs::read(game.join("mods/sodium.jar")).unwrap(), b"sodium");
+    assert!(game.join("options.txt").is_file());
+    assert!(game.join("saves").is_dir());
+    assert_eq!(launcher.instance("Imported").unwrap(), import.instance);
+}
+
+#[test]
+fn maps_loader_components_and_legacy_instances() {
+    let dir = tempfile::tempdir().unwrap();
+    let read = |name: &str, cfg: &str, components: serde_json::Value| {
+        let path = dir.path().join(name);
+        write_instance(&path, cfg, components);
+        read_multimc_instance(&path)
+    };
+
+    let forge = read(
+        "forge",
+        "name=Forge\nOverrideMemory=false\nMaxMemAlloc=8192\n",
+        json!([
+            {"uid": "net.minecraft", "version": "1.20.1"},
+            {"uid": "net.minecraftforge", "version": "47.2.0"},
+        ]),
+    )
+    .unwrap();
+    assert_eq!(
+        forge.config.loader,
+        Some(InstanceLoader {
+            kind: LoaderKind::Forge,
+            version: "1.20.1-47.2.0".to_string(),
+        })
+    );
+    assert_eq!(forge.config.version_id, "1.20.1-forge-47.2.0");
+    assert_eq!(forge.config.max_memory_mb, None);
+    assert!(forge.unmapped_components.is_empty());
+    assert_eq!(forge.game_directory, None);
+
+    let neoforge = read(
+        "neoforge",
+        "",
+        json!([
+            {"uid": "net.minecraft", "version": "1.20.4"},
+            {"uid": "net.neoforged", "version": "20.4.80"},
+        ]),
+    )
+    .unwrap();
+    assert_eq!(neoforge.name, "neoforge");
+    assert_eq!(neoforge.config.version_id, "neoforge-20.4.80");
+
+    let legacy = dir.path().join("legacy");
+    fs::create_dir_all(legacy.join("minecraft")).unwrap();
+    fs::write(legacy.join("instance.cfg"), "IntendedVersion=1.7.10\n").unwrap();
+    let legacy = read_multimc_instance(&legacy).unwrap();
+    assert_eq!(legacy.config, InstanceConfig::vanilla("1.7.10"));
+    assert_eq!(
+        legacy.game_directory,
+        Some(dir.path().join("legacy/minecraft"))
+    );
+
+    assert!(read(
+        "two-loaders",
+        "",
+        json!([
+            {"uid": "net.minecraft", "version": "1.20.1"},
+            {"uid": "net.fabricmc.fabric-loader", "version": "0.15.0"},
+            {"uid": "org.quiltmc.quilt-loader", "version": "0.23.0"},
+        ]),
+    )
+    .is_err());
+    assert!(matches!(
+        read("no-minecraft", "", json!([])),
+        Err(LauncherError::MissingField { .. })
+    ));
+}
@@ -0,0 +1,256 @@
+//! Local HTTP stand-in used by hermetic network tests, plus archive and
+//! metadata builders shared by several test files.
+
+#![allow(dead_code)]
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{BufRead, BufReader, Cursor, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::Path,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use mc_launcher_core::{
+    io::hash::sha1_file,
+    mods::metadata::ModDependency,
+    net::mirror::{Endpoints, Mirror},
+};
+use zip::write::SimpleFileOptions;
+
+/// Canned response served for one path.
+#[derive(Debug, Clone)]
+pub struct Route {
+    pub status: u16,
+    pub body: Vec<u8>,
+    /// Number of leading requests answered with `500` before `status`.
+    pub fail_first: usize,
+    /// Whether `Range: bytes=N-` requests are answered with `206`.
+    pub ranges: bool,
+}
+
+impl Route {
+    pub fn ok(body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status: 200,
+            body: body.into(),
+            fail_first: 0,
+            ranges: false,
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    routes: HashMap<String, Route>,
+    hits: HashMap<String, usize>,
+    ranges: Vec<(String, u64)>,
+    requests: Vec<(String, String)>,
+}
+
+/// Minimal blocking HTTP/1.1 server bound to a random localhost port.
+#[derive(Clone)]
+pub struct StandInServer {
+    base: String,
+    state: Arc<Mutex<State>>,
+}
+
+impl StandInServer {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(State::default()));
+        let shared = Arc::clone(&state);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let state = Arc::clone(&shared);
+                thread::spawn(move || handle(stream, &state));
+            }
+        });
+        Self { base, state }
+    }
+
+    /// Returns the server base URL without a trailing slash.
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    /// Returns the absolute URL for a path beginning with `/`.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base)
+    }
+
+    pub fn route(&self, path: &str, route: Route) {
+        self.state
+            .lock()
+            .unwrap()
+            .routes
+            .insert(path.to_string(), route);
+    }
+
+    pub fn serve(&self, path: &str, body: impl Into<Vec<u8>>) {
+        self.route(path, Route::ok(body));
+    }
+
+    pub fn hits(&self, path: &str) -> usize {
+        self.state
+            .lock()
+            .unwrap()
+            .hits
+            .get(path)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns the `("METHOD target", body)` pairs of every request received.
+    pub fn requests(&self) -> Vec<(String, String)> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Returns the `(path, start)` pairs of every ranged request received.
+    pub fn range_requests(&self) -> Vec<(String, u64)> {
+        self.state.lock().unwrap().ranges.clone()
+    }
+}
+
+/// Returns a mirror that serves the version manifest, assets, libraries, Java
+/// runtime index, and `piston-data` downloads from `server`.
+pub fn stand_in_mirror(name: &str, server: &StandInServer) -> Mirror {
+    Mirror {
+        name: name.to_string(),
+        endpoints: Endpoints {
+            version_manifest: server.url("/mc/game/version_manifest_v2.json"),
+            resources: server.url("/assets"),
+            libraries: server.url("/maven"),
+            java_runtime_manifest: server.url("/runtime/all.json"),
+            ..Endpoints::official()
+        },
+        rewrites: vec![(
+            "https://piston-data.mojang.com".to_string(),
+            server.base().to_string(),
+        )],
+    }
+}
+
+/// Writes `versions/<id>/<id>.json` below `minecraft_dir`.
+pub fn write_profile(minecraft_dir: &Path, id: &str, profile: impl ToString) {
+    let version_dir = minecraft_dir.join("versions").join(id);
+    fs::create_dir_all(&version_dir).unwrap();
+    fs::write(version_dir.join(format!("{id}.json")), profile.to_string()).unwrap();
+}
+
+/// Returns the SHA-1 digest of `bytes` as lowercase hexadecimal.
+pub fn sha1_of(bytes: &[u8]) -> String {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("probe");
+    fs::write(&path, bytes).unwrap();
+    sha1_file(path).unwrap()
+}
+
+/// Builds a zip archive holding `entries` as `(name, contents)` pairs.
+pub fn zip_bytes(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, bytes) in entries {
+        zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+        zip.write_all(bytes).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+/// Writes a zip archive of `entries` to `path`, creating its parent.
+pub fn write_zip(path: &Path, entries: &[(&str, &[u8])]) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, zip_bytes(entries)).unwrap();
+}
+
+/// Returns a mod dependency on `id` with the given version requirements.
+pub fn dependency(id: &str, versions: &[&str]) -> ModDependency {
+    ModDependency {
+        id: id.to_string(),
+        versions: versions.iter().map(|v| v.to_string()).collect(),
+    }
+}
+
+fn handle(stream: TcpStream, state: &Mutex<State>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("GET").to_string();
+    let target = parts.next().unwrap_or("/").to_string();
+    let mut range_start = None;
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+            break;
+        }
+        if let Some((name, value)) = line.trim_end().split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+            if name.eq_ignore_ascii_case("range") {
+                range_start = value
+                    .trim()
+                    .strip_prefix("bytes=")
+                    .and_then(|range| range.strip_suffix('-'))
+                    .and_then(|start| start.parse::<u64>().ok());
+            }
+        }
+    }
+
+    let mut request_body = vec![0; content_length];
+    if reader.read_exact(&mut request_body).is_err() {
+        return;
+    }
+
+    let (status, body, content_range) = {
+        let mut state = state.lock().unwrap();
+        state.requests.push((
+            format!("{method} {target}"),
+            String::from_utf8_lossy(&request_body).into_owned(),
+        ));
+        let path = if state.routes.contains_key(&target) {
+            target
+        } else {
+            target.split('?').next().unwrap_or("/").to_string()
+        };
+        let hits = {
+            let hits = state.hits.entry(path.clone()).or_default();
+            *hits += 1;
+            *hits
+        };
+        match state.routes.get(&path).cloned() {
+            None => (404, b"not found".to_vec(), None),
+            Some(route) if hits <= route.fail_first => (500, b"try again".to_vec(), None),
+            Some(route) => match range_start {
+                Some(start) if route.ranges && (start as usize) < route.body.len() => {
+                    state.ranges.push((path.clone(), start));
+                    let total = route.body.len();
+                    (
+                        206,
+                        route.body[start as usize..].to_vec(),
+                        Some(format!("bytes {start}-{}/{total}", total - 1)),
+                    )
+                }
+                _ => (route.status, route.body, None),
+            },
+        }
+    };
+
+    let mut stream = stream;
+    let mut head = format!(
+        "HTTP/1.1 {status} Stand-In\r\nContent-Length: {}\r\nConnection: close\r\n",
+        body.len()
+    );
+    if let Some(content_range) = content_range {
+        head.push_str(&format!("Content-Range: {content_range}\r\n"));
+    }
+    head.push_str("\r\n");
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(&body);
+}
@@ -0,0 +1,94 @@
+mod support;
+
+use std::{fs, path::Path};
+
+use mc_launcher_core::{
+    install::uninstall::{dependent_versions, DependentsPolicy, UninstallOptions},
+    prelude::*,
+};
+use support::write_profile;
+
+fn setup(root: &Path) {
+    write_profile(
+        root,
+        "1.20.1",
+        r#"{"id":"1.20.1","libraries":[{"name":"com.example:demo:1.0","url":"https://example.invalid/"}]}"#,
+    );
+    write_profile(
+        root,
+        "fabric-loader-0.15.0-1.20.1",
+        r#"{"id":"fabric-loader-0.15.0-1.20.1","inheritsFrom":"1.20.1","libraries":[{"name":"net.fabricmc:fabric-loader:0.15.0","url":"https://example.invalid/"}]}"#,
+    );
+    write_profile(
+        root,
+        "custom",
+        r#"{"id":"custom","inheritsFrom":"fabric-loader-0.15.0-1.20.1"}"#,
+    );
+    write_profile(root, "1.19.4", r#"{"id":"1.19.4"}"#);
+    let library = root.join("libraries/net/fabricmc/fabric-loader/0.15.0");
+    fs::create_dir_all(&library).unwrap();
+    fs::write(library.join("fabric-loader-0.15.0.jar"), b"loader").unwrap();
+}
+
+#[test]
+fn refuses_to_remove_an_inherited_version() {
+    let dir = tempfile::tempdir().unwrap();
+    setup(dir.path());
+
+    assert_eq!(
+        dependent_versions(dir.path(), "1.20.1").unwrap(),
+        ["custom", "fabric-loader-0.15.0-1.20.1"]
+    );
+    let err = Launcher::new(dir.path()).uninstall("1.20.1").unwrap_err();
+
+    assert!(
+        matches!(&err, LauncherError::VersionInUse { id, dependents } if id == "1.20.1" && dependents.len() == 2),
+        "{err}"
+    );
+    assert!(dir.path().join("versions/1.20.1").is_dir());
+    assert!(matches!(
+        Launcher::new(dir.path()).uninstall("1.8.9"),
+        Err(LauncherError::InvalidVersionId { .. })
+    ));
+    assert!(Launcher::new(dir.path()).uninstall("..").is_err());
+    assert!(Launcher::new(dir.path()).uninstall(".").is_err());
+}
+
+#[test]
+fn removes_leaf_profile_and_keeps_shared_files() {
+    let dir = tempfile::tempdir().unwrap();
+    setup(dir.path());
+
+    let result = Launcher::new(dir.path()).uninstall("custom").unwrap();
+
+    assert_eq!(result.removed, ["custom"]);
+    assert_eq!(result.garbage, None);
+    assert!(!dir.path().join("versions/custom").exists());
+    assert!(dir.path().join("versions/1.20.1").is_dir());
+}
+
+#[test]
+fn cascades_and_collects_garbage() {
+    let dir = tempfile::tempdir().unwrap();
+    setup(dir.path());
+
+    let result = Launcher::new(dir.path())
+        .uninstall_with_options(
+            "1.20.1",
+            &UninstallOptions {
+                dependents: DependentsPolicy::Cascade,
+                collect_garbage: true,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        result.removed,
+        ["custom", "fabric-loader-0.15.0-1.20.1", "1.20.1"]
+    );
+    let garbage = result.garbage.unwrap();
+    assert_eq!(garbage.versions, ["1.19.4"]);
+    assert_eq!(garbage.reclaimable_bytes(), 6);
+    assert!(!dir.path().join("libraries/net").exists());
+    assert!(dir.path().join("versions/1.19.4").is_dir());
+}